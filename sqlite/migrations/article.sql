@@ -8,37 +8,29 @@ CREATE TABLE IF NOT EXISTS article (
     status              TEXT    NOT NULL,
     created_at          INTEGER NOT NULL,
     updated_at          INTEGER NOT NULL,
-    published_at        INTEGER
+    published_at        INTEGER,
+    pinned_at           INTEGER,
+    featured            INTEGER NOT NULL DEFAULT 0
 );
 
 CREATE INDEX IF NOT EXISTS idx_published_at ON article (published_at DESC);
 CREATE INDEX IF NOT EXISTS idx_status_published_at ON article (status, published_at DESC);
 
+-- 文章的全文索引，由程序写入，以便使用 FTS5 内置分词器时预先切分中日韩文字
 CREATE VIRTUAL TABLE IF NOT EXISTS article_fts USING fts5 (
     id UNINDEXED,
     title,
     excerpt,
     plain_content,
-    content='article',
     tokenize='simple'
 );
 
-CREATE TRIGGER IF NOT EXISTS article_after_insert AFTER INSERT ON article
-BEGIN
-    INSERT INTO article_fts (rowid, id, title, excerpt, plain_content)
-    VALUES (new.rowid, new.id, new.title, new.excerpt, new.plain_content);
-END;
-
-CREATE TRIGGER IF NOT EXISTS article_after_delete AFTER DELETE ON article
-BEGIN
-    INSERT INTO article_fts (article_fts, rowid, id, title, excerpt, plain_content)
-    VALUES ('delete', old.rowid, old.id, old.title, old.excerpt, old.plain_content);
-END;
+-- 早期版本通过触发器同步外部内容表的全文索引
+DROP TRIGGER IF EXISTS article_after_insert;
+DROP TRIGGER IF EXISTS article_after_update;
+DROP TRIGGER IF EXISTS article_after_delete;
 
-CREATE TRIGGER IF NOT EXISTS article_after_update AFTER UPDATE ON article
+CREATE TRIGGER IF NOT EXISTS article_fts_after_delete AFTER DELETE ON article
 BEGIN
-    INSERT INTO article_fts (article_fts, rowid, id, title, excerpt, plain_content)
-    VALUES ('delete', old.rowid, old.id, old.title, old.excerpt, old.plain_content);
-    INSERT INTO article_fts (rowid, id, title, excerpt, plain_content)
-    VALUES (new.rowid, new.id, new.title, new.excerpt, new.plain_content);
+    DELETE FROM article_fts WHERE rowid = old.rowid;
 END;
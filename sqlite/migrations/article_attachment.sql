@@ -6,3 +6,17 @@ CREATE TABLE IF NOT EXISTS article_attachment (
 );
 
 CREATE INDEX IF NOT EXISTS idx_article_id ON article_attachment (article_id);
+
+-- 附件文本的全文索引，列名与 article_fts 保持一致，以便使用相同的搜索表达式
+CREATE VIRTUAL TABLE IF NOT EXISTS article_attachment_fts USING fts5 (
+    attachment_id UNINDEXED,
+    article_id UNINDEXED,
+    title,
+    plain_content,
+    tokenize='simple'
+);
+
+CREATE TRIGGER IF NOT EXISTS article_attachment_after_delete AFTER DELETE ON article_attachment
+BEGIN
+    DELETE FROM article_attachment_fts WHERE rowid = old.rowid;
+END;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS article_daily_referrer (
+    article_id      TEXT    NOT NULL,
+    date            TEXT    NOT NULL,
+    domain          TEXT    NOT NULL,
+    visits          INTEGER NOT NULL,
+    PRIMARY KEY (article_id, date, domain)
+);
+
+CREATE INDEX IF NOT EXISTS idx_article_daily_referrer_date ON article_daily_referrer (date);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS article_daily_stats (
+    article_id      TEXT    NOT NULL,
+    date            TEXT    NOT NULL,
+    pv              INTEGER NOT NULL,
+    uv              INTEGER NOT NULL,
+    PRIMARY KEY (article_id, date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_article_daily_stats_date ON article_daily_stats (date);
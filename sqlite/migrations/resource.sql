@@ -1,13 +1,14 @@
 CREATE TABLE IF NOT EXISTS resource (
-    id          TEXT    PRIMARY KEY,
-    name        TEXT    NOT NULL,
-    extension   TEXT    NOT NULL,
-    path        TEXT    NOT NULL,
-    size        INTEGER NOT NULL,
-    mime_type   TEXT    NOT NULL,
-    is_public   INTEGER NOT NULL,
-    sha256      TEXT    NOT NULL,
-    created_at  INTEGER NOT NULL
+    id                  TEXT    PRIMARY KEY,
+    name                TEXT    NOT NULL,
+    extension           TEXT    NOT NULL,
+    path                TEXT    NOT NULL,
+    size                INTEGER NOT NULL,
+    mime_type           TEXT    NOT NULL,
+    detected_mime_type  TEXT    NOT NULL,
+    is_public           INTEGER NOT NULL,
+    sha256              TEXT    NOT NULL,
+    created_at          INTEGER NOT NULL
 );
 
 CREATE INDEX IF NOT EXISTS idx_sha256_size_created_at ON resource (sha256, size, created_at DESC);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS resource_stats (
+    resource_id     TEXT    PRIMARY KEY,
+    downloads       INTEGER NOT NULL,
+    uv              INTEGER NOT NULL,
+    bytes_served    INTEGER NOT NULL,
+    updated_at      INTEGER NOT NULL
+);
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS series (
+    id              TEXT    PRIMARY KEY,
+    title           TEXT    NOT NULL,
+    description     TEXT    NOT NULL,
+    created_at      INTEGER NOT NULL,
+    updated_at      INTEGER NOT NULL
+);
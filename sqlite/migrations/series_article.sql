@@ -0,0 +1,10 @@
+-- 系列中的文章，一篇文章最多属于一个系列
+CREATE TABLE IF NOT EXISTS series_article (
+    series_id       TEXT    NOT NULL,
+    article_id      TEXT    NOT NULL,
+    position        INTEGER NOT NULL,
+    PRIMARY KEY (series_id, article_id)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_series_article_article_id ON series_article (article_id);
+CREATE INDEX IF NOT EXISTS idx_series_article_series_id_position ON series_article (series_id, position);
@@ -3,16 +3,17 @@ use boluo::data::Json;
 use boluo::extract::Path;
 use boluo::request::Request;
 use boluo::response::IntoResponse;
-use boluo::static_file::ServeFile;
 
 use crate::context::auth::Admin;
 use crate::context::db::DbPoolConnection;
+use crate::context::visitor::VisitorCookie;
 use crate::error::AppErrorMeta;
 use crate::model::bo::visitor::VisitorBo;
 use crate::model::dto::api::article::{
-    ArticleAttachmentDto, ArticleDetailsDto, ArticleListDto, CreateArticleDto,
-    DownloadArticleAttachmentDto, GetArticleDto, RemoveArticleAttachmentDto, RemoveArticleDto,
-    SearchArticleDto, UnlockArticleDto, UpdateArticleDto, UploadArticleAttachmentDto,
+    ArticleAnalyticsDto, ArticleAttachmentDto, ArticleDetailsDto, ArticleListDto, CreateArticleDto,
+    DownloadArticleAttachmentDto, GetArticleAnalyticsDto, GetArticleDto,
+    RemoveArticleAttachmentDto, RemoveArticleDto, SearchArticleDto, UnlockArticleDto,
+    UpdateArticleDto, UploadArticleAttachmentDto,
 };
 use crate::validator::Validation;
 
@@ -24,7 +25,9 @@ pub async fn unlock_article(
 ) -> Result<impl IntoResponse, BoxError> {
     params.validate(&())?;
     crate::service::article::unlock_article(&visitor, &params.into(), &mut db).await?;
-    Ok(crate::response::ok(()))
+    // 文章访问许可与访客绑定，需要确保访客持有访客 Cookie
+    let cookie = VisitorCookie::persist(&visitor).await?;
+    Ok((cookie, crate::response::ok(())))
 }
 
 #[boluo::route("/article/create", method = "POST")]
@@ -91,6 +94,18 @@ pub async fn get_article(
     Ok(crate::response::ok(ArticleDetailsDto::from(details)))
 }
 
+#[boluo::route("/article/analytics", method = "POST")]
+pub async fn get_article_analytics(
+    _: Admin,
+    Json(params): Json<GetArticleAnalyticsDto>,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    let analytics =
+        crate::service::article_stats::get_article_analytics(&params.into(), &mut db).await?;
+    Ok(crate::response::ok(ArticleAnalyticsDto::from(analytics)))
+}
+
 #[boluo::route("/article/upload_attachment", method = "POST")]
 pub async fn upload_attachment(
     _: Admin,
@@ -132,14 +147,5 @@ pub async fn download_attachment(
     else {
         return Err(AppErrorMeta::HttpNotFound.into_error().into());
     };
-    let response = ServeFile::new(resource.path).call(request).await?;
-    let encoded_filename = urlencoding::encode(&resource.name);
-    let response_headers = [
-        ("Content-Type", resource.mime_type),
-        (
-            "Content-Disposition",
-            format!("filename={encoded_filename}"),
-        ),
-    ];
-    (response_headers, response).into_response()
+    super::resource::serve_resource(resource, &visitor, &request).await
 }
@@ -1,4 +1,5 @@
 pub mod article;
 pub mod auth;
 pub mod resource;
+pub mod series;
 pub mod system;
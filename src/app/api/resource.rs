@@ -1,16 +1,24 @@
 use boluo::BoxError;
+use boluo::body::Body;
 use boluo::data::Json;
-use boluo::extract::Path;
+use boluo::http::header::{
+    ACCEPT_RANGES, CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, ETAG,
+    IF_NONE_MATCH, IF_RANGE, RANGE, X_CONTENT_TYPE_OPTIONS,
+};
+use boluo::http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
 use boluo::request::Request;
-use boluo::response::IntoResponse;
-use boluo::static_file::ServeFile;
+use boluo::response::{IntoResponse, Response};
 
 use crate::context::auth::Admin;
 use crate::context::db::DbPoolConnection;
 use crate::error::AppErrorMeta;
+use crate::model::bo::visitor::VisitorBo;
 use crate::model::dto::api::resource::{
-    DownloadResourceDto, RemoveResourceDto, ResourceDto, UploadResourceDto,
+    DownloadResourceDto, GetResourceStatsDto, RemoveResourceDto, ResourceDto, ResourceStatsDto,
+    SignResourceDto, SignedResourceDto, UploadResourceDto,
 };
+use crate::model::po::resource::ResourcePo;
+use crate::util::body::StreamBody;
 use crate::validator::Validation;
 
 #[boluo::route("/resource/upload", method = "POST")]
@@ -26,28 +34,40 @@ pub async fn upload_resource(
 
 #[boluo::route("/resources/{resource_id}", method = "GET")]
 pub async fn download_resource(
-    Path(params): Path<DownloadResourceDto>,
+    visitor: VisitorBo,
+    params: DownloadResourceDto,
     DbPoolConnection(mut db): DbPoolConnection,
     request: Request,
 ) -> Result<impl IntoResponse, BoxError> {
     params.validate(&())?;
-    let Some(resource) = crate::service::resource::find_resource(&params.into(), &mut db).await?
+    let Some(resource) =
+        crate::service::resource::download_resource(&params.into(), &mut db).await?
     else {
         return Err(AppErrorMeta::NotFound.into_error().into());
     };
-    if !resource.is_public {
-        return Err(AppErrorMeta::NotFound.into_error().into());
-    }
-    let response = ServeFile::new(resource.path).call(request).await?;
-    let encoded_filename = urlencoding::encode(&resource.name);
-    let response_headers = [
-        ("Content-Type", resource.mime_type),
-        (
-            "Content-Disposition",
-            format!("filename={encoded_filename}"),
-        ),
-    ];
-    (response_headers, response).into_response()
+    serve_resource(resource, &visitor, &request).await
+}
+
+#[boluo::route("/resource/sign", method = "POST")]
+pub async fn sign_resource(
+    _: Admin,
+    Json(params): Json<SignResourceDto>,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    let signed = crate::service::resource::sign_resource(&params.into(), &mut db).await?;
+    Ok(crate::response::ok(SignedResourceDto::from(signed)))
+}
+
+#[boluo::route("/resource/stats", method = "POST")]
+pub async fn get_resource_stats(
+    _: Admin,
+    Json(params): Json<GetResourceStatsDto>,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    let stats = crate::service::resource_stats::get_resource_stats(&params.into(), &mut db).await?;
+    Ok(crate::response::ok(ResourceStatsDto::from(stats)))
 }
 
 #[boluo::route("/resource/remove", method = "POST")]
@@ -60,3 +80,104 @@ pub async fn remove_resource(
     crate::service::resource::remove_resource(&params.into(), &mut db).await?;
     Ok(crate::response::ok(()))
 }
+
+/// 响应资源文件的下载请求，支持按字节区间读取
+pub(super) async fn serve_resource(
+    resource: ResourcePo,
+    visitor: &VisitorBo,
+    request: &Request,
+) -> Result<Response, BoxError> {
+    let etag = format!("\"{}\"", resource.sha256);
+    let headers = request.headers();
+
+    if header_matches_etag(headers, IF_NONE_MATCH, &etag) {
+        let mut response = StatusCode::NOT_MODIFIED.into_response_always();
+        insert_header(&mut response, ETAG, etag)?;
+        return Ok(response);
+    }
+
+    let range = match headers.get(RANGE).and_then(|v| v.to_str().ok()) {
+        Some(range)
+            if !headers.contains_key(IF_RANGE) || header_matches_etag(headers, IF_RANGE, &etag) =>
+        {
+            crate::util::range::parse(range, resource.size)
+        }
+        _ => Ok(None),
+    };
+    let range = match range {
+        Ok(range) => range,
+        Err(e) => {
+            let mut response = StatusCode::RANGE_NOT_SATISFIABLE.into_response_always();
+            insert_header(
+                &mut response,
+                CONTENT_RANGE,
+                crate::util::range::content_range(&(0..0), e.size),
+            )?;
+            return Ok(response);
+        }
+    };
+    let is_partial = range.is_some();
+
+    let Some(object) = crate::service::resource::open_resource(&resource, range).await? else {
+        return Err(AppErrorMeta::HttpNotFound.into_error().into());
+    };
+    let content_length = object.range.end - object.range.start;
+    // 爬虫与拒绝跟踪的访客的下载不计入统计
+    let stream = match visitor.is_tracked() {
+        true => crate::service::resource_stats::track_download(
+            &resource.id,
+            visitor.stats_id(),
+            &object.range,
+            object.stream,
+        ),
+        false => object.stream,
+    };
+    let body = StreamBody::new(stream).with_size(content_length);
+
+    let mut response = Response::new(Body::new(body));
+    if is_partial {
+        *response.status_mut() = StatusCode::PARTIAL_CONTENT;
+        insert_header(
+            &mut response,
+            CONTENT_RANGE,
+            crate::util::range::content_range(&object.range, object.size),
+        )?;
+    }
+    // 声明类型或实际内容可能被浏览器当作页面执行时，强制以附件形式下载
+    let disposition = if crate::util::mime::is_inline_safe(&resource.mime_type)
+        && (crate::util::mime::is_inline_safe(&resource.detected_mime_type)
+            || resource.detected_mime_type == mime::APPLICATION_OCTET_STREAM.as_ref())
+    {
+        "inline"
+    } else {
+        "attachment"
+    };
+    insert_header(
+        &mut response,
+        CONTENT_DISPOSITION,
+        crate::util::http::content_disposition(disposition, &resource.name),
+    )?;
+    insert_header(&mut response, CONTENT_TYPE, resource.mime_type)?;
+    insert_header(&mut response, X_CONTENT_TYPE_OPTIONS, "nosniff".to_owned())?;
+    insert_header(&mut response, CONTENT_LENGTH, content_length.to_string())?;
+    insert_header(&mut response, ACCEPT_RANGES, "bytes".to_owned())?;
+    insert_header(&mut response, ETAG, etag)?;
+    Ok(response)
+}
+
+fn header_matches_etag(headers: &HeaderMap, name: HeaderName, etag: &str) -> bool {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',')
+                .any(|tag| tag.trim().trim_start_matches("W/") == etag)
+        })
+}
+
+fn insert_header(response: &mut Response, name: HeaderName, value: String) -> Result<(), BoxError> {
+    response
+        .headers_mut()
+        .insert(name, HeaderValue::try_from(value)?);
+    Ok(())
+}
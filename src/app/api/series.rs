@@ -0,0 +1,86 @@
+use boluo::BoxError;
+use boluo::data::Json;
+use boluo::response::IntoResponse;
+
+use crate::context::auth::Admin;
+use crate::context::db::DbPoolConnection;
+use crate::error::AppErrorMeta;
+use crate::model::dto::api::series::{
+    CreateSeriesDto, GetSeriesDto, RemoveSeriesDto, SeriesDetailsDto, SeriesListItemDto,
+    SetSeriesArticlesDto, UpdateSeriesDto,
+};
+use crate::validator::Validation;
+
+#[boluo::route("/series/create", method = "POST")]
+pub async fn create_series(
+    _: Admin,
+    Json(params): Json<CreateSeriesDto>,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    let series = crate::service::series::create_series(params.into(), &mut db).await?;
+    Ok(crate::response::ok(serde_json::json!({
+        "id": series.series_id
+    })))
+}
+
+#[boluo::route("/series/update", method = "POST")]
+pub async fn update_series(
+    _: Admin,
+    Json(params): Json<UpdateSeriesDto>,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    crate::service::series::update_series(params.into(), &mut db).await?;
+    Ok(crate::response::ok(()))
+}
+
+#[boluo::route("/series/remove", method = "POST")]
+pub async fn remove_series(
+    _: Admin,
+    Json(params): Json<RemoveSeriesDto>,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    crate::service::series::remove_series(&params.into(), &mut db).await?;
+    Ok(crate::response::ok(()))
+}
+
+#[boluo::route("/series/set_articles", method = "POST")]
+pub async fn set_series_articles(
+    _: Admin,
+    Json(params): Json<SetSeriesArticlesDto>,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    crate::service::series::set_series_articles(&params.into(), &mut db).await?;
+    Ok(crate::response::ok(()))
+}
+
+#[boluo::route("/series/list", method = "POST")]
+pub async fn list_series(
+    _: Admin,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    let list = crate::service::series::list_series(&mut db).await?;
+    let list = list
+        .into_iter()
+        .map(SeriesListItemDto::from)
+        .collect::<Vec<_>>();
+    Ok(crate::response::ok(list))
+}
+
+#[boluo::route("/series/get", method = "POST")]
+pub async fn get_series(
+    admin: Option<Admin>,
+    Json(params): Json<GetSeriesDto>,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    let Some(details) =
+        crate::service::series::get_series(admin.as_deref(), &params.into(), &mut db).await?
+    else {
+        return Err(AppErrorMeta::NotFound.with_message("系列不存在").into());
+    };
+    Ok(crate::response::ok(SeriesDetailsDto::from(details)))
+}
@@ -6,7 +6,11 @@ use boluo::data::{Extension, Json};
 use boluo::response::IntoResponse;
 
 use crate::context::auth::Admin;
-use crate::model::dto::api::system::{InfoDto, SetLogLevelDto, SetShutdownTimeoutDto};
+use crate::context::db::DbPoolConnection;
+use crate::model::dto::api::system::{
+    CacheEntryDto, CacheKindStatsDto, InfoDto, InspectCacheDto, RemoveCacheDto,
+    SearchIndexCheckDto, SetLogLevelDto, SetShutdownTimeoutDto,
+};
 use crate::state::AppState;
 use crate::validator::Validation;
 
@@ -52,3 +56,72 @@ pub async fn info(
     let info = crate::service::system::info(state).await?;
     Ok(crate::response::ok(InfoDto::from(info)))
 }
+
+#[boluo::route("/system/cache_stats", method = "POST")]
+pub async fn cache_stats(_: Admin) -> Result<impl IntoResponse, BoxError> {
+    let stats = crate::service::system::cache_stats().await?;
+    Ok(crate::response::ok(
+        stats
+            .into_iter()
+            .map(CacheKindStatsDto::from)
+            .collect::<Vec<_>>(),
+    ))
+}
+
+#[boluo::route("/system/cache_inspect", method = "POST")]
+pub async fn cache_inspect(
+    _: Admin,
+    Json(params): Json<InspectCacheDto>,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    let cache = crate::service::system::inspect_cache(&params.into()).await?;
+    Ok(crate::response::ok(CacheEntryDto::from(cache)))
+}
+
+#[boluo::route("/system/cache_remove", method = "POST")]
+pub async fn cache_remove(
+    _: Admin,
+    Json(params): Json<RemoveCacheDto>,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    let rows = crate::service::system::remove_cache(&params.into()).await?;
+    Ok(crate::response::ok(serde_json::json!({ "rows": rows })))
+}
+
+#[boluo::route("/system/cache_purge_expired", method = "POST")]
+pub async fn cache_purge_expired(_: Admin) -> Result<impl IntoResponse, BoxError> {
+    let rows = crate::service::system::purge_expired_cache().await?;
+    Ok(crate::response::ok(serde_json::json!({ "rows": rows })))
+}
+
+#[boluo::route("/system/search_index_rebuild", method = "POST")]
+pub async fn search_index_rebuild(
+    _: Admin,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    crate::service::system::rebuild_search_index(&mut db).await?;
+    Ok(crate::response::ok(()))
+}
+
+#[boluo::route("/system/search_index_optimize", method = "POST")]
+pub async fn search_index_optimize(
+    _: Admin,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    crate::service::system::optimize_search_index(&mut db).await?;
+    Ok(crate::response::ok(()))
+}
+
+#[boluo::route("/system/search_index_check", method = "POST")]
+pub async fn search_index_check(
+    _: Admin,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    let results = crate::service::system::check_search_index(&mut db).await?;
+    Ok(crate::response::ok(
+        results
+            .into_iter()
+            .map(SearchIndexCheckDto::from)
+            .collect::<Vec<_>>(),
+    ))
+}
@@ -62,6 +62,9 @@ fn build_web() -> Router {
         .mount(web::rss::rss)
         .mount(web::home::home)
         .mount(web::about::about)
+        .mount(web::archive::archives)
+        .mount(web::archive::archive_year)
+        .mount(web::archive::archive_month)
         .mount(web::article::list)
         .mount(web::article::detail)
         .mount(web::article::unlock)
@@ -69,10 +72,12 @@ fn build_web() -> Router {
         .mount(web::article::create_submit)
         .mount(web::article::update)
         .mount(web::article::update_submit)
+        .mount(web::series::detail)
         .mount(web::auth::login)
         .mount(web::auth::login_submit)
         .mount(web::auth::logout)
         .mount(web::auth::logout_submit)
+        .mount(web::visitor::confirm)
 }
 
 fn build_api() -> Router {
@@ -83,11 +88,20 @@ fn build_api() -> Router {
         .mount(api::system::set_log_level)
         .mount(api::system::get_shutdown_timeout)
         .mount(api::system::set_shutdown_timeout)
+        .mount(api::system::cache_stats)
+        .mount(api::system::cache_inspect)
+        .mount(api::system::cache_remove)
+        .mount(api::system::cache_purge_expired)
+        .mount(api::system::search_index_rebuild)
+        .mount(api::system::search_index_optimize)
+        .mount(api::system::search_index_check)
         // 认证模块路由
         .mount(api::auth::login)
         // 资源模块路由
         .mount(api::resource::upload_resource)
         .mount(api::resource::remove_resource)
+        .mount(api::resource::sign_resource)
+        .mount(api::resource::get_resource_stats)
         // 文章模块路由
         .mount(api::article::create_article)
         .mount(api::article::update_article)
@@ -95,8 +109,16 @@ fn build_api() -> Router {
         .mount(api::article::unlock_article)
         .mount(api::article::search_article)
         .mount(api::article::get_article)
+        .mount(api::article::get_article_analytics)
         .mount(api::article::upload_attachment)
         .mount(api::article::remove_attachment)
+        // 系列模块路由
+        .mount(api::series::create_series)
+        .mount(api::series::update_series)
+        .mount(api::series::remove_series)
+        .mount(api::series::set_series_articles)
+        .mount(api::series::list_series)
+        .mount(api::series::get_series)
 }
 
 async fn handle_error(error: BoxError) -> Result<Response, Infallible> {
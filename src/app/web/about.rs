@@ -24,6 +24,7 @@ pub async fn about(
         let params = GetArticleBo {
             article_id: about_article_id.into(),
             ignore_status: true,
+            referrer: None,
         };
         let Some(article) =
             crate::service::article::get_article(admin.as_deref(), &visitor, &params, &mut db)
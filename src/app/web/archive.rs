@@ -0,0 +1,69 @@
+use std::sync::Arc;
+
+use boluo::BoxError;
+use boluo::data::Extension;
+use boluo::response::{Html, IntoResponse};
+
+use crate::context::auth::AdminFromCookie;
+use crate::context::db::DbPoolConnection;
+use crate::model::dto::web::article::{ArchiveArticleDto, SearchArticleDto};
+use crate::model::vo::article::{ArchivesVo, ArticleListVo};
+use crate::state::AppState;
+use crate::storage::db::DbPoolConn;
+use crate::template::render::PageContext;
+use crate::validator::Validation;
+
+#[boluo::route("/archives", method = ["GET"])]
+pub async fn archives(
+    admin: Option<AdminFromCookie>,
+    Extension(state): Extension<Arc<AppState>>,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    let years = crate::service::article::list_archives(&mut db).await?;
+    let context = PageContext::new(ArchivesVo::from(years)).admin(admin.map(Into::into));
+    Ok(Html(state.template.typed_render(&context)))
+}
+
+#[boluo::route("/archives/{year}", method = ["GET"])]
+pub async fn archive_year(
+    admin: Option<AdminFromCookie>,
+    params: ArchiveArticleDto,
+    Extension(state): Extension<Arc<AppState>>,
+    DbPoolConnection(db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    archive_list(admin, params, state, db).await
+}
+
+#[boluo::route("/archives/{year}/{month}", method = ["GET"])]
+pub async fn archive_month(
+    admin: Option<AdminFromCookie>,
+    params: ArchiveArticleDto,
+    Extension(state): Extension<Arc<AppState>>,
+    DbPoolConnection(db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    archive_list(admin, params, state, db).await
+}
+
+async fn archive_list(
+    admin: Option<AdminFromCookie>,
+    params: ArchiveArticleDto,
+    state: Arc<AppState>,
+    mut db: DbPoolConn,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    let list = crate::service::article::list_archive_articles(
+        admin.as_deref(),
+        &(&params).into(),
+        &mut db,
+    )
+    .await?;
+    let search = SearchArticleDto {
+        q: None,
+        sort: None,
+        page: params.page,
+        size: params.size,
+    };
+    let vo = ArticleListVo::from(list, search)?.with_archive(params.year, params.month);
+    let context = PageContext::new(vo).admin(admin.map(Into::into));
+    Ok(Html(state.template.typed_render(&context)))
+}
@@ -3,12 +3,14 @@ use std::sync::Arc;
 use boluo::BoxError;
 use boluo::data::{Extension, Form};
 use boluo::extract::{Path, TypedHeader};
-use boluo::headers::Referer;
+use boluo::headers::{Host, Referer};
 use boluo::response::{Html, IntoResponse, Redirect};
 
 use crate::context::auth::AdminFromCookie;
 use crate::context::db::DbPoolConnection;
+use crate::context::visitor::VisitorCookie;
 use crate::error::AppErrorMeta;
+use crate::model::bo::article::GetArticleBo;
 use crate::model::bo::visitor::VisitorBo;
 use crate::model::dto::web::article::{
     CreateArticleSubmitDto, GetArticleDto, SearchArticleDto, UnlockArticleDto, UpdateArticleDto,
@@ -41,18 +43,44 @@ pub async fn list(
 pub async fn detail(
     admin: Option<AdminFromCookie>,
     visitor: VisitorBo,
+    referer: Option<TypedHeader<Referer>>,
+    host: Option<TypedHeader<Host>>,
     Path(params): Path<GetArticleDto>,
     Extension(state): Extension<Arc<AppState>>,
     DbPoolConnection(mut db): DbPoolConnection,
 ) -> Result<impl IntoResponse, BoxError> {
     params.validate(&())?;
+    let mut params: GetArticleBo = params.into();
+    params.referrer = referer.and_then(|TypedHeader(referer)| {
+        let host = host.as_ref().map(|TypedHeader(host)| host.hostname());
+        crate::util::http::referrer_domain(&referer.to_string(), host).map(Into::into)
+    });
     let Some(article) =
-        crate::service::article::get_article(admin.as_deref(), &visitor, &params.into(), &mut db)
-            .await?
+        crate::service::article::get_article(admin.as_deref(), &visitor, &params, &mut db).await?
     else {
         return Err(AppErrorMeta::NotFound.with_message("文章不存在").into());
     };
+    let config = &crate::config::get().article;
+    let popular_articles = crate::service::article_stats::list_popular_articles(
+        config.popular_days,
+        config.widget_size,
+        &mut db,
+    )
+    .await?;
     let vo = ArticleDetailsVo::from(article);
+    let related_articles = crate::service::article::list_related_articles(
+        &vo.article_id,
+        &vo.title,
+        config.widget_size,
+        &mut db,
+    )
+    .await?;
+    let series =
+        crate::service::series::get_article_series(admin.as_deref(), &vo.article_id, &mut db)
+            .await?;
+    let vo = vo
+        .with_widgets(popular_articles, related_articles)
+        .with_series(series);
     let context = PageContext::new(vo).admin(admin.map(Into::into));
     Ok(Html(state.template.typed_render(&context)))
 }
@@ -66,7 +94,9 @@ pub async fn unlock(
 ) -> Result<impl IntoResponse, BoxError> {
     params.validate(&())?;
     crate::service::article::unlock_article(&visitor, &params.into(), &mut db).await?;
-    Ok(Redirect::to(&referer.to_string()))
+    // 文章访问许可与访客绑定，需要确保访客持有访客 Cookie
+    let cookie = VisitorCookie::persist(&visitor).await?;
+    Ok((cookie, Redirect::to(&referer.to_string())))
 }
 
 #[boluo::route("/articles/_create", method = ["GET"])]
@@ -5,6 +5,7 @@ use boluo::data::Extension;
 use boluo::response::{Html, IntoResponse};
 
 use crate::context::auth::AdminFromCookie;
+use crate::context::db::DbPoolConnection;
 use crate::model::vo::home::HomeVo;
 use crate::state::AppState;
 use crate::template::render::PageContext;
@@ -13,7 +14,18 @@ use crate::template::render::PageContext;
 pub async fn home(
     admin: Option<AdminFromCookie>,
     Extension(state): Extension<Arc<AppState>>,
+    DbPoolConnection(mut db): DbPoolConnection,
 ) -> Result<impl IntoResponse, BoxError> {
-    let context = PageContext::new(HomeVo).admin(admin.map(Into::into));
+    let config = &crate::config::get().article;
+    let featured_articles =
+        crate::service::article::list_featured_articles(config.widget_size, &mut db).await?;
+    let popular_articles = crate::service::article_stats::list_popular_articles(
+        config.popular_days,
+        config.widget_size,
+        &mut db,
+    )
+    .await?;
+    let vo = HomeVo::from_entities(featured_articles, popular_articles);
+    let context = PageContext::new(vo).admin(admin.map(Into::into));
     Ok(Html(state.template.typed_render(&context)))
 }
@@ -1,5 +1,8 @@
 pub mod about;
+pub mod archive;
 pub mod article;
 pub mod auth;
 pub mod home;
 pub mod rss;
+pub mod series;
+pub mod visitor;
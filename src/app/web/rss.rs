@@ -7,6 +7,7 @@ use boluo::response::IntoResponse;
 
 use crate::context::db::DbPoolConnection;
 use crate::model::bo::article::SearchArticleBo;
+use crate::model::common::article::ArticleSort;
 use crate::model::vo::rss::RssVo;
 use crate::state::AppState;
 use crate::template::render::PageContext;
@@ -21,8 +22,12 @@ pub async fn rss(
         status: None,
         published_at_ge: None,
         published_at_lt: None,
+        sort: Some(ArticleSort::Newest),
         page: Some(1),
         size: Some(20),
+        cursor: None,
+        with_total: false,
+        pinned_first: false,
     };
     let list = crate::service::article::search_article(None, &params, &mut db).await?;
     let vo = RssVo::from(list);
@@ -0,0 +1,32 @@
+use std::sync::Arc;
+
+use boluo::BoxError;
+use boluo::data::Extension;
+use boluo::extract::Path;
+use boluo::response::{Html, IntoResponse};
+
+use crate::context::auth::AdminFromCookie;
+use crate::context::db::DbPoolConnection;
+use crate::error::AppErrorMeta;
+use crate::model::dto::web::series::GetSeriesDto;
+use crate::model::vo::series::SeriesDetailsVo;
+use crate::state::AppState;
+use crate::template::render::PageContext;
+use crate::validator::Validation;
+
+#[boluo::route("/series/{series_id}", method = ["GET"])]
+pub async fn detail(
+    admin: Option<AdminFromCookie>,
+    Path(params): Path<GetSeriesDto>,
+    Extension(state): Extension<Arc<AppState>>,
+    DbPoolConnection(mut db): DbPoolConnection,
+) -> Result<impl IntoResponse, BoxError> {
+    params.validate(&())?;
+    let Some(series) =
+        crate::service::series::get_series(admin.as_deref(), &params.into(), &mut db).await?
+    else {
+        return Err(AppErrorMeta::NotFound.with_message("系列不存在").into());
+    };
+    let context = PageContext::new(SeriesDetailsVo::from(series)).admin(admin.map(Into::into));
+    Ok(Html(state.template.typed_render(&context)))
+}
@@ -0,0 +1,9 @@
+use boluo::BoxError;
+use boluo::http::StatusCode;
+use boluo::response::IntoResponse;
+
+/// 页面加载后由浏览器携带访客 Cookie 回传，访客中间件据此确认访客接受 Cookie
+#[boluo::route("/_visitor/confirm", method = ["POST"])]
+pub async fn confirm() -> Result<impl IntoResponse, BoxError> {
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -17,6 +17,8 @@ pub struct AppConfig {
     pub body_limit: BodyLimitConfig,
     /// 数据库配置
     pub database: DatabaseConfig,
+    /// 缓存配置
+    pub cache: CacheConfig,
     /// 资源配置
     pub resource: ResourceConfig,
     /// 管理员配置
@@ -29,6 +31,8 @@ pub struct AppConfig {
     pub cron: CronConfig,
     /// 文章配置
     pub article: ArticleConfig,
+    /// 访客配置
+    pub visitor: VisitorConfig,
 }
 
 impl AppConfig {
@@ -130,6 +134,25 @@ pub struct DatabaseConfig {
 pub struct DatabaseSqliteConfig {
     /// 扩展文件目录
     pub extensions_dir: String,
+    /// 全文索引使用的分词器
+    #[serde(default)]
+    pub fts_tokenizer: FtsTokenizer,
+    /// simple 分词器扩展无法加载时使用的备用分词器，为空时无法加载扩展将导致启动失败
+    #[serde(default)]
+    pub fts_fallback_tokenizer: Option<FtsTokenizer>,
+}
+
+/// 全文索引的分词器
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FtsTokenizer {
+    /// libsimple 扩展提供的中文与拼音分词器
+    #[default]
+    Simple,
+    /// FTS5 内置的三元组分词器，支持任意语言的子串匹配，少于 3 个字符的搜索词退化为逐行查找子串
+    Trigram,
+    /// FTS5 内置的 Unicode 分词器，按空白与标点分词，中日韩文字在写入索引与查询时逐字切分
+    Unicode61,
 }
 
 /// 数据库迁移配置
@@ -172,6 +195,36 @@ pub struct DatabasePoolConfig {
     pub max_lifetime: Duration,
 }
 
+/// 缓存配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CacheConfig {
+    /// 缓存存储后端
+    #[serde(default)]
+    pub backend: CacheBackendConfig,
+}
+
+/// 缓存存储后端配置
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CacheBackendConfig {
+    /// 数据库，缓存存储在 cache 表中
+    #[default]
+    Database,
+    /// 进程内存，重启后缓存数据丢失
+    Memory,
+    /// Redis 协议兼容服务
+    Redis(RedisCacheConfig),
+}
+
+/// Redis 缓存配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RedisCacheConfig {
+    /// 连接地址，例如 `redis://127.0.0.1:6379/0`
+    pub url: String,
+    /// 缓存键前缀，不能为空，统计与清理缓存时只会扫描此前缀下的键
+    pub key_prefix: String,
+}
+
 /// 资源文件配置
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ResourceConfig {
@@ -180,8 +233,87 @@ pub struct ResourceConfig {
     /// 上传文件的大小限制
     #[serde(with = "crate::util::serde::human_size")]
     pub upload_file_max_size: u64,
+    /// 上传文件的存储空间总配额，为空时不限制
+    #[serde(default, with = "crate::util::serde::human_size")]
+    pub quota: Option<u64>,
+    /// 公开资源的上传策略
+    #[serde(default)]
+    pub public: UploadPolicyConfig,
+    /// 文章附件的上传策略
+    #[serde(default)]
+    pub attachment: UploadPolicyConfig,
     /// 公开文件的存储目录
     pub public_dir: String,
+    /// 上传文件的存储后端
+    #[serde(default)]
+    pub storage: ResourceStorageConfig,
+    /// 签名下载链接的默认有效期
+    #[serde(with = "humantime_serde")]
+    pub signed_url_default_ttl: Duration,
+    /// 签名下载链接的最大有效期
+    #[serde(with = "humantime_serde")]
+    pub signed_url_max_ttl: Duration,
+}
+
+/// 上传文件策略配置
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct UploadPolicyConfig {
+    /// 单个文件的大小限制，为空时仅受 upload_file_max_size 限制
+    #[serde(default, with = "crate::util::serde::human_size")]
+    pub max_size: Option<u64>,
+    /// 允许上传的文件扩展名，为空时不限制
+    #[serde(default)]
+    pub allowed_extensions: Vec<String>,
+    /// 禁止上传的文件扩展名
+    #[serde(default)]
+    pub denied_extensions: Vec<String>,
+    /// 允许上传的文件类型，支持 `image/*` 形式的通配，为空时不限制
+    #[serde(default)]
+    pub allowed_mime_types: Vec<String>,
+    /// 禁止上传的文件类型，支持 `image/*` 形式的通配
+    #[serde(default)]
+    pub denied_mime_types: Vec<String>,
+    /// 配置了允许的文件类型时，是否允许上传无法根据内容识别具体类型的文件
+    ///
+    /// 此类文件的探测结果为 `text/plain` 或 `application/octet-stream`，与允许的文件类型匹配时总是允许上传。
+    #[serde(default)]
+    pub allow_unrecognized_content: bool,
+}
+
+/// 资源文件存储后端配置
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ResourceStorageConfig {
+    /// 本地文件系统，文件存储在 upload_dir 目录下
+    #[default]
+    Local,
+    /// S3 兼容对象存储
+    S3(S3StorageConfig),
+}
+
+/// S3 兼容对象存储配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct S3StorageConfig {
+    /// 服务地址，为空时使用 AWS 官方地址
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// 存储桶名称
+    pub bucket: String,
+    /// 区域
+    pub region: String,
+    /// 访问密钥ID
+    pub access_key_id: String,
+    /// 访问密钥
+    pub secret_access_key: String,
+    /// 对象键前缀
+    #[serde(default)]
+    pub prefix: String,
+    /// 是否允许使用 HTTP 协议访问
+    #[serde(default)]
+    pub allow_http: bool,
+    /// 是否使用虚拟主机风格的访问地址
+    #[serde(default)]
+    pub virtual_hosted_style: bool,
 }
 
 /// 管理员配置
@@ -306,8 +438,16 @@ pub struct ArticleConfig {
     /// 文章访问许可有效期
     #[serde(with = "humantime_serde")]
     pub access_access_ttl: Duration,
-    /// 全文搜索匹配结果最大输出条目
-    pub full_text_search_limit: u64,
+    /// 文章访问统计写入数据库的间隔
+    #[serde(with = "humantime_serde")]
+    pub stats_flush_interval: Duration,
+    /// 文章每日访问统计的保留时长
+    #[serde(with = "humantime_serde")]
+    pub daily_stats_retention: Duration,
+    /// 统计热门文章时使用最近多少天的访问量
+    pub popular_days: u64,
+    /// 热门文章、相关文章列表的最大条目
+    pub widget_size: u64,
     /// 文章标题最大长度限制
     #[serde(default, with = "crate::util::serde::human_size")]
     pub title_max_size: usize,
@@ -320,4 +460,68 @@ pub struct ArticleConfig {
     /// 作为 About 页面的文章
     #[serde(default)]
     pub about_article_id: Option<String>,
+    /// 提取文本建立全文索引的附件最大大小，超过此大小的附件不会被索引
+    #[serde(with = "crate::util::serde::human_size")]
+    pub attachment_index_max_size: usize,
+    /// 全文搜索相关度排序的字段权重
+    pub search_weights: ArticleSearchWeights,
+}
+
+/// 全文搜索相关度排序（BM25）的字段权重，权重越大，该字段的匹配对相关度的影响越大
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ArticleSearchWeights {
+    /// 标题
+    pub title: f64,
+    /// 摘要
+    pub excerpt: f64,
+    /// 正文
+    pub content: f64,
+    /// 附件名与附件文本
+    pub attachment: f64,
+}
+
+/// 访客配置
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VisitorConfig {
+    /// 识别爬虫的 User-Agent 正则表达式（不区分大小写），缺少 User-Agent 的请求同样视为爬虫
+    #[serde(default)]
+    pub bot_user_agents: Vec<String>,
+    /// 新访客接受 Cookie 的确认时限，超时未确认的访客及其访问记录会被丢弃，仅在使用 Cookie 识别访客时生效
+    #[serde(with = "humantime_serde")]
+    pub confirm_timeout: Duration,
+    /// 暂存访问记录的未确认访客数量上限，超出后新的未确认访客的访问不计入统计
+    pub max_unconfirmed_visitors: usize,
+    /// 隐私配置
+    #[serde(default)]
+    pub privacy: VisitorPrivacyConfig,
+}
+
+/// 访客隐私配置
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct VisitorPrivacyConfig {
+    /// 遵循 `DNT: 1` 与 `Sec-GPC: 1` 请求头，发送这些请求头的访客不计入访问统计，也不会被主动设置访客 Cookie
+    #[serde(default)]
+    pub honor_dnt: bool,
+    /// 独立访客的识别方式
+    #[serde(default)]
+    pub tracking: VisitorTrackingMode,
+    /// 获取客户端 IP 的请求头，部署在反向代理之后时配置，未配置时使用连接的对端地址
+    ///
+    /// 请求头包含多个地址时取最右侧、由最近一级代理添加的地址。
+    #[serde(default)]
+    pub client_ip_header: Option<String>,
+}
+
+/// 独立访客的识别方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VisitorTrackingMode {
+    /// 为每个访客设置持久的访客 Cookie
+    #[default]
+    Cookie,
+    /// 使用每日轮换的盐值对客户端信息计算哈希，仅在需要时（如解锁文章）设置访客 Cookie
+    ///
+    /// 此模式不要求访客确认接受 Cookie，因此不会过滤不接受 Cookie 的客户端，只能通过
+    /// [`VisitorConfig::bot_user_agents`] 识别爬虫。
+    Hash,
 }
@@ -1,20 +1,30 @@
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 
 use boluo::data::Extension;
 use boluo::extract::FromRequest;
-use boluo::http::header::SET_COOKIE;
+use boluo::http::HeaderMap;
+use boluo::http::header::{SET_COOKIE, USER_AGENT};
+use boluo::listener::ConnectInfo;
 use boluo::request::Request;
 use boluo::response::{IntoResponseParts, ResponseParts};
 use boluo_extra::cookie::{Cookie, CookieJar, SameSite};
 use time::OffsetDateTime;
 
+use crate::config::VisitorTrackingMode;
 use crate::error::{AppError, AppErrorMeta};
 use crate::model::bo::visitor::VisitorBo;
+use crate::model::common::visitor::VisitorKind;
 use crate::util::time::UnixTimestampSecs;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct VisitorId {
     visitor_id: Arc<str>,
+    kind: VisitorKind,
+    /// 是否拒绝跟踪（DNT / Sec-GPC）
+    do_not_track: bool,
+    /// 按每日轮换盐值计算的访客哈希
+    hash: Option<Arc<str>>,
 }
 
 impl VisitorId {
@@ -24,14 +34,74 @@ impl VisitorId {
         &self.visitor_id
     }
 
-    async fn from_cookies(jar: &CookieJar) -> anyhow::Result<Self> {
-        let visitor = match jar.get(Self::COOKIE_KEY).map(|cookie| cookie.value()) {
-            Some(visitor_id) => crate::service::visitor::keep_or_create_visitor(visitor_id).await?,
-            None => crate::service::visitor::create_visitor().await?,
-        };
-        Ok(VisitorId {
-            visitor_id: Arc::from(visitor.visitor_id()),
-        })
+    pub fn kind(&self) -> VisitorKind {
+        self.kind
+    }
+
+    pub fn do_not_track(&self) -> bool {
+        self.do_not_track
+    }
+
+    pub fn hash(&self) -> Option<&str> {
+        self.hash.as_deref()
+    }
+
+    /// 不写入缓存的访客，每次请求使用新的访客ID
+    fn transient(kind: VisitorKind) -> Self {
+        VisitorId {
+            visitor_id: Arc::from(crate::util::uuid::v4()),
+            kind,
+            do_not_track: false,
+            hash: None,
+        }
+    }
+
+    fn persisted(visitor_id: &str) -> Self {
+        VisitorId {
+            visitor_id: Arc::from(visitor_id),
+            kind: VisitorKind::Persisted,
+            do_not_track: false,
+            hash: None,
+        }
+    }
+
+    /// 从 Cookie 中恢复访客，`create` 为真时为没有有效访客 Cookie 的请求创建新访客
+    async fn from_cookies(jar: &CookieJar, create: bool) -> anyhow::Result<Self> {
+        if let Some(visitor_id) = jar.get(Self::COOKIE_KEY).map(|cookie| cookie.value())
+            && let Some(visitor) = crate::service::visitor::keep_visitor(visitor_id).await?
+        {
+            return Ok(Self::persisted(visitor.visitor_id()));
+        }
+        if !create {
+            return Ok(Self::transient(VisitorKind::Ephemeral));
+        }
+        let visitor = crate::service::visitor::create_visitor().await?;
+        Ok(Self::persisted(visitor.visitor_id()))
+    }
+
+    async fn from_headers(
+        headers: &HeaderMap,
+        peer_ip: Option<IpAddr>,
+        jar: &CookieJar,
+    ) -> anyhow::Result<Self> {
+        let user_agent = headers
+            .get(USER_AGENT)
+            .and_then(|value| value.to_str().ok());
+        if crate::service::visitor::is_bot(user_agent) {
+            return Ok(Self::transient(VisitorKind::Bot));
+        }
+
+        let privacy = &crate::config::get().visitor.privacy;
+        let do_not_track = crate::service::visitor::is_do_not_track(headers);
+        // 仅在使用 Cookie 识别访客且访客没有拒绝跟踪时，才主动创建访客
+        let create = privacy.tracking == VisitorTrackingMode::Cookie && !do_not_track;
+        let mut visitor = Self::from_cookies(jar, create).await?;
+        visitor.do_not_track = do_not_track;
+        if privacy.tracking == VisitorTrackingMode::Hash && !do_not_track {
+            let hash = crate::service::visitor::visitor_hash(headers, peer_ip).await?;
+            visitor.hash = Some(Arc::from(hash));
+        }
+        Ok(visitor)
     }
 
     fn build_cookie(&self) -> anyhow::Result<Cookie<'_>> {
@@ -69,7 +139,11 @@ impl FromRequest for VisitorId {
                     .with_message("Cookie 解析错误")
                     .with_source(e)
             })?;
-        let visitor = VisitorId::from_cookies(&jar).await?;
+        let peer_ip = request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        let visitor = VisitorId::from_headers(request.headers(), peer_ip, &jar).await?;
         request.extensions_mut().insert(visitor.clone());
         Ok(visitor)
     }
@@ -79,9 +153,39 @@ impl IntoResponseParts for VisitorId {
     type Error = AppError;
 
     fn into_response_parts(self, mut parts: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        // 只为已写入缓存的访客设置访客 Cookie
+        if self.kind != VisitorKind::Persisted {
+            return Ok(parts);
+        }
         if let Ok(value) = self.build_cookie()?.encoded().to_string().parse() {
             parts.headers.append(SET_COOKIE, value);
         }
         Ok(parts)
     }
 }
+
+/// 临时访客被持久化后需要设置的访客 Cookie
+#[derive(Debug, Clone)]
+pub struct VisitorCookie(Option<VisitorId>);
+
+impl VisitorCookie {
+    /// 在需要保存访客状态（如解锁文章）时持久化临时访客，返回需要设置的访客 Cookie
+    pub async fn persist(visitor: &VisitorBo) -> Result<Self, AppError> {
+        if visitor.kind() != VisitorKind::Ephemeral {
+            return Ok(Self(None));
+        }
+        let visitor = visitor.persist().await?;
+        Ok(Self(Some(VisitorId::persisted(visitor.visitor_id()))))
+    }
+}
+
+impl IntoResponseParts for VisitorCookie {
+    type Error = AppError;
+
+    fn into_response_parts(self, parts: ResponseParts) -> Result<ResponseParts, Self::Error> {
+        match self.0 {
+            Some(visitor) => visitor.into_response_parts(parts),
+            None => Ok(parts),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::state::AppState;
+use crate::util::time::UnixTimestampSecs;
+
+pub async fn prune_article_daily_stats(state: Arc<AppState>) -> anyhow::Result<()> {
+    let retention = crate::config::get().article.daily_stats_retention;
+    let Some(expired_before) = UnixTimestampSecs::now().checked_sub(retention) else {
+        return Ok(());
+    };
+    let date = expired_before.into_str_date_utc()?;
+
+    let mut db = state.db.acquire().await?;
+    let rows = crate::storage::db::article_daily_stats::remove_before(&date, &mut db).await?;
+    let referrer_rows =
+        crate::storage::db::article_daily_referrer::remove_before(&date, &mut db).await?;
+    tracing::info!(
+        "文章每日统计清理成功，清理 {rows} 条访问数据、{referrer_rows} 条来源数据（保留 {date} 及之后的数据）"
+    );
+    Ok(())
+}
+
+pub async fn optimize_search_index(state: Arc<AppState>) -> anyhow::Result<()> {
+    let mut db = state.db.acquire().await?;
+    crate::service::system::optimize_search_index(&mut db).await?;
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use crate::state::AppState;
+use crate::storage::cache::storage::AnyCacheStorage;
 
 pub async fn prune_db_table_cache(state: Arc<AppState>) -> anyhow::Result<()> {
     /// 每次清理缓存数据的条数上限
@@ -11,3 +12,12 @@ pub async fn prune_db_table_cache(state: Arc<AppState>) -> anyhow::Result<()> {
     tracing::info!("数据库缓存表清理成功，清理 {rows} 条数据");
     Ok(())
 }
+
+pub async fn prune_memory_cache(_: Arc<AppState>) -> anyhow::Result<()> {
+    let AnyCacheStorage::Memory(storage) = crate::storage::cache::storage::get() else {
+        return Ok(());
+    };
+    let rows = storage.remove_expired();
+    tracing::info!("内存缓存清理成功，清理 {rows} 条数据");
+    Ok(())
+}
@@ -1,3 +1,4 @@
+mod article;
 mod cache;
 
 use std::sync::Arc;
@@ -7,7 +8,11 @@ use crate::cron::core::task_function_name;
 use crate::state::AppState;
 
 pub fn build(state: Arc<AppState>) -> anyhow::Result<CronTaskCollector<Arc<AppState>>> {
-    CronTaskCollector::new(state).config_add(cache::prune_db_table_cache)
+    CronTaskCollector::new(state)
+        .config_add(cache::prune_db_table_cache)?
+        .config_add(cache::prune_memory_cache)?
+        .config_add(article::prune_article_daily_stats)?
+        .config_add(article::optimize_search_index)
 }
 
 impl CronTaskCollector<Arc<AppState>> {
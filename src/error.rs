@@ -116,6 +116,10 @@ pub enum AppErrorMeta {
     PermissionDenied,
     /// 数据大小超出限制
     DataTooLarge { limit: u64 },
+    /// 存储空间超出配额
+    StorageQuotaExceeded { quota: u64 },
+    /// 文件类型不允许上传
+    FileTypeNotAllowed,
     /// 文章已锁定
     ArticleLocked { article_id: String },
 }
@@ -154,6 +158,8 @@ impl AppErrorMeta {
             AppErrorMeta::AdminAccessTokenInvalid => "AdminAccessTokenInvalid",
             AppErrorMeta::PermissionDenied => "PermissionDenied",
             AppErrorMeta::DataTooLarge { .. } => "DataTooLarge",
+            AppErrorMeta::StorageQuotaExceeded { .. } => "StorageQuotaExceeded",
+            AppErrorMeta::FileTypeNotAllowed => "FileTypeNotAllowed",
             AppErrorMeta::ArticleLocked { .. } => "ArticleAccessTokenMissing",
         }
     }
@@ -172,6 +178,8 @@ impl AppErrorMeta {
             AppErrorMeta::AdminAccessTokenInvalid => StatusCode::UNAUTHORIZED,
             AppErrorMeta::PermissionDenied => StatusCode::FORBIDDEN,
             AppErrorMeta::DataTooLarge { .. } => StatusCode::PAYLOAD_TOO_LARGE,
+            AppErrorMeta::StorageQuotaExceeded { .. } => StatusCode::INSUFFICIENT_STORAGE,
+            AppErrorMeta::FileTypeNotAllowed => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             AppErrorMeta::ArticleLocked { .. } => StatusCode::UNAUTHORIZED,
         }
     }
@@ -192,6 +200,10 @@ impl AppErrorMeta {
             AppErrorMeta::DataTooLarge { limit } => {
                 format!("数据大小超出限制，最大允许 {}", ByteSize::b(*limit))
             }
+            AppErrorMeta::StorageQuotaExceeded { quota } => {
+                format!("存储空间不足，总配额为 {}", ByteSize::b(*quota))
+            }
+            AppErrorMeta::FileTypeNotAllowed => "文件类型不允许上传".into(),
             AppErrorMeta::ArticleLocked { .. } => "文章已锁定".into(),
         }
     }
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+use crate::util::jwt::JwtClaimsData;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ResourceDownloadJwtData {
+    /// 资源ID
+    pub resource_id: String,
+}
+
+impl JwtClaimsData for ResourceDownloadJwtData {
+    fn kind() -> &'static str {
+        "resource-download"
+    }
+}
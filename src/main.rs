@@ -38,6 +38,8 @@ async fn main() -> anyhow::Result<()> {
 
     tracing::debug!("{}", serde_json::to_string_pretty(config::get())?);
 
+    crate::storage::db::init_fts_tokenizer(&config::get().database).await?;
+
     let state = AppState::from_config(config::get())?;
 
     if config::get().database.migrations.auto_migrate {
@@ -45,16 +47,28 @@ async fn main() -> anyhow::Result<()> {
         crate::storage::db::init(&mut db).await?;
     }
 
-    crate::storage::cache::storage::init(state.db.clone())?;
+    crate::service::visitor::init(&config::get().visitor)?;
+    crate::storage::cache::storage::init(&config::get().cache, state.db.clone()).await?;
+    crate::storage::object::init(&config::get().resource)?;
+    crate::service::resource_stats::init(state.db.clone())?;
+    crate::service::article_stats::init(
+        state.db.clone(),
+        config::get().article.stats_flush_interval,
+    )?;
 
     cron::init(state.clone()).await?;
     cron::start().await?;
 
-    start_http_server(state).await?;
+    // HTTP 服务启动失败时也需要写入已缓冲的统计数据
+    let result = start_http_server(state).await;
+
+    crate::service::resource_stats::shutdown().await;
+    // 收到关闭信号时已经写入一次访问统计，这里写入等待活跃请求期间产生的访问记录
+    crate::service::article_stats::shutdown().await;
 
     cron::shutdown().await?;
 
-    Ok(())
+    result
 }
 
 async fn start_http_server(state: Arc<AppState>) -> anyhow::Result<()> {
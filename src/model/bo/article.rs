@@ -1,11 +1,18 @@
 use std::borrow::Cow;
 
-use crate::error::AppError;
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+
+use crate::error::{AppError, AppErrorMeta};
 use crate::model::bo::resource::{ResourceBo, UploadResourceBo};
-use crate::model::common::article::ArticleStatus;
-use crate::model::po::article::ArticlePo;
-use crate::model::po::article_attachment::ArticleAttachmentPo;
+use crate::model::common::article::{ArticleSort, ArticleStatus};
+use crate::model::po::article::{ArchiveMonth, ArticleCursor, ArticlePo, SearchArticleItem};
+use crate::model::po::article_attachment::{ArticleAttachmentPo, MatchedAttachment};
+use crate::model::po::article_daily_referrer::ReferrerVisits;
+use crate::model::po::article_daily_stats::{ArticleVisits, DailyVisits};
 use crate::model::po::article_stats::ArticleStatsPo;
+use crate::model::po::resource_stats::ResourceStatsPo;
+use crate::util::fts;
 use crate::util::pagination::{OptionalPage, Page, PageData};
 
 /// 解锁文章
@@ -43,6 +50,10 @@ pub struct UpdateArticleBo {
     pub password: Option<String>,
     /// 状态
     pub status: ArticleStatus,
+    /// 是否置顶，为空时保持不变
+    pub pinned: Option<bool>,
+    /// 是否为精选文章，为空时保持不变
+    pub featured: Option<bool>,
 }
 
 /// 删除文章
@@ -63,10 +74,18 @@ pub struct SearchArticleBo<'a> {
     pub published_at_ge: Option<i64>,
     /// 发布时间（小于）
     pub published_at_lt: Option<i64>,
+    /// 排序方式
+    pub sort: Option<ArticleSort>,
     /// 分页页码
     pub page: Option<u64>,
     /// 分页大小
     pub size: Option<u64>,
+    /// 游标分页的游标，指定时忽略分页页码
+    pub cursor: Option<Cow<'a, str>>,
+    /// 是否统计总数据条数
+    pub with_total: bool,
+    /// 是否将置顶文章排在最前
+    pub pinned_first: bool,
 }
 
 impl SearchArticleBo<'_> {
@@ -83,6 +102,31 @@ impl SearchArticleBo<'_> {
             .map(|v| v.trim())
             .filter(|v| !v.is_empty())
     }
+
+    /// 排序方式，未指定时全文搜索按相关度排序，否则按发布时间从新到旧排序
+    pub fn sort(&self) -> ArticleSort {
+        match (self.sort, self.trim_full_text()) {
+            (Some(ArticleSort::Relevance), None) => ArticleSort::Newest,
+            (Some(sort), _) => sort,
+            (None, Some(_)) => ArticleSort::Relevance,
+            (None, None) => ArticleSort::Newest,
+        }
+    }
+
+    /// 解析游标分页的游标，游标无效或排序方式不支持游标分页时返回错误
+    pub fn cursor(&self) -> Result<Option<ArticleCursor>, AppError> {
+        let Some(cursor) = self.cursor.as_deref() else {
+            return Ok(None);
+        };
+        let sort = self.sort();
+        if !ArticleCursor::supports(sort) || self.pinned_first {
+            return Err(AppErrorMeta::BadRequest.with_message("当前排序方式不支持游标分页"));
+        }
+        match crate::util::pagination::decode_cursor::<ArticleCursor>(cursor) {
+            Some(cursor) if cursor.sort == sort => Ok(Some(cursor)),
+            _ => Err(AppErrorMeta::BadRequest.with_message("分页游标无效")),
+        }
+    }
 }
 
 /// 文章列表项
@@ -104,8 +148,16 @@ pub struct ArticleListItemBo {
     pub updated_at: i64,
     /// 发布时间
     pub published_at: Option<i64>,
+    /// 是否置顶
+    pub pinned: bool,
     /// 是否需要密码访问
     pub need_password: bool,
+    /// 高亮匹配关键词的标题（HTML），仅全文搜索时存在
+    pub title_html: Option<String>,
+    /// 高亮匹配关键词的正文片段（HTML），仅全文搜索时存在
+    pub snippet_html: Option<String>,
+    /// 附件文本与搜索语句匹配的附件，仅全文搜索时存在
+    pub matched_attachments: Vec<MatchedAttachmentBo>,
 }
 
 impl From<ArticlePo> for ArticleListItemBo {
@@ -119,7 +171,134 @@ impl From<ArticlePo> for ArticleListItemBo {
             created_at: article.created_at,
             updated_at: article.updated_at,
             published_at: article.published_at,
+            pinned: article.pinned_at.is_some(),
             need_password: article.password.is_some(),
+            title_html: None,
+            snippet_html: None,
+            matched_attachments: vec![],
+        }
+    }
+}
+
+impl From<SearchArticleItem> for ArticleListItemBo {
+    fn from(item: SearchArticleItem) -> Self {
+        Self {
+            title_html: item.title_highlight.as_deref().map(fts::highlight_to_html),
+            snippet_html: item.snippet.as_deref().map(fts::highlight_to_html),
+            ..Self::from(item.article)
+        }
+    }
+}
+
+/// 全文搜索匹配的附件
+#[derive(Debug, Clone)]
+pub struct MatchedAttachmentBo {
+    /// 附件ID
+    pub attachment_id: String,
+    /// 附件名
+    pub name: String,
+}
+
+impl From<MatchedAttachment> for MatchedAttachmentBo {
+    fn from(value: MatchedAttachment) -> Self {
+        Self {
+            attachment_id: value.attachment_id,
+            name: value.name,
+        }
+    }
+}
+
+/// 列出归档文章
+#[derive(Debug, Clone)]
+pub struct ArchiveArticleBo {
+    /// 年份（UTC）
+    pub year: i16,
+    /// 月份（UTC），为空时列出全年的文章
+    pub month: Option<i8>,
+    /// 分页页码
+    pub page: Option<u64>,
+    /// 分页大小
+    pub size: Option<u64>,
+}
+
+impl ArchiveArticleBo {
+    /// 归档时段对应的发布时间范围（大于等于，小于）的 Unix 时间戳，
+    /// 时段截止到 9999-12-31 时不限制结束时间
+    pub fn published_at_range(&self) -> Result<(i64, Option<i64>), AppError> {
+        let invalid = |e| {
+            AppErrorMeta::BadRequest
+                .with_message("归档日期错误")
+                .with_source(e)
+        };
+        let start = Date::new(self.year, self.month.unwrap_or(1), 1).map_err(invalid)?;
+        let last = match self.month {
+            Some(_) => start.last_of_month(),
+            None => start.last_of_year(),
+        };
+        let timestamp = |date: Date| -> Result<i64, AppError> {
+            Ok(date
+                .to_zoned(TimeZone::UTC)
+                .map_err(invalid)?
+                .timestamp()
+                .as_second())
+        };
+        let end = if last == Date::MAX {
+            None
+        } else {
+            Some(timestamp(last.tomorrow().map_err(invalid)?)?)
+        };
+        Ok((timestamp(start)?, end))
+    }
+}
+
+/// 按年份分组的文章归档
+#[derive(Debug, Clone)]
+pub struct ArchiveYearBo {
+    /// 年份（UTC）
+    pub year: i64,
+    /// 全年的文章数量
+    pub count: u64,
+    /// 有文章发布的月份，按月份从新到旧排序
+    pub months: Vec<ArchiveMonthBo>,
+}
+
+impl ArchiveYearBo {
+    /// 将按月份从新到旧排序的统计数据按年份分组
+    pub fn from_months(months: Vec<ArchiveMonth>) -> Vec<Self> {
+        let mut years: Vec<Self> = vec![];
+        for month in months {
+            let year = match years.last_mut() {
+                Some(year) if year.year == month.year => year,
+                _ => {
+                    years.push(Self {
+                        year: month.year,
+                        count: 0,
+                        months: vec![],
+                    });
+                    years.last_mut().unwrap()
+                }
+            };
+            year.count += month.count;
+            year.months.push(ArchiveMonthBo::from(month));
+        }
+        years
+    }
+}
+
+/// 文章归档中的月份
+#[derive(Debug, Clone)]
+pub struct ArchiveMonthBo {
+    /// 月份（UTC）
+    pub month: i64,
+    /// 文章数量
+    pub count: u64,
+}
+
+impl From<ArchiveMonth> for ArchiveMonthBo {
+    fn from(value: ArchiveMonth) -> Self {
+        Self {
+            month: value.month,
+            count: value.count,
         }
     }
 }
@@ -131,6 +310,8 @@ pub struct ArticleListBo {
     pub data: PageData<Vec<ArticleListItemBo>>,
     /// 分页
     pub page: Page,
+    /// 下一页的游标，排序方式不支持游标分页或没有下一页时为空
+    pub next_cursor: Option<String>,
 }
 
 /// 获取文章详情
@@ -139,6 +320,8 @@ pub struct GetArticleBo<'a> {
     pub article_id: Cow<'a, str>,
     /// 忽视文章状态条件获取文章
     pub ignore_status: bool,
+    /// 来源域名
+    pub referrer: Option<Cow<'a, str>>,
 }
 
 /// 访客可见的文章详情
@@ -168,6 +351,8 @@ pub struct VisitorArticleDetailsBo {
     pub pv: u64,
     /// 累计独立访客数
     pub uv: u64,
+    /// 相邻文章
+    pub adjacent: AdjacentArticlesBo,
 }
 
 impl VisitorArticleDetailsBo {
@@ -175,6 +360,7 @@ impl VisitorArticleDetailsBo {
         article: ArticlePo,
         attachments: Vec<ArticleAttachmentBo>,
         stats: ArticleStatsPo,
+        adjacent: AdjacentArticlesBo,
     ) -> Self {
         Self {
             article_id: article.id,
@@ -189,6 +375,7 @@ impl VisitorArticleDetailsBo {
             attachments,
             pv: stats.pv,
             uv: stats.uv,
+            adjacent,
         }
     }
 }
@@ -214,6 +401,10 @@ pub struct AdminArticleDetailsBo {
     pub updated_at: i64,
     /// 发布时间
     pub published_at: Option<i64>,
+    /// 置顶时间，为空时未置顶
+    pub pinned_at: Option<i64>,
+    /// 是否为精选文章
+    pub featured: bool,
     /// 是否需要密码访问
     pub need_password: bool,
     /// 附件列表
@@ -222,6 +413,8 @@ pub struct AdminArticleDetailsBo {
     pub pv: u64,
     /// 累计独立访客数
     pub uv: u64,
+    /// 相邻文章
+    pub adjacent: AdjacentArticlesBo,
 }
 
 impl AdminArticleDetailsBo {
@@ -229,6 +422,7 @@ impl AdminArticleDetailsBo {
         article: ArticlePo,
         attachments: Vec<ArticleAttachmentBo>,
         stats: ArticleStatsPo,
+        adjacent: AdjacentArticlesBo,
     ) -> Self {
         Self {
             article_id: article.id,
@@ -239,15 +433,45 @@ impl AdminArticleDetailsBo {
             created_at: article.created_at,
             updated_at: article.updated_at,
             published_at: article.published_at,
+            pinned_at: article.pinned_at,
+            featured: article.featured,
             need_password: article.password.is_some(),
             password: article.password,
             attachments,
             pv: stats.pv,
             uv: stats.uv,
+            adjacent,
+        }
+    }
+}
+
+/// 文章链接
+#[derive(Debug, Clone)]
+pub struct ArticleLinkBo {
+    /// 文章ID
+    pub article_id: String,
+    /// 标题
+    pub title: String,
+}
+
+impl From<ArticlePo> for ArticleLinkBo {
+    fn from(article: ArticlePo) -> Self {
+        Self {
+            article_id: article.id,
+            title: article.title,
         }
     }
 }
 
+/// 按发布时间排序的相邻文章
+#[derive(Debug, Clone, Default)]
+pub struct AdjacentArticlesBo {
+    /// 上一篇（发布时间更早）
+    pub prev: Option<ArticleLinkBo>,
+    /// 下一篇（发布时间更晚）
+    pub next: Option<ArticleLinkBo>,
+}
+
 /// 文章详情
 #[derive(Debug, Clone)]
 pub enum ArticleDetailsBo {
@@ -286,10 +510,21 @@ pub struct ArticleAttachmentBo {
     pub sha256: String,
     /// 创建时间
     pub created_at: i64,
+    /// 累计下载次数
+    pub downloads: u64,
+    /// 累计下载独立访客数
+    pub download_uv: u64,
+    /// 累计传输字节数
+    pub bytes_served: u64,
 }
 
 impl ArticleAttachmentBo {
-    pub fn from_entities(attachment: ArticleAttachmentPo, resource: ResourceBo) -> Self {
+    pub fn from_entities(
+        attachment: ArticleAttachmentPo,
+        resource: ResourceBo,
+        stats: Option<ResourceStatsPo>,
+    ) -> Self {
+        let stats = stats.unwrap_or_default();
         Self {
             attachment_id: attachment.id,
             article_id: attachment.article_id,
@@ -299,6 +534,9 @@ impl ArticleAttachmentBo {
             mime_type: resource.mime_type,
             sha256: resource.sha256,
             created_at: attachment.created_at,
+            downloads: stats.downloads,
+            download_uv: stats.uv,
+            bytes_served: stats.bytes_served,
         }
     }
 }
@@ -370,3 +608,193 @@ impl From<ArticlePo> for ArticleBo {
         }
     }
 }
+
+/// 获取文章访问分析数据
+#[derive(Debug, Clone)]
+pub struct GetArticleAnalyticsBo<'a> {
+    /// 文章ID，为空时统计所有文章
+    pub article_id: Option<Cow<'a, str>>,
+    /// 开始日期（UTC，包含）
+    pub start_date: Cow<'a, str>,
+    /// 结束日期（UTC，包含）
+    pub end_date: Cow<'a, str>,
+    /// 排行榜条目数量
+    pub limit: Option<u64>,
+}
+
+impl GetArticleAnalyticsBo<'_> {
+    /// 日期区间的最大天数
+    pub const MAX_DAYS: i32 = 366;
+
+    pub fn date_range(&self) -> Result<(Date, Date), AppError> {
+        let parse = |date: &str| {
+            date.parse::<Date>().map_err(|e| {
+                AppErrorMeta::BadRequest
+                    .with_message("日期格式错误，应为 YYYY-MM-DD")
+                    .with_source(e)
+            })
+        };
+        let start_date = parse(&self.start_date)?;
+        let end_date = parse(&self.end_date)?;
+        if start_date > end_date {
+            return Err(AppErrorMeta::BadRequest.with_message("开始日期不能晚于结束日期"));
+        }
+        if (end_date - start_date).get_days() >= Self::MAX_DAYS {
+            return Err(AppErrorMeta::BadRequest
+                .with_message(format!("日期区间不能超过 {} 天", Self::MAX_DAYS)));
+        }
+        Ok((start_date, end_date))
+    }
+
+    pub fn limit(&self) -> u64 {
+        self.limit.unwrap_or(10).clamp(1, 100)
+    }
+}
+
+/// 文章访问分析数据
+#[derive(Debug, Clone)]
+pub struct ArticleAnalyticsBo {
+    /// 区间内的页面访问量
+    pub total_pv: u64,
+    /// 区间内每日独立访客数之和
+    pub total_uv: u64,
+    /// 每日访问量
+    pub daily: Vec<ArticleDailyVisitsBo>,
+    /// 访问量最高的文章，指定文章时为空
+    pub top_articles: Vec<ArticleVisitsBo>,
+    /// 访问次数最多的来源域名
+    pub referrers: Vec<ArticleReferrerVisitsBo>,
+}
+
+impl ArticleAnalyticsBo {
+    pub fn from_entities(
+        daily: Vec<DailyVisits>,
+        top_articles: Vec<ArticleVisits>,
+        referrers: Vec<ReferrerVisits>,
+    ) -> Self {
+        Self {
+            total_pv: daily.iter().map(|visits| visits.pv).sum(),
+            total_uv: daily.iter().map(|visits| visits.uv).sum(),
+            daily: daily.into_iter().map(ArticleDailyVisitsBo::from).collect(),
+            top_articles: top_articles
+                .into_iter()
+                .map(ArticleVisitsBo::from)
+                .collect(),
+            referrers: referrers
+                .into_iter()
+                .map(ArticleReferrerVisitsBo::from)
+                .collect(),
+        }
+    }
+}
+
+/// 每日访问量
+#[derive(Debug, Clone)]
+pub struct ArticleDailyVisitsBo {
+    /// 日期（UTC，格式为 YYYY-MM-DD）
+    pub date: String,
+    /// 页面访问量
+    pub pv: u64,
+    /// 独立访客数
+    pub uv: u64,
+}
+
+impl From<DailyVisits> for ArticleDailyVisitsBo {
+    fn from(value: DailyVisits) -> Self {
+        Self {
+            date: value.date,
+            pv: value.pv,
+            uv: value.uv,
+        }
+    }
+}
+
+/// 文章访问量
+#[derive(Debug, Clone)]
+pub struct ArticleVisitsBo {
+    /// 文章ID
+    pub article_id: String,
+    /// 标题
+    pub title: String,
+    /// 页面访问量
+    pub pv: u64,
+    /// 每日独立访客数之和
+    pub uv: u64,
+}
+
+impl From<ArticleVisits> for ArticleVisitsBo {
+    fn from(value: ArticleVisits) -> Self {
+        Self {
+            article_id: value.article_id,
+            title: value.title,
+            pv: value.pv,
+            uv: value.uv,
+        }
+    }
+}
+
+/// 来源域名访问次数
+#[derive(Debug, Clone)]
+pub struct ArticleReferrerVisitsBo {
+    /// 来源域名
+    pub domain: String,
+    /// 访问次数
+    pub visits: u64,
+}
+
+impl From<ReferrerVisits> for ArticleReferrerVisitsBo {
+    fn from(value: ReferrerVisits) -> Self {
+        Self {
+            domain: value.domain,
+            visits: value.visits,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive(year: i16, month: Option<i8>) -> ArchiveArticleBo {
+        ArchiveArticleBo {
+            year,
+            month,
+            page: None,
+            size: None,
+        }
+    }
+
+    #[test]
+    fn published_at_range() {
+        assert_eq!(
+            archive(1970, None).published_at_range().unwrap(),
+            (0, Some(365 * 86400))
+        );
+        assert_eq!(
+            archive(1970, Some(2)).published_at_range().unwrap(),
+            (31 * 86400, Some(59 * 86400))
+        );
+        assert!(
+            archive(9999, None)
+                .published_at_range()
+                .unwrap()
+                .1
+                .is_none()
+        );
+        assert!(
+            archive(9999, Some(12))
+                .published_at_range()
+                .unwrap()
+                .1
+                .is_none()
+        );
+        assert!(
+            archive(9999, Some(11))
+                .published_at_range()
+                .unwrap()
+                .1
+                .is_some()
+        );
+        assert!(archive(1970, Some(13)).published_at_range().is_err());
+    }
+}
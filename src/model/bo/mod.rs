@@ -1,5 +1,6 @@
 pub mod article;
 pub mod auth;
 pub mod resource;
+pub mod series;
 pub mod system;
 pub mod visitor;
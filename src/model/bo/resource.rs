@@ -1,11 +1,16 @@
 use std::borrow::Cow;
 use std::pin::Pin;
+use std::time::Duration;
 
 use boluo::BoxError;
 use boluo::body::Bytes;
 use futures_util::Stream;
 
+use crate::error::AppError;
+use crate::jwt::resource::ResourceDownloadJwtData;
 use crate::model::po::resource::ResourcePo;
+use crate::model::po::resource_stats::ResourceStatsPo;
+use crate::util::jwt::JwtClaimsData;
 
 /// 上传资源文件自定义选项
 #[derive(Debug, Clone)]
@@ -52,6 +57,8 @@ pub struct ResourceBo {
     pub size: u64,
     /// 文件类型
     pub mime_type: String,
+    /// 根据文件内容探测到的文件类型
+    pub detected_mime_type: String,
     /// 是否公开访问
     pub is_public: bool,
     /// 文件哈希
@@ -69,6 +76,7 @@ impl From<ResourcePo> for ResourceBo {
             path: value.path,
             size: value.size,
             mime_type: value.mime_type,
+            detected_mime_type: value.detected_mime_type,
             is_public: value.is_public,
             sha256: value.sha256,
             created_at: value.created_at,
@@ -83,6 +91,87 @@ pub struct FindResourceBo<'a> {
     pub resource_id: Cow<'a, str>,
 }
 
+/// 下载资源文件
+#[derive(Debug, Clone)]
+pub struct DownloadResourceBo<'a> {
+    /// 资源文件ID
+    pub resource_id: Cow<'a, str>,
+    /// 签名下载令牌
+    pub token: Option<Cow<'a, str>>,
+}
+
+/// 签发资源文件下载链接
+#[derive(Debug, Clone)]
+pub struct SignResourceBo<'a> {
+    /// 资源文件ID
+    pub resource_id: Cow<'a, str>,
+    /// 链接有效期
+    pub ttl: Option<Duration>,
+}
+
+/// 资源文件签名下载链接
+#[derive(Debug, Clone)]
+pub struct SignedResourceBo {
+    /// 签名下载令牌
+    pub token: String,
+    /// 下载链接
+    pub url: String,
+    /// 过期时间
+    pub expires_at: i64,
+}
+
+impl SignedResourceBo {
+    pub fn generate(resource_id: &str, ttl: Duration) -> Result<Self, AppError> {
+        let data = ResourceDownloadJwtData {
+            resource_id: resource_id.to_owned(),
+        };
+        let claims = data.with_ttl(ttl);
+        let token = claims.encode(crate::config::get().jwt.secret.as_bytes())?;
+        Ok(Self {
+            url: format!(
+                "/resources/{resource_id}?token={}",
+                urlencoding::encode(&token)
+            ),
+            token,
+            expires_at: claims.exp,
+        })
+    }
+}
+
+/// 资源文件下载统计信息
+#[derive(Debug, Clone)]
+pub struct ResourceStatsBo {
+    /// 资源ID
+    pub resource_id: String,
+    /// 文件名
+    pub name: String,
+    /// 文件大小
+    pub size: u64,
+    /// 累计下载次数
+    pub downloads: u64,
+    /// 累计独立访客数
+    pub uv: u64,
+    /// 累计传输字节数
+    pub bytes_served: u64,
+    /// 最后下载时间
+    pub last_downloaded_at: Option<i64>,
+}
+
+impl ResourceStatsBo {
+    pub fn from_entities(resource: ResourcePo, stats: Option<ResourceStatsPo>) -> Self {
+        let stats = stats.unwrap_or_default();
+        Self {
+            resource_id: resource.id,
+            name: resource.name,
+            size: resource.size,
+            downloads: stats.downloads,
+            uv: stats.uv,
+            bytes_served: stats.bytes_served,
+            last_downloaded_at: Some(stats.updated_at).filter(|time| *time > 0),
+        }
+    }
+}
+
 /// 删除资源文件
 #[derive(Debug, Clone)]
 pub struct RemoveResourceBo<'a> {
@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+
+use crate::model::bo::article::{ArticleLinkBo, ArticleListItemBo};
+use crate::model::po::article::ArticlePo;
+use crate::model::po::series::{SeriesListItem, SeriesPo};
+
+/// 创建系列
+#[derive(Debug, Clone)]
+pub struct CreateSeriesBo {
+    /// 标题
+    pub title: String,
+    /// 描述
+    pub description: String,
+}
+
+/// 修改系列
+#[derive(Debug, Clone)]
+pub struct UpdateSeriesBo {
+    /// 系列ID
+    pub series_id: String,
+    /// 标题
+    pub title: String,
+    /// 描述
+    pub description: String,
+}
+
+/// 删除系列
+#[derive(Debug, Clone)]
+pub struct RemoveSeriesBo<'a> {
+    /// 系列ID
+    pub series_id: Cow<'a, str>,
+}
+
+/// 获取系列详情
+#[derive(Debug, Clone)]
+pub struct GetSeriesBo<'a> {
+    /// 系列ID
+    pub series_id: Cow<'a, str>,
+}
+
+/// 设置系列中的文章
+#[derive(Debug, Clone)]
+pub struct SetSeriesArticlesBo {
+    /// 系列ID
+    pub series_id: String,
+    /// 按顺序排列的文章ID，不在列表中的文章将移出系列
+    pub article_ids: Vec<String>,
+}
+
+/// 系列
+#[derive(Debug, Clone)]
+pub struct SeriesBo {
+    /// 系列ID
+    pub series_id: String,
+    /// 标题
+    pub title: String,
+    /// 描述
+    pub description: String,
+    /// 创建时间
+    pub created_at: i64,
+    /// 修改时间
+    pub updated_at: i64,
+}
+
+impl From<SeriesPo> for SeriesBo {
+    fn from(series: SeriesPo) -> Self {
+        Self {
+            series_id: series.id,
+            title: series.title,
+            description: series.description,
+            created_at: series.created_at,
+            updated_at: series.updated_at,
+        }
+    }
+}
+
+/// 系列列表项
+#[derive(Debug, Clone)]
+pub struct SeriesListItemBo {
+    /// 系列
+    pub series: SeriesBo,
+    /// 系列中的文章数量（包括草稿）
+    pub article_count: u64,
+}
+
+impl From<SeriesListItem> for SeriesListItemBo {
+    fn from(item: SeriesListItem) -> Self {
+        Self {
+            series: SeriesBo::from(item.series),
+            article_count: item.article_count,
+        }
+    }
+}
+
+/// 系列详情
+#[derive(Debug, Clone)]
+pub struct SeriesDetailsBo {
+    /// 系列
+    pub series: SeriesBo,
+    /// 按系列顺序排列的文章
+    pub articles: Vec<ArticleListItemBo>,
+}
+
+impl SeriesDetailsBo {
+    pub fn from_entities(series: SeriesPo, articles: Vec<ArticlePo>) -> Self {
+        Self {
+            series: SeriesBo::from(series),
+            articles: articles.into_iter().map(ArticleListItemBo::from).collect(),
+        }
+    }
+}
+
+/// 文章所在系列的导航信息
+#[derive(Debug, Clone)]
+pub struct ArticleSeriesBo {
+    /// 系列ID
+    pub series_id: String,
+    /// 系列标题
+    pub title: String,
+    /// 文章在系列中的序号，从 1 开始
+    pub position: u64,
+    /// 系列中的文章总数
+    pub total: u64,
+    /// 系列中的上一篇
+    pub prev: Option<ArticleLinkBo>,
+    /// 系列中的下一篇
+    pub next: Option<ArticleLinkBo>,
+}
+
+impl ArticleSeriesBo {
+    /// 根据系列中按顺序排列的文章生成导航信息，文章不在列表中时返回 `None`
+    pub fn from_entities(
+        article_id: &str,
+        series: SeriesPo,
+        articles: Vec<ArticlePo>,
+    ) -> Option<Self> {
+        let index = articles.iter().position(|a| a.id == article_id)?;
+        let total = articles.len() as u64;
+        let mut prev = None;
+        let mut next = None;
+        for (i, article) in articles.into_iter().enumerate() {
+            if i + 1 == index {
+                prev = Some(ArticleLinkBo::from(article));
+            } else if i == index + 1 {
+                next = Some(ArticleLinkBo::from(article));
+            }
+        }
+        Some(Self {
+            series_id: series.id,
+            title: series.title,
+            position: index as u64 + 1,
+            total,
+            prev,
+            next,
+        })
+    }
+}
@@ -1,3 +1,9 @@
+use std::borrow::Cow;
+
+use crate::storage::cache::Cache;
+use crate::storage::cache::storage::CacheKindStats;
+use crate::util::time::UnixTimestampSecs;
+
 #[derive(Debug)]
 pub struct InfoBo {
     /// 系统信息
@@ -6,6 +12,16 @@ pub struct InfoBo {
     pub disks: Vec<InfoDiskBo>,
     /// 数据库信息
     pub database: InfoDatabaseBo,
+    /// 资源存储信息
+    pub resource: InfoResourceBo,
+}
+
+#[derive(Debug)]
+pub struct InfoResourceBo {
+    /// 已占用的存储空间
+    pub used_space: u64,
+    /// 存储空间总配额，为空时不限制
+    pub quota: Option<u64>,
 }
 
 #[derive(Debug)]
@@ -110,3 +126,82 @@ impl From<&sysinfo::Disk> for InfoDiskBo {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct CacheKindStatsBo {
+    /// 缓存类型
+    pub kind: String,
+    /// 缓存数量
+    pub total: u64,
+    /// 已过期但尚未清理的缓存数量
+    pub expired: u64,
+    /// 缓存数据大小
+    pub data_size: u64,
+}
+
+impl From<CacheKindStats> for CacheKindStatsBo {
+    fn from(value: CacheKindStats) -> Self {
+        Self {
+            kind: value.kind,
+            total: value.total,
+            expired: value.expired,
+            data_size: value.data_size,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CacheEntryBo {
+    /// 缓存ID
+    pub id: String,
+    /// 缓存类型
+    pub kind: String,
+    /// 缓存数据
+    pub data: serde_json::Value,
+    /// 创建时间
+    pub created_at: i64,
+    /// 过期时间
+    pub expires_at: i64,
+    /// 是否已过期
+    pub is_expired: bool,
+}
+
+impl From<Cache<serde_json::Value>> for CacheEntryBo {
+    fn from(value: Cache<serde_json::Value>) -> Self {
+        Self {
+            is_expired: UnixTimestampSecs::now().as_i64() > value.expires_at,
+            id: value.id,
+            kind: value.kind,
+            data: value.data,
+            created_at: value.created_at,
+            expires_at: value.expires_at,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct InspectCacheBo<'a> {
+    /// 缓存类型
+    pub kind: Cow<'a, str>,
+    /// 缓存ID
+    pub id: Cow<'a, str>,
+}
+
+#[derive(Debug)]
+pub struct RemoveCacheBo<'a> {
+    /// 缓存类型
+    pub kind: Cow<'a, str>,
+    /// 缓存ID前缀，为空时删除该类型的所有缓存
+    pub id_prefix: Cow<'a, str>,
+}
+
+/// 全文索引检查结果
+#[derive(Debug)]
+pub struct SearchIndexCheckBo {
+    /// 全文索引表
+    pub table: String,
+    /// 检查是否通过
+    pub ok: bool,
+    /// 检查未通过时的错误信息
+    pub message: Option<String>,
+}
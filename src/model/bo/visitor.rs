@@ -9,39 +9,111 @@ use crate::context::visitor::VisitorId;
 use crate::error::{AppError, AppErrorMeta};
 use crate::model::co::article::VisitorArticleAccessPermitCo;
 use crate::model::co::visitor::VisitorCo;
+use crate::model::common::visitor::VisitorKind;
 use crate::storage::cache::storage::CacheSetMode;
 use crate::storage::cache::{Cache, CacheData};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct VisitorBo {
     visitor: Cache<VisitorCo>,
+    /// 访客类型，临时访客与爬虫不会写入缓存
+    #[serde(skip)]
+    kind: VisitorKind,
+    /// 是否拒绝跟踪（DNT / Sec-GPC）
+    #[serde(skip)]
+    do_not_track: bool,
+    /// 按每日轮换盐值计算的访客哈希
+    #[serde(skip)]
+    hash: Option<String>,
 }
 
 impl VisitorBo {
     pub const VISITOR_TTL: Duration = Duration::from_secs(3600 * 24 * 7);
     pub const VISITOR_KEEP_THRESHOLD: Duration = Duration::from_secs(3600 * 24);
 
+    /// 创建尚未确认接受 Cookie 的访客，访客需要在确认时限内携带 Cookie 再次请求
     pub async fn create_and_cache() -> anyhow::Result<Self> {
         let data = VisitorCo {
             visitor_id: crate::util::uuid::v4(),
+            unconfirmed: true,
         };
-        let visitor = data.with_ttl(Self::VISITOR_TTL);
+        let visitor = data.with_ttl(crate::config::get().visitor.confirm_timeout);
         visitor.set(CacheSetMode::OnlyIfNotExists).await?;
         // 删除此访客残留的文章访问许可
         Self::cleanup_article(&visitor.data.visitor_id).await?;
-        Ok(Self { visitor })
+        Ok(Self::new(visitor, VisitorKind::Persisted))
+    }
+
+    /// 创建不写入缓存的临时访客或爬虫访客
+    pub fn transient(visitor_id: &str, kind: VisitorKind) -> Self {
+        let data = VisitorCo {
+            visitor_id: visitor_id.to_owned(),
+            unconfirmed: false,
+        };
+        Self::new(data.with_ttl(Duration::ZERO), kind)
     }
 
     pub async fn from_cache(visitor_id: &str) -> anyhow::Result<Option<Self>> {
         Ok(Cache::get(visitor_id)
             .await?
-            .map(|cache| Self { visitor: cache }))
+            .map(|cache| Self::new(cache, VisitorKind::Persisted)))
+    }
+
+    /// 将临时访客写入缓存，访客已携带访客 Cookie 请求解锁等操作，视为已确认接受 Cookie
+    pub async fn persist(&self) -> anyhow::Result<Self> {
+        let data = VisitorCo {
+            visitor_id: self.visitor_id().to_owned(),
+            unconfirmed: false,
+        };
+        let visitor = data.with_ttl(Self::VISITOR_TTL);
+        visitor.set(CacheSetMode::OnlyIfNotExists).await?;
+        Ok(Self {
+            visitor,
+            kind: VisitorKind::Persisted,
+            do_not_track: self.do_not_track,
+            hash: self.hash.clone(),
+        })
+    }
+
+    /// 设置访客的跟踪偏好
+    pub fn with_tracking(mut self, do_not_track: bool, hash: Option<String>) -> Self {
+        self.do_not_track = do_not_track;
+        self.hash = hash;
+        self
     }
 
     pub fn visitor_id(&self) -> &str {
         &self.visitor.data.visitor_id
     }
 
+    pub fn kind(&self) -> VisitorKind {
+        self.kind
+    }
+
+    pub fn is_bot(&self) -> bool {
+        self.kind == VisitorKind::Bot
+    }
+
+    /// 访问是否计入统计，爬虫与拒绝跟踪的访客不计入统计
+    pub fn is_tracked(&self) -> bool {
+        !self.is_bot() && !self.do_not_track
+    }
+
+    /// 统计独立访客时使用的标识，启用哈希识别时使用访客哈希，否则使用访客ID
+    pub fn stats_id(&self) -> &str {
+        self.hash.as_deref().unwrap_or(self.visitor_id())
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        !self.visitor.data.unconfirmed
+    }
+
+    /// 确认访客接受 Cookie
+    pub async fn confirm(&mut self) -> anyhow::Result<bool> {
+        self.visitor.data.unconfirmed = false;
+        self.visitor.set(CacheSetMode::OnlyIfExists).await
+    }
+
     pub fn created_at(&self) -> i64 {
         self.visitor.created_at
     }
@@ -68,6 +140,34 @@ impl VisitorBo {
         Cache::<VisitorArticleAccessPermitCo>::exists(data.generate_id().as_ref()).await
     }
 
+    /// 列出此访客拥有访问许可的所有文章ID
+    pub async fn list_articles(&self) -> anyhow::Result<Vec<String>> {
+        /// 每次扫描的访问许可数量
+        const SCAN_LIMIT: u64 = 100;
+
+        let id_prefix = Self::article_permit_prefix(self.visitor_id());
+        let mut article_ids = vec![];
+        let mut cursor = None;
+        loop {
+            let page = Cache::<VisitorArticleAccessPermitCo>::scan_prefix(
+                &id_prefix,
+                cursor.as_deref(),
+                SCAN_LIMIT,
+            )
+            .await?;
+            article_ids.extend(
+                page.entries
+                    .iter()
+                    .filter_map(|entry| entry.id.strip_prefix(&id_prefix))
+                    .map(ToOwned::to_owned),
+            );
+            match page.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => return Ok(article_ids),
+            }
+        }
+    }
+
     pub async fn keep(visitor_id: &str) -> anyhow::Result<bool> {
         let Some(ttl) = Cache::<VisitorCo>::get_ttl(visitor_id).await? else {
             return Ok(false);
@@ -79,10 +179,30 @@ impl VisitorBo {
         }
     }
 
+    fn new(visitor: Cache<VisitorCo>, kind: VisitorKind) -> Self {
+        Self {
+            visitor,
+            kind,
+            do_not_track: false,
+            hash: None,
+        }
+    }
+
     async fn cleanup_article(visitor_id: &str) -> anyhow::Result<()> {
-        Cache::<VisitorArticleAccessPermitCo>::batch_remove(visitor_id)
-            .await
-            .map_err(From::from)
+        Cache::<VisitorArticleAccessPermitCo>::batch_remove(&Self::article_permit_prefix(
+            visitor_id,
+        ))
+        .await
+        .map_err(From::from)
+    }
+
+    /// 访客的文章访问许可的缓存ID前缀
+    fn article_permit_prefix(visitor_id: &str) -> String {
+        let data = VisitorArticleAccessPermitCo {
+            visitor_id: visitor_id.into(),
+            article_id: "".into(),
+        };
+        data.generate_id().into_owned()
     }
 }
 
@@ -93,12 +213,21 @@ impl FromRequest for VisitorBo {
         let Some(visitor) = Option::<Extension<VisitorId>>::from_request(request).await? else {
             return Err(AppErrorMeta::Internal.with_context("请求扩展中 VisitorId 不存在"));
         };
-        let Some(visitor) = VisitorBo::from_cache(visitor.visitor_id()).await? else {
-            return Err(AppErrorMeta::Internal.with_context(format!(
-                "缓存中没有找到访客信息，访客ID: {}",
-                visitor.visitor_id()
-            )));
+        let bo = match visitor.kind() {
+            VisitorKind::Persisted => {
+                let Some(bo) = VisitorBo::from_cache(visitor.visitor_id()).await? else {
+                    return Err(AppErrorMeta::Internal.with_context(format!(
+                        "缓存中没有找到访客信息，访客ID: {}",
+                        visitor.visitor_id()
+                    )));
+                };
+                bo
+            }
+            kind => VisitorBo::transient(visitor.visitor_id(), kind),
         };
-        Ok(visitor)
+        Ok(bo.with_tracking(
+            visitor.do_not_track(),
+            visitor.hash().map(ToOwned::to_owned),
+        ))
     }
 }
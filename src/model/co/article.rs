@@ -39,3 +39,23 @@ impl CacheData for VisitorArticleAccessRecordCo<'_> {
         format!("{}:{}", self.visitor_id, self.article_id).into()
     }
 }
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VisitorArticleDailyAccessRecordCo<'a> {
+    /// 访客ID
+    pub visitor_id: Cow<'a, str>,
+    /// 文章ID
+    pub article_id: Cow<'a, str>,
+    /// 日期（UTC，格式为 YYYY-MM-DD）
+    pub date: Cow<'a, str>,
+}
+
+impl CacheData for VisitorArticleDailyAccessRecordCo<'_> {
+    fn kind() -> &'static str {
+        "visitor_article_daily_access_record"
+    }
+
+    fn generate_id(&self) -> Cow<'_, str> {
+        format!("{}:{}:{}", self.visitor_id, self.article_id, self.date).into()
+    }
+}
@@ -1,2 +1,3 @@
 pub mod article;
+pub mod resource;
 pub mod visitor;
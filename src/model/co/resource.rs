@@ -0,0 +1,23 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::storage::cache::CacheData;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VisitorResourceDownloadRecordCo<'a> {
+    /// 访客ID
+    pub visitor_id: Cow<'a, str>,
+    /// 资源ID
+    pub resource_id: Cow<'a, str>,
+}
+
+impl CacheData for VisitorResourceDownloadRecordCo<'_> {
+    fn kind() -> &'static str {
+        "visitor_resource_download_record"
+    }
+
+    fn generate_id(&self) -> Cow<'_, str> {
+        format!("{}:{}", self.visitor_id, self.resource_id).into()
+    }
+}
@@ -8,6 +8,9 @@ use crate::storage::cache::CacheData;
 pub struct VisitorCo {
     /// 访客ID
     pub visitor_id: String,
+    /// 是否尚未确认接受 Cookie
+    #[serde(default)]
+    pub unconfirmed: bool,
 }
 
 impl CacheData for VisitorCo {
@@ -19,3 +22,22 @@ impl CacheData for VisitorCo {
         self.visitor_id.as_str().into()
     }
 }
+
+/// 访客哈希的每日盐值
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct VisitorHashSaltCo {
+    /// 日期（UTC，格式为 YYYY-MM-DD）
+    pub date: String,
+    /// 盐值
+    pub salt: String,
+}
+
+impl CacheData for VisitorHashSaltCo {
+    fn kind() -> &'static str {
+        "visitor_hash_salt"
+    }
+
+    fn generate_id(&self) -> Cow<'_, str> {
+        self.date.as_str().into()
+    }
+}
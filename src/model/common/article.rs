@@ -9,3 +9,17 @@ pub enum ArticleStatus {
     /// 发布
     Published,
 }
+
+/// 文章搜索结果的排序方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArticleSort {
+    /// 按全文搜索的相关度排序，未进行全文搜索时按发布时间从新到旧排序
+    Relevance,
+    /// 按发布时间从新到旧排序
+    Newest,
+    /// 按发布时间从旧到新排序
+    Oldest,
+    /// 按累计页面访问量从高到低排序
+    MostViewed,
+}
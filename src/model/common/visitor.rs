@@ -0,0 +1,11 @@
+/// 访客类型
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum VisitorKind {
+    /// 已写入缓存并设置了访客 Cookie 的访客
+    #[default]
+    Persisted,
+    /// 仅在本次请求中有效的临时访客，不会写入缓存
+    Ephemeral,
+    /// 爬虫
+    Bot,
+}
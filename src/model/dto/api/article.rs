@@ -7,12 +7,13 @@ use serde::{Deserialize, Serialize, Serializer};
 
 use crate::error::{AppError, AppErrorMeta};
 use crate::model::bo::article::{
-    AdminArticleDetailsBo, ArticleAttachmentBo, ArticleDetailsBo, ArticleListBo, ArticleListItemBo,
-    CreateArticleBo, DownloadArticleAttachmentBo, GetArticleBo, RemoveArticleAttachmentBo,
-    RemoveArticleBo, SearchArticleBo, UnlockArticleBo, UpdateArticleBo, UploadArticleAttachmentBo,
-    VisitorArticleDetailsBo,
+    AdminArticleDetailsBo, ArticleAnalyticsBo, ArticleAttachmentBo, ArticleDailyVisitsBo,
+    ArticleDetailsBo, ArticleListBo, ArticleListItemBo, ArticleReferrerVisitsBo, ArticleVisitsBo,
+    CreateArticleBo, DownloadArticleAttachmentBo, GetArticleAnalyticsBo, GetArticleBo,
+    RemoveArticleAttachmentBo, RemoveArticleBo, SearchArticleBo, UnlockArticleBo, UpdateArticleBo,
+    UploadArticleAttachmentBo, VisitorArticleDetailsBo,
 };
-use crate::model::common::article::ArticleStatus;
+use crate::model::common::article::{ArticleSort, ArticleStatus};
 use crate::model::dto::api::resource::UploadResourceDto;
 use crate::util::pagination::PageData;
 
@@ -71,6 +72,10 @@ pub struct UpdateArticleDto {
     pub password: Option<String>,
     /// 状态
     pub status: ArticleStatus,
+    /// 是否置顶，为空时保持不变
+    pub pinned: Option<bool>,
+    /// 是否为精选文章，为空时保持不变
+    pub featured: Option<bool>,
 }
 
 impl Into<UpdateArticleBo> for UpdateArticleDto {
@@ -81,6 +86,8 @@ impl Into<UpdateArticleBo> for UpdateArticleDto {
             markdown_content: self.markdown_content,
             password: self.password,
             status: self.status,
+            pinned: self.pinned,
+            featured: self.featured,
         }
     }
 }
@@ -111,10 +118,16 @@ pub struct SearchArticleDto {
     pub published_at_ge: Option<i64>,
     /// 发布时间（小于）
     pub published_at_lt: Option<i64>,
+    /// 排序方式
+    pub sort: Option<ArticleSort>,
     /// 分页页码
     pub page: Option<u64>,
     /// 分页大小
     pub size: Option<u64>,
+    /// 游标分页的游标，取自上一页结果的 `next_cursor`，不能与分页页码同时使用
+    pub cursor: Option<String>,
+    /// 是否统计总数据条数，默认统计
+    pub with_total: Option<bool>,
 }
 
 impl<'a> Into<SearchArticleBo<'a>> for SearchArticleDto {
@@ -124,8 +137,12 @@ impl<'a> Into<SearchArticleBo<'a>> for SearchArticleDto {
             status: self.status,
             published_at_ge: self.published_at_ge,
             published_at_lt: self.published_at_lt,
+            sort: self.sort,
             page: self.page,
             size: self.size,
+            cursor: self.cursor.map(Into::into),
+            with_total: self.with_total.unwrap_or(true),
+            pinned_first: false,
         }
     }
 }
@@ -145,6 +162,8 @@ pub struct ArticleListItemDto {
     pub updated_at: i64,
     /// 发布时间
     pub published_at: Option<i64>,
+    /// 是否置顶
+    pub pinned: bool,
     /// 是否需要密码访问
     pub need_password: bool,
 }
@@ -158,6 +177,7 @@ impl From<ArticleListItemBo> for ArticleListItemDto {
             created_at: value.created_at,
             updated_at: value.updated_at,
             published_at: value.published_at,
+            pinned: value.pinned,
             need_password: value.need_password,
         }
     }
@@ -165,21 +185,30 @@ impl From<ArticleListItemBo> for ArticleListItemDto {
 
 /// 文章列表
 #[derive(Debug, Clone, Serialize)]
-#[serde(transparent)]
-pub struct ArticleListDto(PageData<Vec<ArticleListItemDto>>);
+pub struct ArticleListDto {
+    /// 文章列表数据
+    #[serde(flatten)]
+    pub data: PageData<Vec<ArticleListItemDto>>,
+    /// 下一页的游标，排序方式不支持游标分页或没有下一页时为空
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
 
 impl From<ArticleListBo> for ArticleListDto {
     fn from(value: ArticleListBo) -> Self {
-        Self(PageData {
-            items: value
-                .data
-                .items
-                .into_iter()
-                .map(ArticleListItemDto::from)
-                .collect(),
-            count: value.data.count,
-            total: value.data.total,
-        })
+        Self {
+            data: PageData {
+                items: value
+                    .data
+                    .items
+                    .into_iter()
+                    .map(ArticleListItemDto::from)
+                    .collect(),
+                count: value.data.count,
+                total: value.data.total,
+            },
+            next_cursor: value.next_cursor,
+        }
     }
 }
 
@@ -195,6 +224,7 @@ impl<'a> Into<GetArticleBo<'a>> for GetArticleDto {
         GetArticleBo {
             article_id: self.article_id.into(),
             ignore_status: false,
+            referrer: None,
         }
     }
 }
@@ -272,6 +302,10 @@ pub struct AdminArticleDetailsDto {
     pub updated_at: i64,
     /// 发布时间
     pub published_at: Option<i64>,
+    /// 置顶时间，为空时未置顶
+    pub pinned_at: Option<i64>,
+    /// 是否为精选文章
+    pub featured: bool,
     /// 是否需要密码访问
     pub need_password: bool,
     /// 附件列表
@@ -294,6 +328,8 @@ impl From<AdminArticleDetailsBo> for AdminArticleDetailsDto {
             created_at: value.created_at,
             updated_at: value.updated_at,
             published_at: value.published_at,
+            pinned_at: value.pinned_at,
+            featured: value.featured,
             need_password: value.need_password,
             attachments: value
                 .attachments
@@ -342,6 +378,12 @@ pub struct ArticleAttachmentDto {
     pub sha256: String,
     /// 创建时间
     pub created_at: i64,
+    /// 累计下载次数
+    pub downloads: u64,
+    /// 累计下载独立访客数
+    pub download_uv: u64,
+    /// 累计传输字节数
+    pub bytes_served: u64,
 }
 
 impl From<ArticleAttachmentBo> for ArticleAttachmentDto {
@@ -355,6 +397,9 @@ impl From<ArticleAttachmentBo> for ArticleAttachmentDto {
             mime_type: value.mime_type,
             sha256: value.sha256,
             created_at: value.created_at,
+            downloads: value.downloads,
+            download_uv: value.download_uv,
+            bytes_served: value.bytes_served,
         }
     }
 }
@@ -364,7 +409,7 @@ impl Serialize for ArticleAttachmentDto {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("ArticleAttachmentDto", 9)?;
+        let mut state = serializer.serialize_struct("ArticleAttachmentDto", 12)?;
 
         state.serialize_field("attachment_id", &self.attachment_id)?;
         state.serialize_field("article_id", &self.article_id)?;
@@ -374,6 +419,9 @@ impl Serialize for ArticleAttachmentDto {
         state.serialize_field("mime_type", &self.mime_type)?;
         state.serialize_field("sha256", &self.sha256)?;
         state.serialize_field("created_at", &self.created_at)?;
+        state.serialize_field("downloads", &self.downloads)?;
+        state.serialize_field("download_uv", &self.download_uv)?;
+        state.serialize_field("bytes_served", &self.bytes_served)?;
         state.serialize_field(
             "url",
             &format!(
@@ -458,3 +506,117 @@ impl<'a> Into<DownloadArticleAttachmentBo<'a>> for DownloadArticleAttachmentDto
         }
     }
 }
+
+/// 获取文章访问分析数据
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetArticleAnalyticsDto {
+    /// 文章ID，为空时统计所有文章
+    pub article_id: Option<String>,
+    /// 开始日期（UTC，包含，格式为 YYYY-MM-DD）
+    pub start_date: String,
+    /// 结束日期（UTC，包含，格式为 YYYY-MM-DD）
+    pub end_date: String,
+    /// 排行榜条目数量
+    pub limit: Option<u64>,
+}
+
+impl<'a> Into<GetArticleAnalyticsBo<'a>> for GetArticleAnalyticsDto {
+    fn into(self) -> GetArticleAnalyticsBo<'a> {
+        GetArticleAnalyticsBo {
+            article_id: self.article_id.map(Into::into),
+            start_date: self.start_date.into(),
+            end_date: self.end_date.into(),
+            limit: self.limit,
+        }
+    }
+}
+
+/// 文章访问分析数据
+#[derive(Debug, Clone, Serialize)]
+pub struct ArticleAnalyticsDto {
+    /// 区间内的页面访问量
+    pub total_pv: u64,
+    /// 区间内每日独立访客数之和
+    pub total_uv: u64,
+    /// 每日访问量
+    pub daily: Vec<ArticleDailyVisitsDto>,
+    /// 访问量最高的文章，指定文章时为空
+    pub top_articles: Vec<ArticleVisitsDto>,
+    /// 访问次数最多的来源域名
+    pub referrers: Vec<ArticleReferrerVisitsDto>,
+}
+
+impl From<ArticleAnalyticsBo> for ArticleAnalyticsDto {
+    fn from(value: ArticleAnalyticsBo) -> Self {
+        Self {
+            total_pv: value.total_pv,
+            total_uv: value.total_uv,
+            daily: value.daily.into_iter().map(From::from).collect(),
+            top_articles: value.top_articles.into_iter().map(From::from).collect(),
+            referrers: value.referrers.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+/// 每日访问量
+#[derive(Debug, Clone, Serialize)]
+pub struct ArticleDailyVisitsDto {
+    /// 日期（UTC，格式为 YYYY-MM-DD）
+    pub date: String,
+    /// 页面访问量
+    pub pv: u64,
+    /// 独立访客数
+    pub uv: u64,
+}
+
+impl From<ArticleDailyVisitsBo> for ArticleDailyVisitsDto {
+    fn from(value: ArticleDailyVisitsBo) -> Self {
+        Self {
+            date: value.date,
+            pv: value.pv,
+            uv: value.uv,
+        }
+    }
+}
+
+/// 文章访问量
+#[derive(Debug, Clone, Serialize)]
+pub struct ArticleVisitsDto {
+    /// 文章ID
+    pub article_id: String,
+    /// 标题
+    pub title: String,
+    /// 页面访问量
+    pub pv: u64,
+    /// 每日独立访客数之和
+    pub uv: u64,
+}
+
+impl From<ArticleVisitsBo> for ArticleVisitsDto {
+    fn from(value: ArticleVisitsBo) -> Self {
+        Self {
+            article_id: value.article_id,
+            title: value.title,
+            pv: value.pv,
+            uv: value.uv,
+        }
+    }
+}
+
+/// 来源域名访问次数
+#[derive(Debug, Clone, Serialize)]
+pub struct ArticleReferrerVisitsDto {
+    /// 来源域名
+    pub domain: String,
+    /// 访问次数
+    pub visits: u64,
+}
+
+impl From<ArticleReferrerVisitsBo> for ArticleReferrerVisitsDto {
+    fn from(value: ArticleReferrerVisitsBo) -> Self {
+        Self {
+            domain: value.domain,
+            visits: value.visits,
+        }
+    }
+}
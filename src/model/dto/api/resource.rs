@@ -1,8 +1,10 @@
 use std::pin::Pin;
+use std::time::Duration;
 
 use boluo::BoxError;
 use boluo::body::{Body, Bytes};
-use boluo::extract::FromRequest;
+use boluo::data::Form;
+use boluo::extract::{FromRequest, Path};
 use boluo::http::HeaderMap;
 use boluo::request::Request;
 use futures_util::Stream;
@@ -11,7 +13,8 @@ use serde::{Deserialize, Serialize, Serializer};
 
 use crate::error::{AppError, AppErrorMeta};
 use crate::model::bo::resource::{
-    FindResourceBo, RemoveResourceBo, ResourceBo, UploadResourceBo, UploadResourceMetaBo,
+    DownloadResourceBo, FindResourceBo, RemoveResourceBo, ResourceBo, ResourceStatsBo,
+    SignResourceBo, SignedResourceBo, UploadResourceBo, UploadResourceMetaBo,
 };
 
 /// 上传资源文件元数据
@@ -106,6 +109,8 @@ pub struct ResourceDto {
     pub size: u64,
     /// 文件类型
     pub mime_type: String,
+    /// 根据文件内容探测到的文件类型
+    pub detected_mime_type: String,
     /// 文件哈希
     pub sha256: String,
     /// 创建时间
@@ -120,6 +125,7 @@ impl From<ResourceBo> for ResourceDto {
             extension: value.extension,
             size: value.size,
             mime_type: value.mime_type,
+            detected_mime_type: value.detected_mime_type,
             sha256: value.sha256,
             created_at: value.created_at,
         }
@@ -131,13 +137,14 @@ impl Serialize for ResourceDto {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("ResourceDto", 8)?;
+        let mut state = serializer.serialize_struct("ResourceDto", 9)?;
 
         state.serialize_field("resource_id", &self.resource_id)?;
         state.serialize_field("name", &self.name)?;
         state.serialize_field("extension", &self.extension)?;
         state.serialize_field("size", &self.size)?;
         state.serialize_field("mime_type", &self.mime_type)?;
+        state.serialize_field("detected_mime_type", &self.detected_mime_type)?;
         state.serialize_field("sha256", &self.sha256)?;
         state.serialize_field("created_at", &self.created_at)?;
         state.serialize_field("url", &format!("/resources/{}", self.resource_id))?;
@@ -151,12 +158,77 @@ impl Serialize for ResourceDto {
 pub struct DownloadResourceDto {
     /// 资源文件ID
     pub resource_id: String,
+    /// 签名下载令牌
+    pub token: Option<String>,
 }
 
-impl<'a> Into<FindResourceBo<'a>> for DownloadResourceDto {
-    fn into(self) -> FindResourceBo<'a> {
-        FindResourceBo {
+impl FromRequest for DownloadResourceDto {
+    type Error = BoxError;
+
+    async fn from_request(request: &mut Request) -> Result<Self, Self::Error> {
+        #[derive(Deserialize)]
+        struct PathParams {
+            resource_id: String,
+        }
+        #[derive(Deserialize)]
+        struct FormParams {
+            token: Option<String>,
+        }
+
+        let Path(path_params) = Path::<PathParams>::from_request(request).await?;
+        let Form(form_params) = Form::<FormParams>::from_request(request).await?;
+
+        Ok(Self {
+            resource_id: path_params.resource_id,
+            token: form_params.token,
+        })
+    }
+}
+
+impl<'a> Into<DownloadResourceBo<'a>> for DownloadResourceDto {
+    fn into(self) -> DownloadResourceBo<'a> {
+        DownloadResourceBo {
             resource_id: self.resource_id.into(),
+            token: self.token.map(Into::into),
+        }
+    }
+}
+
+/// 签发资源文件下载链接
+#[derive(Debug, Clone, Deserialize)]
+pub struct SignResourceDto {
+    /// 资源文件ID
+    pub resource_id: String,
+    /// 链接有效期（秒），为空时使用默认有效期
+    pub ttl: Option<u64>,
+}
+
+impl<'a> Into<SignResourceBo<'a>> for SignResourceDto {
+    fn into(self) -> SignResourceBo<'a> {
+        SignResourceBo {
+            resource_id: self.resource_id.into(),
+            ttl: self.ttl.map(Duration::from_secs),
+        }
+    }
+}
+
+/// 资源文件签名下载链接
+#[derive(Debug, Clone, Serialize)]
+pub struct SignedResourceDto {
+    /// 签名下载令牌
+    pub token: String,
+    /// 下载链接
+    pub url: String,
+    /// 过期时间
+    pub expires_at: i64,
+}
+
+impl From<SignedResourceBo> for SignedResourceDto {
+    fn from(value: SignedResourceBo) -> Self {
+        Self {
+            token: value.token,
+            url: value.url,
+            expires_at: value.expires_at,
         }
     }
 }
@@ -175,3 +247,51 @@ impl<'a> Into<RemoveResourceBo<'a>> for RemoveResourceDto {
         }
     }
 }
+
+/// 获取资源文件下载统计信息
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetResourceStatsDto {
+    /// 资源文件ID
+    pub resource_id: String,
+}
+
+impl<'a> Into<FindResourceBo<'a>> for GetResourceStatsDto {
+    fn into(self) -> FindResourceBo<'a> {
+        FindResourceBo {
+            resource_id: self.resource_id.into(),
+        }
+    }
+}
+
+/// 资源文件下载统计信息
+#[derive(Debug, Clone, Serialize)]
+pub struct ResourceStatsDto {
+    /// 资源ID
+    pub resource_id: String,
+    /// 文件名
+    pub name: String,
+    /// 文件大小
+    pub size: u64,
+    /// 累计下载次数
+    pub downloads: u64,
+    /// 累计独立访客数
+    pub uv: u64,
+    /// 累计传输字节数
+    pub bytes_served: u64,
+    /// 最后下载时间
+    pub last_downloaded_at: Option<i64>,
+}
+
+impl From<ResourceStatsBo> for ResourceStatsDto {
+    fn from(value: ResourceStatsBo) -> Self {
+        Self {
+            resource_id: value.resource_id,
+            name: value.name,
+            size: value.size,
+            downloads: value.downloads,
+            uv: value.uv,
+            bytes_served: value.bytes_served,
+            last_downloaded_at: value.last_downloaded_at,
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::bo::series::{
+    CreateSeriesBo, GetSeriesBo, RemoveSeriesBo, SeriesBo, SeriesDetailsBo, SeriesListItemBo,
+    SetSeriesArticlesBo, UpdateSeriesBo,
+};
+use crate::model::dto::api::article::ArticleListItemDto;
+
+/// 创建系列
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateSeriesDto {
+    /// 标题
+    pub title: String,
+    /// 描述
+    #[serde(default)]
+    pub description: String,
+}
+
+impl Into<CreateSeriesBo> for CreateSeriesDto {
+    fn into(self) -> CreateSeriesBo {
+        CreateSeriesBo {
+            title: self.title,
+            description: self.description,
+        }
+    }
+}
+
+/// 修改系列
+#[derive(Debug, Clone, Deserialize)]
+pub struct UpdateSeriesDto {
+    /// 系列ID
+    pub series_id: String,
+    /// 标题
+    pub title: String,
+    /// 描述
+    #[serde(default)]
+    pub description: String,
+}
+
+impl Into<UpdateSeriesBo> for UpdateSeriesDto {
+    fn into(self) -> UpdateSeriesBo {
+        UpdateSeriesBo {
+            series_id: self.series_id,
+            title: self.title,
+            description: self.description,
+        }
+    }
+}
+
+/// 删除系列
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoveSeriesDto {
+    /// 系列ID
+    pub series_id: String,
+}
+
+impl<'a> Into<RemoveSeriesBo<'a>> for RemoveSeriesDto {
+    fn into(self) -> RemoveSeriesBo<'a> {
+        RemoveSeriesBo {
+            series_id: self.series_id.into(),
+        }
+    }
+}
+
+/// 获取系列详情
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetSeriesDto {
+    /// 系列ID
+    pub series_id: String,
+}
+
+impl<'a> Into<GetSeriesBo<'a>> for GetSeriesDto {
+    fn into(self) -> GetSeriesBo<'a> {
+        GetSeriesBo {
+            series_id: self.series_id.into(),
+        }
+    }
+}
+
+/// 设置系列中的文章
+#[derive(Debug, Clone, Deserialize)]
+pub struct SetSeriesArticlesDto {
+    /// 系列ID
+    pub series_id: String,
+    /// 按顺序排列的文章ID，不在列表中的文章将移出系列
+    pub article_ids: Vec<String>,
+}
+
+impl Into<SetSeriesArticlesBo> for SetSeriesArticlesDto {
+    fn into(self) -> SetSeriesArticlesBo {
+        SetSeriesArticlesBo {
+            series_id: self.series_id,
+            article_ids: self.article_ids,
+        }
+    }
+}
+
+/// 系列
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesDto {
+    /// 系列ID
+    pub series_id: String,
+    /// 标题
+    pub title: String,
+    /// 描述
+    pub description: String,
+    /// 创建时间
+    pub created_at: i64,
+    /// 修改时间
+    pub updated_at: i64,
+}
+
+impl From<SeriesBo> for SeriesDto {
+    fn from(value: SeriesBo) -> Self {
+        Self {
+            series_id: value.series_id,
+            title: value.title,
+            description: value.description,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+/// 系列列表项
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesListItemDto {
+    /// 系列
+    #[serde(flatten)]
+    pub series: SeriesDto,
+    /// 系列中的文章数量（包括草稿）
+    pub article_count: u64,
+}
+
+impl From<SeriesListItemBo> for SeriesListItemDto {
+    fn from(value: SeriesListItemBo) -> Self {
+        Self {
+            series: SeriesDto::from(value.series),
+            article_count: value.article_count,
+        }
+    }
+}
+
+/// 系列详情
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesDetailsDto {
+    /// 系列
+    #[serde(flatten)]
+    pub series: SeriesDto,
+    /// 按系列顺序排列的文章
+    pub articles: Vec<ArticleListItemDto>,
+}
+
+impl From<SeriesDetailsBo> for SeriesDetailsDto {
+    fn from(value: SeriesDetailsBo) -> Self {
+        Self {
+            series: SeriesDto::from(value.series),
+            articles: value
+                .articles
+                .into_iter()
+                .map(ArticleListItemDto::from)
+                .collect(),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::model::bo::system::{InfoBo, InfoCpuBo, InfoDatabaseBo, InfoDiskBo, InfoSystemBo};
+use crate::model::bo::system::{
+    CacheEntryBo, CacheKindStatsBo, InfoBo, InfoCpuBo, InfoDatabaseBo, InfoDiskBo, InfoResourceBo,
+    InfoSystemBo, InspectCacheBo, RemoveCacheBo, SearchIndexCheckBo,
+};
 
 #[derive(Debug, Deserialize)]
 pub struct SetLogLevelDto {
@@ -20,6 +23,8 @@ pub struct InfoDto {
     pub disks: Vec<InfoDiskDto>,
     /// 数据库信息
     pub database: InfoDatabaseDto,
+    /// 资源存储信息
+    pub resource: InfoResourceDto,
 }
 
 impl From<InfoBo> for InfoDto {
@@ -28,6 +33,24 @@ impl From<InfoBo> for InfoDto {
             system: value.system.into(),
             disks: value.disks.into_iter().map(InfoDiskDto::from).collect(),
             database: value.database.into(),
+            resource: value.resource.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct InfoResourceDto {
+    /// 已占用的存储空间
+    pub used_space: u64,
+    /// 存储空间总配额，为空时不限制
+    pub quota: Option<u64>,
+}
+
+impl From<InfoResourceBo> for InfoResourceDto {
+    fn from(value: InfoResourceBo) -> Self {
+        Self {
+            used_space: value.used_space,
+            quota: value.quota,
         }
     }
 }
@@ -144,3 +167,112 @@ impl From<InfoDiskBo> for InfoDiskDto {
         }
     }
 }
+
+/// 查看缓存数据
+#[derive(Debug, Clone, Deserialize)]
+pub struct InspectCacheDto {
+    /// 缓存类型
+    pub kind: String,
+    /// 缓存ID
+    pub id: String,
+}
+
+impl<'a> Into<InspectCacheBo<'a>> for InspectCacheDto {
+    fn into(self) -> InspectCacheBo<'a> {
+        InspectCacheBo {
+            kind: self.kind.into(),
+            id: self.id.into(),
+        }
+    }
+}
+
+/// 删除缓存数据
+#[derive(Debug, Clone, Deserialize)]
+pub struct RemoveCacheDto {
+    /// 缓存类型
+    pub kind: String,
+    /// 缓存ID前缀，为空时删除该类型的所有缓存
+    #[serde(default)]
+    pub id_prefix: String,
+}
+
+impl<'a> Into<RemoveCacheBo<'a>> for RemoveCacheDto {
+    fn into(self) -> RemoveCacheBo<'a> {
+        RemoveCacheBo {
+            kind: self.kind.into(),
+            id_prefix: self.id_prefix.into(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheKindStatsDto {
+    /// 缓存类型
+    pub kind: String,
+    /// 缓存数量
+    pub total: u64,
+    /// 已过期但尚未清理的缓存数量
+    pub expired: u64,
+    /// 缓存数据大小
+    pub data_size: u64,
+}
+
+impl From<CacheKindStatsBo> for CacheKindStatsDto {
+    fn from(value: CacheKindStatsBo) -> Self {
+        Self {
+            kind: value.kind,
+            total: value.total,
+            expired: value.expired,
+            data_size: value.data_size,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CacheEntryDto {
+    /// 缓存ID
+    pub id: String,
+    /// 缓存类型
+    pub kind: String,
+    /// 缓存数据
+    pub data: serde_json::Value,
+    /// 创建时间
+    pub created_at: i64,
+    /// 过期时间
+    pub expires_at: i64,
+    /// 是否已过期
+    pub is_expired: bool,
+}
+
+impl From<CacheEntryBo> for CacheEntryDto {
+    fn from(value: CacheEntryBo) -> Self {
+        Self {
+            id: value.id,
+            kind: value.kind,
+            data: value.data,
+            created_at: value.created_at,
+            expires_at: value.expires_at,
+            is_expired: value.is_expired,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchIndexCheckDto {
+    /// 全文索引表
+    pub table: String,
+    /// 检查是否通过
+    pub ok: bool,
+    /// 检查未通过时的错误信息
+    pub message: Option<String>,
+}
+
+impl From<SearchIndexCheckBo> for SearchIndexCheckDto {
+    fn from(value: SearchIndexCheckBo) -> Self {
+        Self {
+            table: value.table,
+            ok: value.ok,
+            message: value.message,
+        }
+    }
+}
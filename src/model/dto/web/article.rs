@@ -5,15 +5,18 @@ use boluo::request::Request;
 use serde::{Deserialize, Serialize};
 
 use crate::model::bo::article::{
-    CreateArticleBo, GetArticleBo, SearchArticleBo, UnlockArticleBo, UpdateArticleBo,
+    ArchiveArticleBo, CreateArticleBo, GetArticleBo, SearchArticleBo, UnlockArticleBo,
+    UpdateArticleBo,
 };
-use crate::model::common::article::ArticleStatus;
+use crate::model::common::article::{ArticleSort, ArticleStatus};
 
 /// 搜索文章
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SearchArticleDto {
     /// 全文搜索
     pub q: Option<String>,
+    /// 排序方式
+    pub sort: Option<ArticleSort>,
     /// 分页页码
     pub page: Option<u64>,
     /// 分页大小
@@ -27,8 +30,12 @@ impl<'a> Into<SearchArticleBo<'a>> for SearchArticleDto {
             status: Some(ArticleStatus::Published),
             published_at_ge: None,
             published_at_lt: None,
+            sort: self.sort,
             page: self.page,
             size: self.size,
+            cursor: None,
+            with_total: true,
+            pinned_first: false,
         }
     }
 }
@@ -40,12 +47,69 @@ impl<'a> Into<SearchArticleBo<'a>> for &'a SearchArticleDto {
             status: None,
             published_at_ge: None,
             published_at_lt: None,
+            sort: self.sort,
             page: self.page,
             size: self.size,
+            cursor: None,
+            with_total: true,
+            // 未进行全文搜索时将置顶文章排在最前
+            pinned_first: self.q.as_deref().is_none_or(|q| q.trim().is_empty()),
         }
     }
 }
 
+/// 列出归档文章
+#[derive(Debug, Clone)]
+pub struct ArchiveArticleDto {
+    /// 年份
+    pub year: i16,
+    /// 月份
+    pub month: Option<i8>,
+    /// 分页页码
+    pub page: Option<u64>,
+    /// 分页大小
+    pub size: Option<u64>,
+}
+
+impl Into<ArchiveArticleBo> for &ArchiveArticleDto {
+    fn into(self) -> ArchiveArticleBo {
+        ArchiveArticleBo {
+            year: self.year,
+            month: self.month,
+            page: self.page,
+            size: self.size,
+        }
+    }
+}
+
+impl FromRequest for ArchiveArticleDto {
+    type Error = BoxError;
+
+    async fn from_request(request: &mut Request) -> Result<Self, Self::Error> {
+        #[derive(Deserialize)]
+        struct PathParams {
+            year: i16,
+            #[serde(default)]
+            month: Option<i8>,
+        }
+        #[derive(Deserialize)]
+        struct FormParams {
+            page: Option<u64>,
+            size: Option<u64>,
+        }
+
+        let Path(path_params) = Path::<PathParams>::from_request(request).await?;
+        let Form(form_params) = Form::<FormParams>::from_request(request).await?;
+
+        Ok(Self {
+            year: path_params.year,
+            month: path_params.month,
+            page: form_params.page,
+            size: form_params.size,
+        })
+    }
+}
+
 /// 获取文章详情
 #[derive(Debug, Clone, Deserialize)]
 pub struct GetArticleDto {
@@ -58,6 +122,7 @@ impl<'a> Into<GetArticleBo<'a>> for GetArticleDto {
         GetArticleBo {
             article_id: self.article_id.into(),
             ignore_status: false,
+            referrer: None,
         }
     }
 }
@@ -139,6 +204,7 @@ impl<'a> Into<GetArticleBo<'a>> for UpdateArticleDto {
         GetArticleBo {
             article_id: self.article_id.into(),
             ignore_status: false,
+            referrer: None,
         }
     }
 }
@@ -156,6 +222,10 @@ pub struct UpdateArticleSubmitDto {
     pub password: Option<String>,
     /// 状态
     pub status: ArticleStatus,
+    /// 是否置顶
+    pub pinned: bool,
+    /// 是否为精选文章
+    pub featured: bool,
 }
 
 impl Into<UpdateArticleBo> for UpdateArticleSubmitDto {
@@ -166,6 +236,8 @@ impl Into<UpdateArticleBo> for UpdateArticleSubmitDto {
             markdown_content: self.markdown_content,
             password: self.password.filter(|v| !v.is_empty()),
             status: self.status,
+            pinned: Some(self.pinned),
+            featured: Some(self.featured),
         }
     }
 }
@@ -184,6 +256,9 @@ impl FromRequest for UpdateArticleSubmitDto {
             pub markdown_content: String,
             pub password: Option<String>,
             pub status: ArticleStatus,
+            /// 复选框未选中时不提交
+            pub pinned: Option<String>,
+            pub featured: Option<String>,
         }
 
         let Path(path_params) = Path::<PathParams>::from_request(request).await?;
@@ -195,6 +270,8 @@ impl FromRequest for UpdateArticleSubmitDto {
             markdown_content: form_params.markdown_content,
             password: form_params.password,
             status: form_params.status,
+            pinned: form_params.pinned.is_some(),
+            featured: form_params.featured.is_some(),
         })
     }
 }
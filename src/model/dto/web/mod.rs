@@ -1,2 +1,3 @@
 pub mod article;
 pub mod auth;
+pub mod series;
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+use crate::model::bo::series::GetSeriesBo;
+
+/// 获取系列详情
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetSeriesDto {
+    /// 系列ID
+    pub series_id: String,
+}
+
+impl<'a> Into<GetSeriesBo<'a>> for GetSeriesDto {
+    fn into(self) -> GetSeriesBo<'a> {
+        GetSeriesBo {
+            series_id: self.series_id.into(),
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use std::borrow::Cow;
+use serde::{Deserialize, Serialize};
 
-use crate::model::common::article::ArticleStatus;
+use crate::model::common::article::{ArticleSort, ArticleStatus};
+use crate::util::fts::SearchQuery;
 
 /// 文章
 #[derive(Debug, Clone, sqlx::FromRow)]
@@ -25,13 +26,17 @@ pub struct ArticlePo {
     pub updated_at: i64,
     /// 发布时间
     pub published_at: Option<i64>,
+    /// 置顶时间，为空时未置顶
+    pub pinned_at: Option<i64>,
+    /// 是否为精选文章
+    pub featured: bool,
 }
 
 /// 搜索文章
 #[derive(Debug, Clone)]
-pub struct SearchArticle<'a> {
+pub struct SearchArticle {
     /// 全文搜索
-    pub full_text: Option<Cow<'a, str>>,
+    pub query: Option<SearchQuery>,
     /// 状态
     pub status: Option<ArticleStatus>,
     /// 发布时间（大于等于）
@@ -40,4 +45,62 @@ pub struct SearchArticle<'a> {
     pub published_at_lt: Option<i64>,
     /// 是否需要密码访问
     pub need_password: Option<bool>,
+    /// 排序方式
+    pub sort: ArticleSort,
+    /// 游标分页的起始位置，仅列出排在该位置之后的文章
+    pub cursor: Option<ArticleCursor>,
+    /// 是否将置顶文章排在最前
+    pub pinned_first: bool,
+}
+
+/// 文章列表游标分页的位置，即上一页最后一篇文章的排序字段
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ArticleCursor {
+    /// 排序方式，仅支持按发布时间排序
+    pub sort: ArticleSort,
+    /// 发布时间
+    pub published_at: Option<i64>,
+    /// 修改时间
+    pub updated_at: i64,
+    /// 文章ID
+    pub id: String,
+}
+
+impl ArticleCursor {
+    /// 判断排序方式是否支持游标分页
+    pub fn supports(sort: ArticleSort) -> bool {
+        matches!(sort, ArticleSort::Newest | ArticleSort::Oldest)
+    }
+}
+
+/// 文章搜索结果
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SearchArticleItem {
+    /// 文章
+    #[sqlx(flatten)]
+    pub article: ArticlePo,
+    /// 带高亮标记的标题，仅全文搜索时存在
+    pub title_highlight: Option<String>,
+    /// 带高亮标记的正文片段，仅全文搜索时存在
+    pub snippet: Option<String>,
+}
+
+/// 按月份汇总的已发布文章数量
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ArchiveMonth {
+    /// 年份（UTC）
+    pub year: i64,
+    /// 月份（UTC）
+    pub month: i64,
+    /// 文章数量
+    pub count: u64,
+}
+
+/// 相邻文章的方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Adjacent {
+    /// 上一篇，发布时间更早的文章
+    Previous,
+    /// 下一篇，发布时间更晚的文章
+    Next,
 }
@@ -10,3 +10,14 @@ pub struct ArticleAttachmentPo {
     /// 创建时间
     pub created_at: i64,
 }
+
+/// 全文搜索匹配的附件
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct MatchedAttachment {
+    /// 附件ID
+    pub attachment_id: String,
+    /// 文章ID
+    pub article_id: String,
+    /// 附件名
+    pub name: String,
+}
@@ -0,0 +1,21 @@
+/// 文章每日来源域名统计信息
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ArticleDailyReferrerPo {
+    /// 文章ID
+    pub article_id: String,
+    /// 日期（UTC，格式为 YYYY-MM-DD）
+    pub date: String,
+    /// 来源域名
+    pub domain: String,
+    /// 访问次数
+    pub visits: u64,
+}
+
+/// 按来源域名汇总的访问次数
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ReferrerVisits {
+    /// 来源域名
+    pub domain: String,
+    /// 访问次数
+    pub visits: u64,
+}
@@ -0,0 +1,36 @@
+/// 文章每日访问统计信息
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ArticleDailyStatsPo {
+    /// 文章ID
+    pub article_id: String,
+    /// 日期（UTC，格式为 YYYY-MM-DD）
+    pub date: String,
+    /// 当日页面访问量
+    pub pv: u64,
+    /// 当日独立访客数
+    pub uv: u64,
+}
+
+/// 按日期汇总的访问量
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct DailyVisits {
+    /// 日期（UTC，格式为 YYYY-MM-DD）
+    pub date: String,
+    /// 页面访问量
+    pub pv: u64,
+    /// 独立访客数
+    pub uv: u64,
+}
+
+/// 按文章汇总的访问量
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ArticleVisits {
+    /// 文章ID
+    pub article_id: String,
+    /// 文章标题
+    pub title: String,
+    /// 页面访问量
+    pub pv: u64,
+    /// 独立访客数
+    pub uv: u64,
+}
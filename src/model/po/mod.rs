@@ -1,5 +1,9 @@
 pub mod article;
 pub mod article_attachment;
+pub mod article_daily_referrer;
+pub mod article_daily_stats;
 pub mod article_stats;
 pub mod cache;
 pub mod resource;
+pub mod resource_stats;
+pub mod series;
@@ -13,6 +13,8 @@ pub struct ResourcePo {
     pub size: u64,
     /// 文件类型
     pub mime_type: String,
+    /// 根据文件内容探测到的文件类型
+    pub detected_mime_type: String,
     /// 是否公开访问
     pub is_public: bool,
     /// 文件哈希
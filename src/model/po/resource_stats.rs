@@ -0,0 +1,14 @@
+/// 资源文件下载统计信息
+#[derive(Debug, Clone, Default, sqlx::FromRow)]
+pub struct ResourceStatsPo {
+    /// 资源ID
+    pub resource_id: String,
+    /// 累计下载次数
+    pub downloads: u64,
+    /// 累计独立访客数
+    pub uv: u64,
+    /// 累计传输字节数
+    pub bytes_served: u64,
+    /// 更新时间
+    pub updated_at: i64,
+}
@@ -0,0 +1,35 @@
+/// 文章系列
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SeriesPo {
+    /// 系列ID
+    pub id: String,
+    /// 标题
+    pub title: String,
+    /// 描述
+    pub description: String,
+    /// 创建时间
+    pub created_at: i64,
+    /// 修改时间
+    pub updated_at: i64,
+}
+
+/// 系列中的文章
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SeriesArticlePo {
+    /// 系列ID
+    pub series_id: String,
+    /// 文章ID
+    pub article_id: String,
+    /// 在系列中的位置，从 1 开始
+    pub position: i64,
+}
+
+/// 系列列表项
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct SeriesListItem {
+    /// 系列
+    #[sqlx(flatten)]
+    pub series: SeriesPo,
+    /// 系列中的文章数量
+    pub article_count: u64,
+}
@@ -3,10 +3,13 @@ use serde::{Serialize, Serializer};
 
 use crate::error::{AppError, AppErrorMeta};
 use crate::model::bo::article::{
-    ArticleAttachmentBo, ArticleDetailsBo, ArticleListBo, ArticleListItemBo,
+    ArchiveMonthBo, ArchiveYearBo, ArticleAttachmentBo, ArticleDetailsBo, ArticleLinkBo,
+    ArticleListBo, ArticleListItemBo, ArticleVisitsBo, MatchedAttachmentBo,
 };
+use crate::model::bo::series::ArticleSeriesBo;
 use crate::model::common::article::ArticleStatus;
 use crate::model::dto::web::article::SearchArticleDto;
+use crate::model::vo::series::ArticleSeriesVo;
 use crate::template::render::TemplateRenderData;
 use crate::util::pagination::PageNavigation;
 
@@ -25,8 +28,16 @@ pub struct ArticleListItemVo {
     pub updated_at: i64,
     /// 发布时间
     pub published_at: Option<i64>,
+    /// 是否置顶
+    pub pinned: bool,
     /// 是否需要密码访问
     pub need_password: bool,
+    /// 高亮匹配关键词的标题（HTML），仅全文搜索时存在
+    pub title_html: Option<String>,
+    /// 高亮匹配关键词的正文片段（HTML），仅全文搜索时存在
+    pub snippet_html: Option<String>,
+    /// 附件文本与搜索语句匹配的附件，仅全文搜索时存在
+    pub matched_attachments: Vec<MatchedAttachmentVo>,
 }
 
 impl From<ArticleListItemBo> for ArticleListItemVo {
@@ -38,7 +49,57 @@ impl From<ArticleListItemBo> for ArticleListItemVo {
             created_at: value.created_at,
             updated_at: value.updated_at,
             published_at: value.published_at,
+            pinned: value.pinned,
             need_password: value.need_password,
+            title_html: value.title_html,
+            snippet_html: value.snippet_html,
+            matched_attachments: value
+                .matched_attachments
+                .into_iter()
+                .map(MatchedAttachmentVo::from)
+                .collect(),
+        }
+    }
+}
+
+/// 全文搜索匹配的附件
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchedAttachmentVo {
+    /// 附件ID
+    pub attachment_id: String,
+    /// 附件名
+    pub name: String,
+}
+
+impl From<MatchedAttachmentBo> for MatchedAttachmentVo {
+    fn from(value: MatchedAttachmentBo) -> Self {
+        Self {
+            attachment_id: value.attachment_id,
+            name: value.name,
+        }
+    }
+}
+
+/// 热门文章列表项
+#[derive(Debug, Clone, Serialize)]
+pub struct PopularArticleVo {
+    /// 文章ID
+    pub article_id: String,
+    /// 标题
+    pub title: String,
+    /// 统计期间的页面访问量
+    pub pv: u64,
+    /// 统计期间的独立访客数
+    pub uv: u64,
+}
+
+impl From<ArticleVisitsBo> for PopularArticleVo {
+    fn from(value: ArticleVisitsBo) -> Self {
+        Self {
+            article_id: value.article_id,
+            title: value.title,
+            pv: value.pv,
+            uv: value.uv,
         }
     }
 }
@@ -52,6 +113,8 @@ pub struct ArticleListVo {
     pub page_navigation: PageNavigation,
     /// 文章搜索条件
     pub search: SearchArticleDto,
+    /// 归档时段，仅归档页面存在
+    pub archive: Option<ArchivePeriodVo>,
 }
 
 impl ArticleListVo {
@@ -66,8 +129,83 @@ impl ArticleListVo {
                 .collect(),
             page_navigation,
             search,
+            archive: None,
         })
     }
+
+    pub fn with_archive(mut self, year: i16, month: Option<i8>) -> Self {
+        self.archive = Some(ArchivePeriodVo { year, month });
+        self
+    }
+}
+
+/// 归档时段
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchivePeriodVo {
+    /// 年份
+    pub year: i16,
+    /// 月份，为空时表示全年
+    pub month: Option<i8>,
+}
+
+/// 文章归档页面
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchivesVo {
+    /// 按年份分组的文章数量
+    pub years: Vec<ArchiveYearVo>,
+}
+
+impl From<Vec<ArchiveYearBo>> for ArchivesVo {
+    fn from(years: Vec<ArchiveYearBo>) -> Self {
+        Self {
+            years: years.into_iter().map(ArchiveYearVo::from).collect(),
+        }
+    }
+}
+
+impl TemplateRenderData for ArchivesVo {
+    fn template_name() -> &'static str {
+        "article/archives.html"
+    }
+}
+
+/// 文章归档中的年份
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveYearVo {
+    /// 年份
+    pub year: i64,
+    /// 全年的文章数量
+    pub count: u64,
+    /// 有文章发布的月份
+    pub months: Vec<ArchiveMonthVo>,
+}
+
+impl From<ArchiveYearBo> for ArchiveYearVo {
+    fn from(value: ArchiveYearBo) -> Self {
+        Self {
+            year: value.year,
+            count: value.count,
+            months: value.months.into_iter().map(ArchiveMonthVo::from).collect(),
+        }
+    }
+}
+
+/// 文章归档中的月份
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveMonthVo {
+    /// 月份
+    pub month: i64,
+    /// 文章数量
+    pub count: u64,
+}
+
+impl From<ArchiveMonthBo> for ArchiveMonthVo {
+    fn from(value: ArchiveMonthBo) -> Self {
+        Self {
+            month: value.month,
+            count: value.count,
+        }
+    }
 }
 
 impl TemplateRenderData for ArticleListVo {
@@ -139,6 +277,24 @@ impl Serialize for ArticleAttachmentVo {
     }
 }
 
+/// 文章链接
+#[derive(Debug, Clone, Serialize)]
+pub struct ArticleLinkVo {
+    /// 文章ID
+    pub article_id: String,
+    /// 标题
+    pub title: String,
+}
+
+impl From<ArticleLinkBo> for ArticleLinkVo {
+    fn from(value: ArticleLinkBo) -> Self {
+        Self {
+            article_id: value.article_id,
+            title: value.title,
+        }
+    }
+}
+
 /// 文章详情页面
 #[derive(Debug, Clone, Serialize)]
 pub struct ArticleDetailsVo {
@@ -166,6 +322,39 @@ pub struct ArticleDetailsVo {
     pub pv: u64,
     /// 累计独立访客数
     pub uv: u64,
+    /// 上一篇（发布时间更早）
+    pub prev: Option<ArticleLinkVo>,
+    /// 下一篇（发布时间更晚）
+    pub next: Option<ArticleLinkVo>,
+    /// 所在系列的导航信息
+    pub series: Option<ArticleSeriesVo>,
+    /// 热门文章
+    pub popular_articles: Vec<PopularArticleVo>,
+    /// 相关文章
+    pub related_articles: Vec<ArticleListItemVo>,
+}
+
+impl ArticleDetailsVo {
+    pub fn with_widgets(
+        mut self,
+        popular_articles: Vec<ArticleVisitsBo>,
+        related_articles: Vec<ArticleListItemBo>,
+    ) -> Self {
+        self.popular_articles = popular_articles
+            .into_iter()
+            .map(PopularArticleVo::from)
+            .collect();
+        self.related_articles = related_articles
+            .into_iter()
+            .map(ArticleListItemVo::from)
+            .collect();
+        self
+    }
+
+    pub fn with_series(mut self, series: Option<ArticleSeriesBo>) -> Self {
+        self.series = series.map(ArticleSeriesVo::from);
+        self
+    }
 }
 
 impl From<ArticleDetailsBo> for ArticleDetailsVo {
@@ -188,6 +377,11 @@ impl From<ArticleDetailsBo> for ArticleDetailsVo {
                     .collect(),
                 pv: bo.pv,
                 uv: bo.uv,
+                prev: bo.adjacent.prev.map(ArticleLinkVo::from),
+                next: bo.adjacent.next.map(ArticleLinkVo::from),
+                series: None,
+                popular_articles: vec![],
+                related_articles: vec![],
             },
             ArticleDetailsBo::Admin(bo) => Self {
                 article_id: bo.article_id,
@@ -206,6 +400,11 @@ impl From<ArticleDetailsBo> for ArticleDetailsVo {
                     .collect(),
                 pv: bo.pv,
                 uv: bo.uv,
+                prev: bo.adjacent.prev.map(ArticleLinkVo::from),
+                next: bo.adjacent.next.map(ArticleLinkVo::from),
+                series: None,
+                popular_articles: vec![],
+                related_articles: vec![],
             },
         }
     }
@@ -261,6 +460,10 @@ pub struct UpdateArticleVo {
     pub updated_at: i64,
     /// 发布时间
     pub published_at: Option<i64>,
+    /// 是否置顶
+    pub pinned: bool,
+    /// 是否为精选文章
+    pub featured: bool,
     /// 访问密码
     pub password: Option<String>,
     /// 附件列表
@@ -287,6 +490,8 @@ impl TryFrom<ArticleDetailsBo> for UpdateArticleVo {
                 created_at: bo.created_at,
                 updated_at: bo.updated_at,
                 published_at: bo.published_at,
+                pinned: bo.pinned_at.is_some(),
+                featured: bo.featured,
                 password: bo.password,
                 attachments: bo
                     .attachments
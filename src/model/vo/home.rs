@@ -1,10 +1,35 @@
 use serde::Serialize;
 
+use crate::model::bo::article::{ArticleListItemBo, ArticleVisitsBo};
+use crate::model::vo::article::{ArticleListItemVo, PopularArticleVo};
 use crate::template::render::TemplateRenderData;
 
 /// 首页
 #[derive(Debug, Clone, Serialize)]
-pub struct HomeVo;
+pub struct HomeVo {
+    /// 精选文章
+    pub featured_articles: Vec<ArticleListItemVo>,
+    /// 热门文章
+    pub popular_articles: Vec<PopularArticleVo>,
+}
+
+impl HomeVo {
+    pub fn from_entities(
+        featured_articles: Vec<ArticleListItemBo>,
+        popular_articles: Vec<ArticleVisitsBo>,
+    ) -> Self {
+        Self {
+            featured_articles: featured_articles
+                .into_iter()
+                .map(ArticleListItemVo::from)
+                .collect(),
+            popular_articles: popular_articles
+                .into_iter()
+                .map(PopularArticleVo::from)
+                .collect(),
+        }
+    }
+}
 
 impl TemplateRenderData for HomeVo {
     fn template_name() -> &'static str {
@@ -4,3 +4,4 @@ pub mod auth;
 pub mod error;
 pub mod home;
 pub mod rss;
+pub mod series;
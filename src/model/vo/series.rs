@@ -0,0 +1,72 @@
+use serde::Serialize;
+
+use crate::model::bo::series::{ArticleSeriesBo, SeriesDetailsBo};
+use crate::model::vo::article::{ArticleLinkVo, ArticleListItemVo};
+use crate::template::render::TemplateRenderData;
+
+/// 系列详情页面
+#[derive(Debug, Clone, Serialize)]
+pub struct SeriesDetailsVo {
+    /// 系列ID
+    pub series_id: String,
+    /// 标题
+    pub title: String,
+    /// 描述
+    pub description: String,
+    /// 修改时间
+    pub updated_at: i64,
+    /// 按系列顺序排列的文章
+    pub articles: Vec<ArticleListItemVo>,
+}
+
+impl From<SeriesDetailsBo> for SeriesDetailsVo {
+    fn from(value: SeriesDetailsBo) -> Self {
+        Self {
+            series_id: value.series.series_id,
+            title: value.series.title,
+            description: value.series.description,
+            updated_at: value.series.updated_at,
+            articles: value
+                .articles
+                .into_iter()
+                .map(ArticleListItemVo::from)
+                .collect(),
+        }
+    }
+}
+
+impl TemplateRenderData for SeriesDetailsVo {
+    fn template_name() -> &'static str {
+        "series/detail.html"
+    }
+}
+
+/// 文章所在系列的导航信息
+#[derive(Debug, Clone, Serialize)]
+pub struct ArticleSeriesVo {
+    /// 系列ID
+    pub series_id: String,
+    /// 系列标题
+    pub title: String,
+    /// 文章在系列中的序号，从 1 开始
+    pub position: u64,
+    /// 系列中的文章总数
+    pub total: u64,
+    /// 系列中的上一篇
+    pub prev: Option<ArticleLinkVo>,
+    /// 系列中的下一篇
+    pub next: Option<ArticleLinkVo>,
+}
+
+impl From<ArticleSeriesBo> for ArticleSeriesVo {
+    fn from(value: ArticleSeriesBo) -> Self {
+        Self {
+            series_id: value.series_id,
+            title: value.title,
+            position: value.position,
+            total: value.total,
+            prev: value.prev.map(ArticleLinkVo::from),
+            next: value.next.map(ArticleLinkVo::from),
+        }
+    }
+}
@@ -1,30 +1,30 @@
-use std::borrow::Cow;
+use std::collections::HashMap;
 use std::sync::LazyLock;
-use std::time::Duration;
 
+use futures_util::TryStreamExt;
 use regex::Regex;
 
 use crate::error::{AppError, AppErrorMeta};
 use crate::model::bo::article::{
-    AdminArticleDetailsBo, ArticleAttachmentBo, ArticleBo, ArticleDetailsBo, ArticleListBo,
+    AdjacentArticlesBo, AdminArticleDetailsBo, ArchiveArticleBo, ArchiveYearBo,
+    ArticleAttachmentBo, ArticleBo, ArticleDetailsBo, ArticleLinkBo, ArticleListBo,
     ArticleListItemBo, CreateArticleBo, DownloadArticleAttachmentBo, GetArticleBo,
-    RemoveArticleAttachmentBo, RemoveArticleBo, SearchArticleBo, UnlockArticleBo, UpdateArticleBo,
-    UploadArticleAttachmentBo, VisitorArticleDetailsBo,
+    MatchedAttachmentBo, RemoveArticleAttachmentBo, RemoveArticleBo, SearchArticleBo,
+    UnlockArticleBo, UpdateArticleBo, UploadArticleAttachmentBo, VisitorArticleDetailsBo,
 };
 use crate::model::bo::auth::AdminBo;
-use crate::model::bo::resource::{RemoveResourceBo, UploadResourceOptionsBo};
+use crate::model::bo::resource::{RemoveResourceBo, ResourceBo, UploadResourceOptionsBo};
 use crate::model::bo::visitor::VisitorBo;
-use crate::model::co::article::VisitorArticleAccessRecordCo;
-use crate::model::common::article::ArticleStatus;
-use crate::model::po::article::{ArticlePo, SearchArticle};
+use crate::model::common::article::{ArticleSort, ArticleStatus};
+use crate::model::po::article::{Adjacent, ArticleCursor, ArticlePo, SearchArticle};
 use crate::model::po::article_attachment::ArticleAttachmentPo;
 use crate::model::po::article_stats::ArticleStatsPo;
 use crate::model::po::resource::ResourcePo;
-use crate::storage::cache::CacheData;
-use crate::storage::cache::storage::CacheSetMode;
 use crate::storage::db::DbConn;
+use crate::storage::object::ObjectStorage;
+use crate::util::fts::SearchQuery;
 use crate::util::join::HashJoin;
-use crate::util::pagination::PageData;
+use crate::util::pagination::{Offset, PageData};
 use crate::util::time::UnixTimestampSecs;
 
 /// 解锁文章（获取访问令牌）
@@ -65,6 +65,8 @@ pub async fn create_article(bo: CreateArticleBo, db: &mut DbConn) -> Result<Arti
                 ArticleStatus::Draft => None,
                 ArticleStatus::Published => Some(now),
             },
+            pinned_at: None,
+            featured: false,
         };
         let stats = ArticleStatsPo {
             id: crate::util::uuid::v4(),
@@ -103,6 +105,16 @@ pub async fn update_article(bo: UpdateArticleBo, db: &mut DbConn) -> Result<(),
             ArticleStatus::Published => Some(now),
         },
     };
+    if let Some(pinned) = bo.pinned {
+        // 保持已置顶文章的置顶时间，以免改变置顶文章之间的顺序
+        article.pinned_at = match pinned {
+            true => article.pinned_at.or(Some(now)),
+            false => None,
+        };
+    }
+    if let Some(featured) = bo.featured {
+        article.featured = featured;
+    }
 
     crate::storage::db::article::update(&article, db).await?;
     Ok(())
@@ -118,6 +130,9 @@ pub async fn remove_article(bo: &RemoveArticleBo<'_>, db: &mut DbConn) -> Result
             crate::storage::db::article_attachment::list_by_article_id(&article.id, tx).await?;
         crate::storage::db::article::remove(&article.id, tx).await?;
         crate::storage::db::article_attachment::remove_by_article_id(&article.id, tx).await?;
+        crate::storage::db::article_daily_stats::remove_by_article_id(&article.id, tx).await?;
+        crate::storage::db::article_daily_referrer::remove_by_article_id(&article.id, tx).await?;
+        crate::storage::db::series_article::remove_by_article_id(&article.id, tx).await?;
 
         // 清理文章附件对应的资源文件。文章附件对应的资源文件不会在其他地方复用，所以删除不会引起
         // 其他内容的错误。
@@ -139,33 +154,95 @@ pub async fn search_article(
     db: &mut DbConn,
 ) -> Result<ArticleListBo, AppError> {
     let page = bo.page()?;
-    let offset = page.to_offset()?;
+    let cursor = bo.cursor()?;
+    // 游标分页从游标位置开始，多查询一条数据用于判断是否存在下一页
+    let offset = match &cursor {
+        Some(_) => Offset {
+            offset: 0,
+            size: page.size + 1,
+        },
+        None => Offset {
+            size: page.size + 1,
+            ..page.to_offset()?
+        },
+    };
 
-    let full_text = bo.trim_full_text();
+    let query = bo.trim_full_text().map(SearchQuery::parse);
+    let (after, before) = match &query {
+        Some(query) => query.published_at_range()?,
+        None => (None, None),
+    };
     let params = SearchArticle {
-        full_text: full_text.map(Cow::from),
         status: if admin.is_some() {
             bo.status
         } else {
             Some(ArticleStatus::Published)
         },
-        published_at_ge: bo.published_at_ge,
-        published_at_lt: bo.published_at_lt,
-        need_password: if full_text.is_some() && admin.is_none() {
+        published_at_ge: bo.published_at_ge.into_iter().chain(after).max(),
+        published_at_lt: bo.published_at_lt.into_iter().chain(before).min(),
+        need_password: if query.is_some() && admin.is_none() {
             Some(false)
         } else {
             None
         },
+        sort: bo.sort(),
+        query,
+        cursor,
+        pinned_first: bo.pinned_first,
     };
 
-    let items = crate::storage::db::article::search(&params, offset, db).await?;
-    let total = crate::storage::db::article::search_count(&params, db).await?;
+    let mut items = crate::storage::db::article::search(&params, offset, db).await?;
+    let has_next = items.len() > usize::try_from(page.size)?;
+    items.truncate(usize::try_from(page.size)?);
+    let total = match bo.with_total {
+        true => Some(crate::storage::db::article::search_count(&params, db).await?),
+        false => None,
+    };
 
-    let items = items.into_iter().map(ArticleListItemBo::from).collect();
+    let mut items = items
+        .into_iter()
+        .map(ArticleListItemBo::from)
+        .collect::<Vec<_>>();
+
+    let next_cursor = match items.last() {
+        Some(item) if has_next && ArticleCursor::supports(params.sort) && !params.pinned_first => {
+            let cursor = ArticleCursor {
+                sort: params.sort,
+                published_at: item.published_at,
+                updated_at: item.updated_at,
+                id: item.article_id.clone(),
+            };
+            Some(crate::util::pagination::encode_cursor(&cursor)?)
+        }
+        _ => None,
+    };
+
+    // 标出附件文本与搜索语句匹配的附件
+    if let Some(query) = &params.query {
+        let article_ids = items
+            .iter()
+            .map(|item| item.article_id.as_str())
+            .collect::<Vec<_>>();
+        let matched =
+            crate::storage::db::article_attachment::list_matched(query, &article_ids, db).await?;
+        let mut matched_by_article = HashMap::<_, Vec<_>>::new();
+        for attachment in matched {
+            matched_by_article
+                .entry(attachment.article_id.clone())
+                .or_default()
+                .push(MatchedAttachmentBo::from(attachment));
+        }
+        for item in &mut items {
+            if let Some(matched) = matched_by_article.remove(&item.article_id) {
+                item.matched_attachments = matched;
+            }
+        }
+    }
 
     Ok(ArticleListBo {
         data: PageData::from_vec(items)?.with_total(total),
         page,
+        next_cursor,
     })
 }
 
@@ -192,11 +269,13 @@ pub async fn get_article(
     let attachments = list_attachment(&article.id, db).await?;
 
     if admin.is_none() {
-        if let Err(e) = update_article_visit_stats(&article, visitor, db).await {
-            tracing::error!("更新文章访问记录信息失败：{e}");
+        let referrer = bo.referrer.as_deref();
+        if let Err(e) = crate::service::article_stats::record_visit(&article.id, visitor, referrer)
+        {
+            tracing::error!("记录文章访问信息失败：{e}");
         }
     }
-    let Some(stats) =
+    let Some(mut stats) =
         crate::storage::db::article_stats::find_by_article_id(&article.id, db).await?
     else {
         return Err(AppErrorMeta::Internal.with_context(format!(
@@ -204,22 +283,87 @@ pub async fn get_article(
             article.id
         )));
     };
+    stats.pv += crate::service::article_stats::pending_pv(&article.id);
+
+    // 访客只能看到已发布的相邻文章
+    let status = admin.is_none().then_some(ArticleStatus::Published);
+    let adjacent = AdjacentArticlesBo {
+        prev: crate::storage::db::article::find_adjacent(&article, Adjacent::Previous, status, db)
+            .await?
+            .map(ArticleLinkBo::from),
+        next: crate::storage::db::article::find_adjacent(&article, Adjacent::Next, status, db)
+            .await?
+            .map(ArticleLinkBo::from),
+    };
 
     if admin.is_some() {
-        let details = AdminArticleDetailsBo::from_entities(article, attachments, stats);
+        let details = AdminArticleDetailsBo::from_entities(article, attachments, stats, adjacent);
         Ok(Some(ArticleDetailsBo::from(details)))
     } else {
-        let details = VisitorArticleDetailsBo::from_entities(article, attachments, stats);
+        let details = VisitorArticleDetailsBo::from_entities(article, attachments, stats, adjacent);
         Ok(Some(ArticleDetailsBo::from(details)))
     }
 }
 
+/// 获取精选的已发布文章
+pub async fn list_featured_articles(
+    limit: u64,
+    db: &mut DbConn,
+) -> Result<Vec<ArticleListItemBo>, AppError> {
+    let articles = crate::storage::db::article::list_featured(limit, db).await?;
+    Ok(articles.into_iter().map(ArticleListItemBo::from).collect())
+}
+
+/// 获取按年份、月份分组的已发布文章数量
+pub async fn list_archives(db: &mut DbConn) -> Result<Vec<ArchiveYearBo>, AppError> {
+    let months = crate::storage::db::article::list_archive_months(db).await?;
+    Ok(ArchiveYearBo::from_months(months))
+}
+
+/// 列出指定年份或月份发布的文章，按发布时间从新到旧排序
+pub async fn list_archive_articles(
+    admin: Option<&AdminBo>,
+    bo: &ArchiveArticleBo,
+    db: &mut DbConn,
+) -> Result<ArticleListBo, AppError> {
+    let (published_at_ge, published_at_lt) = bo.published_at_range()?;
+    let search = SearchArticleBo {
+        full_text: None,
+        status: Some(ArticleStatus::Published),
+        published_at_ge: Some(published_at_ge),
+        published_at_lt,
+        sort: Some(ArticleSort::Newest),
+        page: bo.page,
+        size: bo.size,
+        cursor: None,
+        with_total: true,
+        pinned_first: false,
+    };
+    search_article(admin, &search, db).await
+}
+
+/// 获取与文章相关的已发布文章，以文章标题中的关键词进行全文检索
+pub async fn list_related_articles(
+    article_id: &str,
+    title: &str,
+    limit: u64,
+    db: &mut DbConn,
+) -> Result<Vec<ArticleListItemBo>, AppError> {
+    let keywords = title_keywords(title);
+    if keywords.is_empty() {
+        return Ok(vec![]);
+    }
+    let articles =
+        crate::storage::db::article::list_related(article_id, &keywords, limit, db).await?;
+    Ok(articles.into_iter().map(ArticleListItemBo::from).collect())
+}
+
 /// 上传文章附件
 pub async fn upload_attachment(
     bo: UploadArticleAttachmentBo<'_>,
     db: &mut DbConn,
 ) -> Result<ArticleAttachmentBo, AppError> {
-    crate::storage::db::transaction(db, async |tx| {
+    let (attachment, resource) = crate::storage::db::transaction(db, async |tx| {
         let Some(article) = crate::storage::db::article::find(&bo.article_id, tx).await? else {
             return Err(AppErrorMeta::NotFound.with_message("文章不存在，无法上传附件"));
         };
@@ -240,9 +384,17 @@ pub async fn upload_attachment(
             crate::service::resource::upload_resource_with_options(bo.attachment, options, tx)
                 .await?;
 
-        Ok(ArticleAttachmentBo::from_entities(attachment, resource))
+        Ok((attachment, resource))
     })
-    .await
+    .await?;
+
+    // 提取附件文本较为耗时，在事务提交后进行，失败时不影响附件上传
+    if let Err(e) = index_attachment_text(&attachment, &resource, db).await {
+        tracing::warn!("附件文本索引失败：{e}（附件ID：{}）", attachment.id);
+    }
+    Ok(ArticleAttachmentBo::from_entities(
+        attachment, resource, None,
+    ))
 }
 
 /// 删除文章附件
@@ -310,6 +462,76 @@ pub async fn download_attachment(
     Ok(Some(resource))
 }
 
+/// 为尚未写入全文索引的附件提取文本并写入索引，返回写入索引的附件数量
+///
+/// 用于补充索引功能上线前上传的附件，以及上传时索引失败的附件，单个附件失败时跳过并继续处理。
+pub async fn index_unindexed_attachments(db: &mut DbConn) -> Result<u64, AppError> {
+    let mut indexed = 0;
+    for attachment in crate::storage::db::article_attachment::list_unindexed(db).await? {
+        let Some(resource) =
+            crate::storage::db::resource::find(&attachment.resource_id, db).await?
+        else {
+            tracing::warn!(
+                "附件关联的资源不存在，跳过文本索引（附件ID：{}，资源ID：{}）",
+                attachment.id,
+                attachment.resource_id
+            );
+            continue;
+        };
+        match index_attachment_text(&attachment, &resource.into(), db).await {
+            Ok(true) => indexed += 1,
+            Ok(false) => {}
+            Err(e) => tracing::warn!("附件文本索引失败：{e}（附件ID：{}）", attachment.id),
+        }
+    }
+    Ok(indexed)
+}
+
+/// 从附件中提取文本并写入全文索引，不支持提取文本或超出大小限制的附件会被跳过，返回是否写入索引
+async fn index_attachment_text(
+    attachment: &ArticleAttachmentPo,
+    resource: &ResourceBo,
+    db: &mut DbConn,
+) -> anyhow::Result<bool> {
+    let config = &crate::config::get().article;
+    if resource.size > u64::try_from(config.attachment_index_max_size)?
+        || !crate::util::extract::is_supported(&resource.detected_mime_type)
+    {
+        return Ok(false);
+    }
+    let Some(object) = crate::storage::object::get()
+        .get(&resource.path, None)
+        .await?
+    else {
+        return Ok(false);
+    };
+    let data = object
+        .stream
+        .try_fold(Vec::new(), |mut data, chunk| async move {
+            data.extend_from_slice(&chunk);
+            Ok(data)
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!(e))?;
+    let Some(text) = crate::util::extract::extract_text(
+        data,
+        &resource.detected_mime_type,
+        config.content_max_size,
+    )
+    .await?
+    else {
+        return Ok(false);
+    };
+    let rows = crate::storage::db::article_attachment::index_text(
+        &attachment.id,
+        &resource.name,
+        &text,
+        db,
+    )
+    .await?;
+    Ok(rows > 0)
+}
+
 async fn list_attachment(
     article_id: &str,
     db: &mut DbConn,
@@ -325,6 +547,18 @@ async fn list_attachment(
     )
     .await?;
 
+    let mut stats = crate::storage::db::resource_stats::list_by_resource_ids(
+        &attachments
+            .iter()
+            .map(|a| a.resource_id.as_str())
+            .collect::<Vec<_>>(),
+        db,
+    )
+    .await?
+    .into_iter()
+    .map(|stats| (stats.resource_id.clone(), stats))
+    .collect::<HashMap<_, _>>();
+
     let mut attachments = HashJoin::new()
         .l_source(attachments)
         .r_source(resources)
@@ -340,9 +574,11 @@ async fn list_attachment(
                         article_id, attachment.id, attachment.resource_id
                     )));
             };
+            let stats = stats.remove(&attachment.resource_id);
             Ok(Some(ArticleAttachmentBo::from_entities(
                 attachment,
                 resource.into(),
+                stats,
             )))
         })?;
 
@@ -354,24 +590,6 @@ async fn list_attachment(
     Ok(attachments)
 }
 
-async fn update_article_visit_stats(
-    article: &ArticlePo,
-    visitor: &VisitorBo,
-    db: &mut DbConn,
-) -> Result<(), AppError> {
-    let cache_data = VisitorArticleAccessRecordCo {
-        visitor_id: visitor.visitor_id().into(),
-        article_id: article.id.as_str().into(),
-    };
-    let cache = cache_data.with_ttl(Duration::from_secs(3600 * 24));
-    let uv_add = match cache.set(CacheSetMode::OnlyIfNotExists).await? {
-        true => 1,
-        false => 0,
-    };
-    crate::storage::db::article_stats::increment_by_article_id(&article.id, 1, uv_add, db).await?;
-    Ok(())
-}
-
 fn clean_markdown_content(markdown: &str) -> String {
     // HTML 标签
     static HTML_TAG_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"<[^>]+>"#).unwrap());
@@ -388,6 +606,21 @@ fn clean_markdown_content(markdown: &str) -> String {
     plain.trim().to_owned()
 }
 
+/// 将标题按文字、数字以外的符号拆分为关键词
+fn title_keywords(title: &str) -> Vec<&str> {
+    /// 关键词最大数量
+    const MAX_KEYWORDS: usize = 8;
+
+    let mut keywords = vec![];
+    for keyword in title.split(|c: char| !c.is_alphanumeric()) {
+        if !keyword.is_empty() && !keywords.contains(&keyword) {
+            keywords.push(keyword);
+        }
+    }
+    keywords.truncate(MAX_KEYWORDS);
+    keywords
+}
+
 fn truncate_excerpt(plain_content: &str) -> String {
     plain_content
         .chars()
@@ -0,0 +1,387 @@
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::Duration;
+
+use jiff::civil::Date;
+use tokio::sync::{mpsc, oneshot};
+
+use crate::error::AppError;
+use crate::model::bo::article::{ArticleAnalyticsBo, ArticleVisitsBo, GetArticleAnalyticsBo};
+use crate::model::bo::visitor::VisitorBo;
+use crate::model::co::article::{VisitorArticleAccessRecordCo, VisitorArticleDailyAccessRecordCo};
+use crate::model::po::article_daily_stats::DailyVisits;
+use crate::storage::cache::CacheData;
+use crate::storage::cache::storage::CacheSetMode;
+use crate::storage::db::{DbConn, DbPool};
+use crate::util::time::UnixTimestampSecs;
+
+/// 尚未写入数据库的文章访问记录，按（日期，文章ID）合并
+static BUFFER: Mutex<BTreeMap<(String, String), PendingVisits>> = Mutex::new(BTreeMap::new());
+
+/// 尚未确认接受 Cookie 的访客的访问记录，按访客ID分组
+static UNCONFIRMED: Mutex<BTreeMap<String, Vec<UnconfirmedVisit>>> = Mutex::new(BTreeMap::new());
+
+static FLUSHER: OnceLock<mpsc::UnboundedSender<FlusherMessage>> = OnceLock::new();
+
+/// 启动文章访问统计写入器，访问记录会在内存中合并后按固定间隔批量写入数据库
+pub fn init(db: DbPool, interval: Duration) -> anyhow::Result<()> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    FLUSHER
+        .set(sender)
+        .map_err(|_| anyhow::anyhow!("重复初始化文章访问统计写入器"))?;
+    tokio::spawn(run_flusher(db, interval, receiver));
+    Ok(())
+}
+
+/// 立即写入缓冲区中的访问记录，写入器继续运行
+///
+/// 在收到关闭信号时调用，避免等待活跃请求处理完成期间进程被强制结束导致访问记录丢失。
+pub async fn flush_now() {
+    send_message(FlusherMessage::Flush).await;
+}
+
+/// 关闭文章访问统计写入器，等待缓冲区中的访问记录全部写入
+pub async fn shutdown() {
+    send_message(FlusherMessage::Shutdown).await;
+}
+
+async fn send_message(message: impl FnOnce(oneshot::Sender<()>) -> FlusherMessage) {
+    let Some(sender) = FLUSHER.get() else {
+        return;
+    };
+    let (done_sender, done_receiver) = oneshot::channel();
+    if sender.send(message(done_sender)).is_ok() {
+        let _ = done_receiver.await;
+    }
+}
+
+/// 记录一次文章访问，访问记录会暂存在内存中等待批量写入
+///
+/// 爬虫与拒绝跟踪的访客的访问不计入统计，尚未确认接受 Cookie 的访客的访问会等到访客确认后再计入统计，
+/// 未确认访客数量达到上限时不再暂存新访客的访问。
+pub fn record_visit(
+    article_id: &str,
+    visitor: &VisitorBo,
+    referrer: Option<&str>,
+) -> anyhow::Result<()> {
+    if !visitor.is_tracked() {
+        return Ok(());
+    }
+    let now = UnixTimestampSecs::now();
+    let visit = UnconfirmedVisit {
+        date: now.into_str_date_utc()?,
+        article_id: article_id.to_owned(),
+        referrer: referrer.map(ToOwned::to_owned),
+        visited_at: now.as_i64(),
+    };
+    if visitor.is_confirmed() {
+        add_visit(&mut lock(&BUFFER), visitor.stats_id(), visit);
+    } else {
+        let mut unconfirmed = lock(&UNCONFIRMED);
+        if unconfirmed.len() >= crate::config::get().visitor.max_unconfirmed_visitors
+            && !unconfirmed.contains_key(visitor.visitor_id())
+        {
+            return Ok(());
+        }
+        unconfirmed
+            .entry(visitor.visitor_id().to_owned())
+            .or_default()
+            .push(visit);
+    }
+    Ok(())
+}
+
+/// 访客确认接受 Cookie 后，将其暂存的访问记录计入统计
+pub fn confirm_visitor(visitor_id: &str) {
+    let Some(visits) = lock(&UNCONFIRMED).remove(visitor_id) else {
+        return;
+    };
+    let mut buffer = lock(&BUFFER);
+    for visit in visits {
+        add_visit(&mut buffer, visitor_id, visit);
+    }
+}
+
+/// 获取文章尚未写入数据库的页面访问量
+pub fn pending_pv(article_id: &str) -> u64 {
+    lock(&BUFFER)
+        .iter()
+        .filter(|((_, id), _)| id == article_id)
+        .map(|(_, visits)| visits.pv)
+        .sum()
+}
+
+/// 将缓冲区中的访问记录写入数据库，写入失败的记录会放回缓冲区等待下次写入
+pub async fn flush(db: &DbPool) -> anyhow::Result<()> {
+    remove_expired_unconfirmed();
+
+    let mut pending = std::mem::take(&mut *lock(&BUFFER));
+    if pending.is_empty() {
+        return Ok(());
+    }
+
+    for ((date, article_id), visits) in &mut pending {
+        for visitor_id in std::mem::take(&mut visits.visitors) {
+            let (is_new, is_new_today) = check_new_visitor(date, article_id, &visitor_id).await;
+            visits.uv += u64::from(is_new);
+            visits.daily_uv += u64::from(is_new_today);
+        }
+    }
+
+    let result = save_visits(db, &pending).await;
+    if result.is_err() {
+        // 访客去重记录已经写入缓存，放回缓冲区时只需要保留已去重的独立访客数
+        let mut buffer = lock(&BUFFER);
+        for (key, visits) in pending {
+            buffer.entry(key).or_default().merge(visits);
+        }
+    }
+    result
+}
+
+/// 获取文章访问分析数据
+pub async fn get_article_analytics(
+    bo: &GetArticleAnalyticsBo<'_>,
+    db: &mut DbConn,
+) -> Result<ArticleAnalyticsBo, AppError> {
+    let (start, end) = bo.date_range()?;
+    let (start_date, end_date) = (start.to_string(), end.to_string());
+    let article_id = bo.article_id.as_deref();
+
+    let daily =
+        crate::storage::db::article_daily_stats::list_daily(article_id, &start_date, &end_date, db)
+            .await?;
+    let top_articles = match article_id {
+        Some(_) => vec![],
+        None => {
+            crate::storage::db::article_daily_stats::list_top_articles(
+                &start_date,
+                &end_date,
+                bo.limit(),
+                db,
+            )
+            .await?
+        }
+    };
+    let referrers = crate::storage::db::article_daily_referrer::list_top_domains(
+        article_id,
+        &start_date,
+        &end_date,
+        bo.limit(),
+        db,
+    )
+    .await?;
+
+    let daily = fill_missing_dates(start, end, daily)?;
+    Ok(ArticleAnalyticsBo::from_entities(
+        daily,
+        top_articles,
+        referrers,
+    ))
+}
+
+/// 获取最近 `days` 天（包含当天）访问量最高的已发布文章，尚未写入数据库的访问记录不计入排行
+pub async fn list_popular_articles(
+    days: u64,
+    limit: u64,
+    db: &mut DbConn,
+) -> Result<Vec<ArticleVisitsBo>, AppError> {
+    let days = Duration::from_secs(3600 * 24 * days.saturating_sub(1));
+    let start_date = UnixTimestampSecs::now()
+        .checked_sub(days)
+        .unwrap_or(UnixTimestampSecs::new(0))
+        .into_str_date_utc()?;
+    let articles =
+        crate::storage::db::article_daily_stats::list_popular(&start_date, limit, db).await?;
+    Ok(articles.into_iter().map(ArticleVisitsBo::from).collect())
+}
+
+/// 尚未确认的访问记录
+#[derive(Debug)]
+struct UnconfirmedVisit {
+    /// 日期（UTC，格式为 YYYY-MM-DD）
+    date: String,
+    /// 文章ID
+    article_id: String,
+    /// 来源域名
+    referrer: Option<String>,
+    /// 访问时间
+    visited_at: i64,
+}
+
+/// 文章的待写入访问记录
+#[derive(Debug, Default)]
+struct PendingVisits {
+    /// 页面访问量
+    pv: u64,
+    /// 已完成去重的累计独立访客数增量
+    uv: u64,
+    /// 已完成去重的当日独立访客数增量
+    daily_uv: u64,
+    /// 尚未去重的访客ID
+    visitors: HashSet<String>,
+    /// 来源域名的访问次数
+    referrers: BTreeMap<String, u64>,
+}
+
+impl PendingVisits {
+    fn merge(&mut self, other: PendingVisits) {
+        self.pv += other.pv;
+        self.uv += other.uv;
+        self.daily_uv += other.daily_uv;
+        self.visitors.extend(other.visitors);
+        for (domain, visits) in other.referrers {
+            *self.referrers.entry(domain).or_default() += visits;
+        }
+    }
+}
+
+enum FlusherMessage {
+    Flush(oneshot::Sender<()>),
+    Shutdown(oneshot::Sender<()>),
+}
+
+async fn run_flusher(
+    db: DbPool,
+    interval: Duration,
+    mut receiver: mpsc::UnboundedReceiver<FlusherMessage>,
+) {
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(e) = flush(&db).await {
+                    tracing::error!("写入文章访问统计失败：{e}");
+                }
+            }
+            message = receiver.recv() => {
+                if let Err(e) = flush(&db).await {
+                    tracing::error!("写入文章访问统计失败：{e}");
+                }
+                match message {
+                    Some(FlusherMessage::Flush(done)) => {
+                        let _ = done.send(());
+                    }
+                    Some(FlusherMessage::Shutdown(done)) => {
+                        let _ = done.send(());
+                        break;
+                    }
+                    None => break,
+                }
+            }
+        }
+    }
+}
+
+/// 判断访客是否为 24 小时内首次访问文章，以及是否为当日首次访问文章
+async fn check_new_visitor(date: &str, article_id: &str, visitor_id: &str) -> (bool, bool) {
+    let record = VisitorArticleAccessRecordCo {
+        visitor_id: visitor_id.into(),
+        article_id: article_id.into(),
+    };
+    let daily_record = VisitorArticleDailyAccessRecordCo {
+        visitor_id: visitor_id.into(),
+        article_id: article_id.into(),
+        date: date.into(),
+    };
+    let is_new = record
+        .with_ttl(Duration::from_secs(3600 * 24))
+        .set(CacheSetMode::OnlyIfNotExists)
+        .await;
+    let is_new_today = daily_record
+        .with_ttl(Duration::from_secs(3600 * 48))
+        .set(CacheSetMode::OnlyIfNotExists)
+        .await;
+    if let Err(e) = &is_new {
+        tracing::error!("保存文章访问记录失败：{e}（文章ID：{article_id}）");
+    }
+    if let Err(e) = &is_new_today {
+        tracing::error!("保存文章每日访问记录失败：{e}（文章ID：{article_id}）");
+    }
+    (is_new.unwrap_or_default(), is_new_today.unwrap_or_default())
+}
+
+async fn save_visits(
+    db: &DbPool,
+    pending: &BTreeMap<(String, String), PendingVisits>,
+) -> anyhow::Result<()> {
+    let mut db = db.acquire().await?;
+    crate::storage::db::transaction(&mut db, async |tx| {
+        for ((date, article_id), visits) in pending {
+            crate::storage::db::article_stats::increment_by_article_id(
+                article_id, visits.pv, visits.uv, tx,
+            )
+            .await?;
+            crate::storage::db::article_daily_stats::increment(
+                article_id,
+                date,
+                visits.pv,
+                visits.daily_uv,
+                tx,
+            )
+            .await?;
+            for (domain, count) in &visits.referrers {
+                crate::storage::db::article_daily_referrer::increment(
+                    article_id, date, domain, *count, tx,
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    })
+    .await
+}
+
+/// 补齐没有访问记录的日期，保证图表数据连续
+fn fill_missing_dates(
+    start_date: Date,
+    end_date: Date,
+    daily: Vec<DailyVisits>,
+) -> anyhow::Result<Vec<DailyVisits>> {
+    let mut daily = daily.into_iter().peekable();
+    let mut filled = vec![];
+    let mut date = start_date;
+    while date <= end_date {
+        let date_str = date.to_string();
+        match daily.next_if(|visits| visits.date == date_str) {
+            Some(visits) => filled.push(visits),
+            None => filled.push(DailyVisits {
+                date: date_str,
+                pv: 0,
+                uv: 0,
+            }),
+        }
+        date = date.tomorrow()?;
+    }
+    Ok(filled)
+}
+
+fn add_visit(
+    buffer: &mut BTreeMap<(String, String), PendingVisits>,
+    visitor_id: &str,
+    visit: UnconfirmedVisit,
+) {
+    let visits = buffer.entry((visit.date, visit.article_id)).or_default();
+    visits.pv += 1;
+    visits.visitors.insert(visitor_id.to_owned());
+    if let Some(referrer) = visit.referrer {
+        *visits.referrers.entry(referrer).or_default() += 1;
+    }
+}
+
+/// 丢弃超过确认时限仍未确认的访问记录
+fn remove_expired_unconfirmed() {
+    let timeout = crate::config::get().visitor.confirm_timeout;
+    let Some(expired_before) = UnixTimestampSecs::now().checked_sub(timeout) else {
+        return;
+    };
+    lock(&UNCONFIRMED).retain(|_, visits| {
+        visits.retain(|visit| visit.visited_at >= expired_before.as_i64());
+        !visits.is_empty()
+    });
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|err| err.into_inner())
+}
@@ -1,5 +1,8 @@
 pub mod article;
+pub mod article_stats;
 pub mod auth;
 pub mod resource;
+pub mod resource_stats;
+pub mod series;
 pub mod system;
 pub mod visitor;
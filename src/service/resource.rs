@@ -1,5 +1,5 @@
 use std::env;
-use std::path::Path;
+use std::ops::Range;
 
 use boluo::BoxError;
 use boluo::body::Bytes;
@@ -8,13 +8,17 @@ use sha2::{Digest, Sha256};
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 
+use crate::config::UploadPolicyConfig;
 use crate::error::{AppError, AppErrorMeta};
+use crate::jwt::resource::ResourceDownloadJwtData;
 use crate::model::bo::resource::{
-    FindResourceBo, RemoveResourceBo, ResourceBo, UploadResourceBo, UploadResourceMetaBo,
-    UploadResourceOptionsBo,
+    DownloadResourceBo, FindResourceBo, RemoveResourceBo, ResourceBo, SignResourceBo,
+    SignedResourceBo, UploadResourceBo, UploadResourceMetaBo, UploadResourceOptionsBo,
 };
 use crate::model::po::resource::ResourcePo;
 use crate::storage::db::DbConn;
+use crate::storage::object::{ObjectData, ObjectStorage};
+use crate::util::jwt::JwtClaims;
 use crate::util::time::UnixTimestampSecs;
 
 /// 删除资源文件
@@ -23,10 +27,11 @@ pub async fn remove_resource(bo: &RemoveResourceBo<'_>, db: &mut DbConn) -> Resu
         return Ok(());
     };
     crate::storage::db::resource::remove(&resource.id, db).await?;
+    crate::storage::db::resource_stats::remove_by_resource_id(&resource.id, db).await?;
 
     let ref_count = crate::storage::db::resource::count_by_path(&resource.path, db).await?;
     if ref_count == 0
-        && let Err(e) = std::fs::remove_file(&resource.path)
+        && let Err(e) = crate::storage::object::get().remove(&resource.path).await
     {
         tracing::warn!("文件删除失败，路径：{}，错误：{e}", resource.path);
     }
@@ -38,19 +43,96 @@ pub async fn remove_resource(bo: &RemoveResourceBo<'_>, db: &mut DbConn) -> Resu
 pub async fn find_resource(
     bo: &FindResourceBo<'_>,
     db: &mut DbConn,
+) -> Result<Option<ResourcePo>, AppError> {
+    crate::storage::db::resource::find(&bo.resource_id, db)
+        .await
+        .map_err(From::from)
+}
+
+/// 下载资源文件，非公开的资源需要提供有效的签名下载令牌
+pub async fn download_resource(
+    bo: &DownloadResourceBo<'_>,
+    db: &mut DbConn,
 ) -> Result<Option<ResourcePo>, AppError> {
     let Some(resource) = crate::storage::db::resource::find(&bo.resource_id, db).await? else {
         return Ok(None);
     };
-    if !Path::new(&resource.path).is_file() {
+    if resource.is_public {
+        return Ok(Some(resource));
+    }
+    let Some(token) = &bo.token else {
+        return Ok(None);
+    };
+    validate_download_token(&resource.id, token)?;
+    Ok(Some(resource))
+}
+
+/// 签发资源文件的限时下载链接
+pub async fn sign_resource(
+    bo: &SignResourceBo<'_>,
+    db: &mut DbConn,
+) -> Result<SignedResourceBo, AppError> {
+    let config = &crate::config::get().resource;
+    let ttl = bo.ttl.unwrap_or(config.signed_url_default_ttl);
+    if ttl > config.signed_url_max_ttl {
+        return Err(AppErrorMeta::BadRequest.with_message(format!(
+            "下载链接有效期过长，最大允许 {} 秒",
+            config.signed_url_max_ttl.as_secs()
+        )));
+    }
+    if crate::storage::db::resource::find(&bo.resource_id, db)
+        .await?
+        .is_none()
+    {
+        return Err(AppErrorMeta::NotFound.with_message("资源不存在"));
+    }
+    SignedResourceBo::generate(&bo.resource_id, ttl)
+}
+
+/// 验证资源文件的签名下载令牌
+fn validate_download_token(resource_id: &str, token: &str) -> Result<(), AppError> {
+    let claims = JwtClaims::<ResourceDownloadJwtData>::decode(
+        token,
+        crate::config::get().jwt.secret.as_bytes(),
+    )
+    .map_err(|e| {
+        AppErrorMeta::PermissionDenied
+            .with_message("下载链接无效")
+            .with_source(e)
+            .with_context(format!("token: {token}"))
+    })?;
+
+    if claims.data.resource_id != resource_id {
+        return Err(AppErrorMeta::PermissionDenied
+            .with_message("下载链接无效")
+            .with_context(format!(
+                "令牌资源ID: {}, 请求资源ID: {resource_id}",
+                claims.data.resource_id
+            )));
+    }
+    if !claims.is_effective() || claims.is_expired() {
+        return Err(AppErrorMeta::PermissionDenied.with_message("下载链接已过期"));
+    }
+
+    Ok(())
+}
+
+/// 读取资源文件数据，可指定读取的字节区间
+pub async fn open_resource(
+    resource: &ResourcePo,
+    range: Option<Range<u64>>,
+) -> Result<Option<ObjectData>, AppError> {
+    let data = crate::storage::object::get()
+        .get(&resource.path, range)
+        .await?;
+    if data.is_none() {
         tracing::warn!(
             "资源文件不存在 (id: {}, path: {})",
             resource.id,
             resource.path
         );
-        return Ok(None);
     }
-    Ok(Some(resource))
+    Ok(data)
 }
 
 /// 基于自定义选项上传资源文件
@@ -59,35 +141,61 @@ pub async fn upload_resource_with_options(
     options: UploadResourceOptionsBo,
     db: &mut DbConn,
 ) -> Result<ResourceBo, AppError> {
-    if upload.meta.size > crate::config::get().resource.upload_file_max_size {
-        return Err(AppErrorMeta::DataTooLarge {
-            limit: crate::config::get().resource.upload_file_max_size,
-        }
-        .into());
+    let config = &crate::config::get().resource;
+    // 公开资源与文章附件（非公开资源）分别使用各自的上传策略
+    let policy = if options.is_public {
+        &config.public
+    } else {
+        &config.attachment
+    };
+
+    let max_size = policy
+        .max_size
+        .map_or(config.upload_file_max_size, |max_size| {
+            max_size.min(config.upload_file_max_size)
+        });
+    if upload.meta.size > max_size {
+        return Err(AppErrorMeta::DataTooLarge { limit: max_size }.into());
+    }
+
+    let extension = crate::util::path::extension(&upload.meta.name).to_owned();
+    check_file_type(policy, &extension, &upload.meta.mime_type)?;
+
+    if let Some(quota) = config.quota {
+        check_storage_quota(&upload.meta, quota, db).await?;
     }
 
-    let temp_file = save_file_verify_sha256(&upload.meta, upload.data).await?;
+    let (temp_file, detected_mime_type) =
+        save_file_verify_sha256(&upload.meta, upload.data).await?;
+
+    if !is_detected_mime_type_allowed(policy, detected_mime_type) {
+        return Err(AppErrorMeta::FileTypeNotAllowed.with_context(format!(
+            "文件名: {}, 探测到的文件类型: {detected_mime_type}",
+            upload.meta.name
+        )));
+    }
+
+    let storage = crate::storage::object::get();
 
     let duplicate_path = if let Some(duplicate) =
         crate::storage::db::resource::find_duplicate(&upload.meta.sha256, upload.meta.size, db)
             .await?
-        && Path::new(&duplicate.path).is_file()
+        && storage.exists(&duplicate.path).await?
     {
         Some(duplicate.path)
     } else {
         None
     };
 
-    let (created_file, path) = match duplicate_path {
-        Some(duplicate_path) => (None, duplicate_path),
+    let (created, path) = match duplicate_path {
+        Some(duplicate_path) => (false, duplicate_path),
         None => {
-            let path = generate_storage_path();
-            let file = temp_file.move_to(&path).await?;
-            (Some(file), path)
+            let path = generate_storage_key();
+            storage.put_file(&path, temp_file.path()).await?;
+            (true, path)
         }
     };
 
-    let extension = crate::util::path::extension(&upload.meta.name).to_owned();
     let resource = ResourcePo {
         id: options.resource_id,
         name: upload.meta.name,
@@ -95,14 +203,16 @@ pub async fn upload_resource_with_options(
         path,
         size: upload.meta.size,
         mime_type: upload.meta.mime_type,
+        detected_mime_type: detected_mime_type.to_owned(),
         is_public: options.is_public,
         sha256: upload.meta.sha256,
         created_at: UnixTimestampSecs::now().as_i64(),
     };
-    crate::storage::db::resource::create(&resource, db).await?;
-
-    if let Some(file) = created_file {
-        TempFileGuard::keep(file)
+    if let Err(e) = crate::storage::db::resource::create(&resource, db).await {
+        if created && let Err(e) = storage.remove(&resource.path).await {
+            tracing::warn!("文件删除失败，路径：{}，错误：{e}", resource.path);
+        }
+        return Err(e.into());
     }
 
     Ok(ResourceBo::from(resource))
@@ -120,11 +230,98 @@ pub async fn upload_resource(
     upload_resource_with_options(bo, options, db).await
 }
 
-/// 将数据存储到临时文件并计算其 SHA256 值，并校验与用户提供的哈希是否一致
+/// 获取上传文件已占用的存储空间
+pub async fn storage_usage(db: &mut DbConn) -> Result<u64, AppError> {
+    crate::storage::db::resource::sum_storage_size(db)
+        .await
+        .map_err(From::from)
+}
+
+/// 检查文件扩展名与声明的文件类型是否符合上传策略
+fn check_file_type(
+    policy: &UploadPolicyConfig,
+    extension: &str,
+    mime_type: &str,
+) -> Result<(), AppError> {
+    let extension_allowed = if policy.allowed_extensions.is_empty() {
+        true
+    } else {
+        is_extension_listed(&policy.allowed_extensions, extension)
+    };
+    let mime_type_allowed = if policy.allowed_mime_types.is_empty() {
+        true
+    } else {
+        is_mime_type_listed(&policy.allowed_mime_types, mime_type)
+    };
+    if !extension_allowed
+        || !mime_type_allowed
+        || is_extension_listed(&policy.denied_extensions, extension)
+        || is_mime_type_listed(&policy.denied_mime_types, mime_type)
+    {
+        return Err(AppErrorMeta::FileTypeNotAllowed
+            .with_context(format!("扩展名: {extension}, 文件类型: {mime_type}")));
+    }
+    Ok(())
+}
+
+/// 检查根据文件内容探测到的文件类型是否符合上传策略，防止通过伪造声明的文件类型绕过限制
+fn is_detected_mime_type_allowed(policy: &UploadPolicyConfig, detected_mime_type: &str) -> bool {
+    let allowed = policy.allowed_mime_types.is_empty()
+        || is_mime_type_listed(&policy.allowed_mime_types, detected_mime_type)
+        || (policy.allow_unrecognized_content
+            && crate::util::mime::is_unrecognized(detected_mime_type));
+    allowed && !is_mime_type_listed(&policy.denied_mime_types, detected_mime_type)
+}
+
+/// 检查上传文件后是否会超出存储空间配额，与已有文件重复时不占用额外空间
+async fn check_storage_quota(
+    meta: &UploadResourceMetaBo,
+    quota: u64,
+    db: &mut DbConn,
+) -> Result<(), AppError> {
+    if crate::storage::db::resource::find_duplicate(&meta.sha256, meta.size, db)
+        .await?
+        .is_some()
+    {
+        return Ok(());
+    }
+    let usage = crate::storage::db::resource::sum_storage_size(db).await?;
+    if usage.saturating_add(meta.size) > quota {
+        return Err(AppErrorMeta::StorageQuotaExceeded { quota }
+            .with_context(format!("已用空间: {usage}, 上传文件大小: {}", meta.size)));
+    }
+    Ok(())
+}
+
+fn is_extension_listed(list: &[String], extension: &str) -> bool {
+    list.iter()
+        .any(|item| item.trim_start_matches('.').eq_ignore_ascii_case(extension))
+}
+
+fn is_mime_type_listed(list: &[String], mime_type: &str) -> bool {
+    let mime_type = mime_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    list.iter().any(|item| {
+        let item = item.trim().to_ascii_lowercase();
+        match item.strip_suffix("/*") {
+            Some("*") => true,
+            Some(prefix) => mime_type
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('/')),
+            None => item == mime_type,
+        }
+    })
+}
+
+/// 将数据存储到临时文件并计算其 SHA256 值，并校验与用户提供的哈希是否一致，同时根据文件内容探测文件类型
 async fn save_file_verify_sha256<T>(
     meta: &UploadResourceMetaBo,
     mut data: T,
-) -> Result<TempFileGuard, AppError>
+) -> Result<(TempFileGuard, &'static str), AppError>
 where
     T: Stream<Item = Result<Bytes, BoxError>> + Unpin,
 {
@@ -132,6 +329,7 @@ where
     let mut remaining = meta.size;
     let mut hasher = Sha256::new();
     let mut temp_file = File::create_new(&temp_path).await?;
+    let mut head = Vec::with_capacity(crate::util::mime::SNIFF_LEN);
 
     let guard = TempFileGuard::new(temp_path);
 
@@ -147,6 +345,10 @@ where
         remaining -= chunk_size;
         temp_file.write_all(&chunk).await?;
         hasher.update(&chunk);
+        if head.len() < crate::util::mime::SNIFF_LEN {
+            let len = chunk.len().min(crate::util::mime::SNIFF_LEN - head.len());
+            head.extend_from_slice(&chunk[..len]);
+        }
     }
     temp_file.sync_all().await?;
 
@@ -162,13 +364,13 @@ where
             .with_message("上传文件的 SHA256 哈希校验失败，请检查文件完整性或哈希值是否正确"));
     }
 
-    Ok(guard)
+    Ok((guard, crate::util::mime::sniff(&head)))
 }
 
-/// 生成文件存储路径
-fn generate_storage_path() -> String {
+/// 生成文件存储键
+fn generate_storage_key() -> String {
     let file_name = crate::util::uuid::v4();
-    crate::util::path::root(&crate::config::get().resource.upload_dir)
+    crate::util::path::Root::new()
         .join(file_name.get(0..2).unwrap())
         .join(file_name.get(2..4).unwrap())
         .join(file_name)
@@ -204,27 +406,102 @@ impl TempFileGuard {
     pub fn keep(this: Self) {
         std::mem::forget(this);
     }
-
-    pub async fn move_to(self, to: &str) -> std::io::Result<TempFileGuard> {
-        if let Some(dir) = Path::new(to).parent() {
-            tokio::fs::create_dir_all(dir).await?;
-        }
-        // 原子创建文件，防止覆盖原有的文件
-        let f = File::create_new(to).await.map(|_| TempFileGuard::new(to))?;
-        // 尝试移动文件
-        if tokio::fs::rename(&self.path, to).await.is_err() {
-            tokio::fs::copy(&self.path, to).await?;
-        } else {
-            TempFileGuard::keep(self);
-        }
-        Ok(f)
-    }
 }
 
 impl Drop for TempFileGuard {
     fn drop(&mut self) {
-        if let Err(e) = std::fs::remove_file(&self.path) {
+        if let Err(e) = std::fs::remove_file(&self.path)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
             tracing::warn!("文件删除失败，路径：{}，错误：{e}", self.path);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| (*item).to_owned()).collect()
+    }
+
+    #[test]
+    fn mime_type_listed() {
+        let list = list(&["image/*", " Text/Plain ", "application/pdf"]);
+        assert!(is_mime_type_listed(&list, "image/png"));
+        assert!(is_mime_type_listed(&list, "IMAGE/SVG+XML"));
+        assert!(is_mime_type_listed(&list, "text/plain; charset=utf-8"));
+        assert!(is_mime_type_listed(&list, "application/pdf"));
+        assert!(!is_mime_type_listed(&list, "imagex/png"));
+        assert!(!is_mime_type_listed(&list, "image"));
+        assert!(!is_mime_type_listed(&list, "text/html"));
+        assert!(!is_mime_type_listed(&list, "application/pdf-x"));
+        assert!(is_mime_type_listed(&["*/*".to_owned()], "video/mp4"));
+        assert!(!is_mime_type_listed(&[], "video/mp4"));
+    }
+
+    #[test]
+    fn declared_file_type() {
+        let policy = UploadPolicyConfig {
+            allowed_extensions: list(&["png", ".jpg"]),
+            denied_extensions: list(&["exe"]),
+            allowed_mime_types: list(&["image/*"]),
+            denied_mime_types: list(&["image/svg+xml"]),
+            ..Default::default()
+        };
+        assert!(check_file_type(&policy, "png", "image/png").is_ok());
+        assert!(check_file_type(&policy, "JPG", "image/jpeg").is_ok());
+        assert!(check_file_type(&policy, "gif", "image/gif").is_err());
+        assert!(check_file_type(&policy, "png", "application/pdf").is_err());
+        assert!(check_file_type(&policy, "png", "image/svg+xml").is_err());
+
+        let policy = UploadPolicyConfig {
+            denied_extensions: list(&["exe"]),
+            ..Default::default()
+        };
+        assert!(check_file_type(&policy, "pdf", "application/pdf").is_ok());
+        assert!(check_file_type(&policy, "exe", "image/png").is_err());
+    }
+
+    #[test]
+    fn detected_file_type() {
+        let mut policy = UploadPolicyConfig {
+            allowed_mime_types: list(&["image/*", "application/pdf"]),
+            denied_mime_types: list(&["image/svg+xml"]),
+            ..Default::default()
+        };
+        assert!(is_detected_mime_type_allowed(&policy, "image/png"));
+        assert!(is_detected_mime_type_allowed(&policy, "application/pdf"));
+        assert!(!is_detected_mime_type_allowed(&policy, "image/svg+xml"));
+        // 声明为图片的可执行文件
+        assert!(!is_detected_mime_type_allowed(
+            &policy,
+            "application/x-executable"
+        ));
+        assert!(!is_detected_mime_type_allowed(&policy, "text/plain"));
+        assert!(!is_detected_mime_type_allowed(
+            &policy,
+            "application/octet-stream"
+        ));
+
+        policy.allow_unrecognized_content = true;
+        assert!(is_detected_mime_type_allowed(&policy, "text/plain"));
+        assert!(is_detected_mime_type_allowed(
+            &policy,
+            "application/octet-stream"
+        ));
+        assert!(!is_detected_mime_type_allowed(&policy, "text/html"));
+
+        // 没有配置允许的文件类型时只检查禁止的文件类型
+        let policy = UploadPolicyConfig {
+            denied_mime_types: list(&["text/html"]),
+            ..Default::default()
+        };
+        assert!(is_detected_mime_type_allowed(
+            &policy,
+            "application/octet-stream"
+        ));
+        assert!(!is_detected_mime_type_allowed(&policy, "text/html"));
+    }
+}
@@ -0,0 +1,168 @@
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use boluo::BoxError;
+use boluo::body::Bytes;
+use futures_util::{Stream, StreamExt};
+use tokio::sync::{mpsc, oneshot};
+
+use crate::error::{AppError, AppErrorMeta};
+use crate::model::bo::resource::{FindResourceBo, ResourceStatsBo};
+use crate::model::co::resource::VisitorResourceDownloadRecordCo;
+use crate::storage::cache::CacheData;
+use crate::storage::cache::storage::CacheSetMode;
+use crate::storage::db::{DbConn, DbPool};
+use crate::storage::object::ObjectStream;
+use crate::util::time::UnixTimestampSecs;
+
+static RECORDER: OnceLock<mpsc::UnboundedSender<RecorderMessage>> = OnceLock::new();
+
+/// 启动下载统计记录器，下载记录会在后台异步写入数据库
+pub fn init(db: DbPool) -> anyhow::Result<()> {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    RECORDER
+        .set(sender)
+        .map_err(|_| anyhow::anyhow!("重复初始化下载统计记录器"))?;
+    tokio::spawn(run_recorder(db, receiver));
+    Ok(())
+}
+
+/// 关闭下载统计记录器，等待已提交的下载记录全部写入
+pub async fn shutdown() {
+    let Some(sender) = RECORDER.get() else {
+        return;
+    };
+    let (done_sender, done_receiver) = oneshot::channel();
+    if sender.send(RecorderMessage::Shutdown(done_sender)).is_ok() {
+        let _ = done_receiver.await;
+    }
+}
+
+/// 获取资源文件的下载统计信息
+pub async fn get_resource_stats(
+    bo: &FindResourceBo<'_>,
+    db: &mut DbConn,
+) -> Result<ResourceStatsBo, AppError> {
+    let Some(resource) = crate::storage::db::resource::find(&bo.resource_id, db).await? else {
+        return Err(AppErrorMeta::NotFound.with_message("资源不存在"));
+    };
+    let stats = crate::storage::db::resource_stats::find_by_resource_id(&resource.id, db).await?;
+    Ok(ResourceStatsBo::from_entities(resource, stats))
+}
+
+/// 统计资源文件的下载数据，数据流结束或被丢弃时提交下载记录
+pub fn track_download(
+    resource_id: &str,
+    visitor_id: &str,
+    range: &Range<u64>,
+    stream: ObjectStream,
+) -> ObjectStream {
+    Box::pin(TrackedStream {
+        inner: stream,
+        record: Some(DownloadRecord {
+            resource_id: resource_id.to_owned(),
+            visitor_id: visitor_id.to_owned(),
+            // 续传或分段请求只统计流量，从头开始的请求才计为一次下载
+            is_download: range.start == 0,
+            bytes_served: 0,
+        }),
+    })
+}
+
+/// 下载记录
+#[derive(Debug)]
+struct DownloadRecord {
+    /// 资源ID
+    resource_id: String,
+    /// 访客ID
+    visitor_id: String,
+    /// 是否计为一次下载
+    is_download: bool,
+    /// 已传输的字节数
+    bytes_served: u64,
+}
+
+enum RecorderMessage {
+    Record(DownloadRecord),
+    Shutdown(oneshot::Sender<()>),
+}
+
+async fn run_recorder(db: DbPool, mut receiver: mpsc::UnboundedReceiver<RecorderMessage>) {
+    while let Some(message) = receiver.recv().await {
+        match message {
+            RecorderMessage::Record(record) => {
+                if let Err(e) = save_record(&db, &record).await {
+                    tracing::error!("保存资源下载记录失败：{e}（记录：{record:?}）");
+                }
+            }
+            RecorderMessage::Shutdown(done) => {
+                let _ = done.send(());
+                break;
+            }
+        }
+    }
+}
+
+async fn save_record(db: &DbPool, record: &DownloadRecord) -> anyhow::Result<()> {
+    let (downloads_add, uv_add) = if record.is_download {
+        let cache_data = VisitorResourceDownloadRecordCo {
+            visitor_id: record.visitor_id.as_str().into(),
+            resource_id: record.resource_id.as_str().into(),
+        };
+        let cache = cache_data.with_ttl(Duration::from_secs(3600 * 24));
+        let uv_add = match cache.set(CacheSetMode::OnlyIfNotExists).await? {
+            true => 1,
+            false => 0,
+        };
+        (1, uv_add)
+    } else {
+        (0, 0)
+    };
+    let mut db = db.acquire().await?;
+    crate::storage::db::resource_stats::increment_by_resource_id(
+        &record.resource_id,
+        downloads_add,
+        uv_add,
+        record.bytes_served,
+        UnixTimestampSecs::now().as_i64(),
+        &mut db,
+    )
+    .await?;
+    Ok(())
+}
+
+struct TrackedStream {
+    inner: ObjectStream,
+    record: Option<DownloadRecord>,
+}
+
+impl Stream for TrackedStream {
+    type Item = Result<Bytes, BoxError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = self.inner.poll_next_unpin(cx);
+        if let Poll::Ready(Some(Ok(data))) = &poll
+            && let Some(record) = &mut self.record
+        {
+            record.bytes_served += data.len() as u64;
+        }
+        poll
+    }
+}
+
+impl Drop for TrackedStream {
+    fn drop(&mut self) {
+        let Some(record) = self.record.take() else {
+            return;
+        };
+        let Some(sender) = RECORDER.get() else {
+            return;
+        };
+        if sender.send(RecorderMessage::Record(record)).is_err() {
+            tracing::warn!("下载统计记录器已关闭，丢弃下载记录");
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::HashSet;
+
+use crate::error::{AppError, AppErrorMeta};
+use crate::model::bo::auth::AdminBo;
+use crate::model::bo::series::{
+    ArticleSeriesBo, CreateSeriesBo, GetSeriesBo, RemoveSeriesBo, SeriesBo, SeriesDetailsBo,
+    SeriesListItemBo, SetSeriesArticlesBo, UpdateSeriesBo,
+};
+use crate::model::common::article::ArticleStatus;
+use crate::model::po::series::{SeriesArticlePo, SeriesPo};
+use crate::storage::db::DbConn;
+use crate::util::time::UnixTimestampSecs;
+
+/// 创建系列
+pub async fn create_series(bo: CreateSeriesBo, db: &mut DbConn) -> Result<SeriesBo, AppError> {
+    let now = UnixTimestampSecs::now().as_i64();
+    let series = SeriesPo {
+        id: crate::util::uuid::v4(),
+        title: bo.title,
+        description: bo.description,
+        created_at: now,
+        updated_at: now,
+    };
+    crate::storage::db::series::create(&series, db).await?;
+    Ok(SeriesBo::from(series))
+}
+
+/// 修改系列
+pub async fn update_series(bo: UpdateSeriesBo, db: &mut DbConn) -> Result<(), AppError> {
+    let Some(mut series) = crate::storage::db::series::find(&bo.series_id, db).await? else {
+        return Err(AppErrorMeta::NotFound.with_message("系列不存在，无法编辑系列"));
+    };
+    series.title = bo.title;
+    series.description = bo.description;
+    series.updated_at = UnixTimestampSecs::now().as_i64();
+    crate::storage::db::series::update(&series, db).await?;
+    Ok(())
+}
+
+/// 删除系列，系列中的文章不会被删除
+pub async fn remove_series(bo: &RemoveSeriesBo<'_>, db: &mut DbConn) -> Result<(), AppError> {
+    crate::storage::db::transaction(db, async |tx| {
+        crate::storage::db::series::remove(&bo.series_id, tx).await?;
+        crate::storage::db::series_article::remove_by_series_id(&bo.series_id, tx).await?;
+        Ok(())
+    })
+    .await
+}
+
+/// 按顺序设置系列中的文章，一篇文章最多属于一个系列
+pub async fn set_series_articles(
+    bo: &SetSeriesArticlesBo,
+    db: &mut DbConn,
+) -> Result<(), AppError> {
+    let mut article_ids = HashSet::new();
+    if !bo.article_ids.iter().all(|id| article_ids.insert(id)) {
+        return Err(AppErrorMeta::BadRequest.with_message("系列中的文章不能重复"));
+    }
+    crate::storage::db::transaction(db, async |tx| {
+        let Some(mut series) = crate::storage::db::series::find(&bo.series_id, tx).await? else {
+            return Err(AppErrorMeta::NotFound.with_message("系列不存在"));
+        };
+        for article_id in &bo.article_ids {
+            if crate::storage::db::article::find(article_id, tx)
+                .await?
+                .is_none()
+            {
+                return Err(AppErrorMeta::NotFound
+                    .with_message(format!("文章不存在，文章ID: {article_id}")));
+            }
+            if let Some(existing) =
+                crate::storage::db::series_article::find_by_article_id(article_id, tx).await?
+                && existing.series_id != series.id
+            {
+                return Err(AppErrorMeta::BadRequest
+                    .with_message(format!("文章已属于其他系列，文章ID: {article_id}")));
+            }
+        }
+
+        crate::storage::db::series_article::remove_by_series_id(&series.id, tx).await?;
+        for (index, article_id) in bo.article_ids.iter().enumerate() {
+            let series_article = SeriesArticlePo {
+                series_id: series.id.clone(),
+                article_id: article_id.clone(),
+                position: i64::try_from(index + 1)?,
+            };
+            crate::storage::db::series_article::create(&series_article, tx).await?;
+        }
+        series.updated_at = UnixTimestampSecs::now().as_i64();
+        crate::storage::db::series::update(&series, tx).await?;
+        Ok(())
+    })
+    .await
+}
+
+/// 列出所有系列
+pub async fn list_series(db: &mut DbConn) -> Result<Vec<SeriesListItemBo>, AppError> {
+    let series = crate::storage::db::series::list(db).await?;
+    Ok(series.into_iter().map(SeriesListItemBo::from).collect())
+}
+
+/// 获取系列详情，访客只能看到系列中已发布的文章
+pub async fn get_series(
+    admin: Option<&AdminBo>,
+    bo: &GetSeriesBo<'_>,
+    db: &mut DbConn,
+) -> Result<Option<SeriesDetailsBo>, AppError> {
+    let Some(series) = crate::storage::db::series::find(&bo.series_id, db).await? else {
+        return Ok(None);
+    };
+    let status = admin.is_none().then_some(ArticleStatus::Published);
+    let articles =
+        crate::storage::db::series_article::list_articles(&series.id, status, db).await?;
+    Ok(Some(SeriesDetailsBo::from_entities(series, articles)))
+}
+
+/// 获取文章所在系列的导航信息，访客看到的序号与总数只计算已发布的文章
+pub async fn get_article_series(
+    admin: Option<&AdminBo>,
+    article_id: &str,
+    db: &mut DbConn,
+) -> Result<Option<ArticleSeriesBo>, AppError> {
+    let Some(series_article) =
+        crate::storage::db::series_article::find_by_article_id(article_id, db).await?
+    else {
+        return Ok(None);
+    };
+    let Some(series) = crate::storage::db::series::find(&series_article.series_id, db).await?
+    else {
+        return Ok(None);
+    };
+    let status = admin.is_none().then_some(ArticleStatus::Published);
+    let articles =
+        crate::storage::db::series_article::list_articles(&series.id, status, db).await?;
+    Ok(ArticleSeriesBo::from_entities(article_id, series, articles))
+}
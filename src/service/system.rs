@@ -2,9 +2,15 @@ use std::sync::{Arc, LazyLock, Mutex};
 
 use sysinfo::{Disks, System};
 
-use crate::error::AppError;
-use crate::model::bo::system::{InfoBo, InfoDatabaseBo, InfoDiskBo, InfoSystemBo};
+use crate::error::{AppError, AppErrorMeta};
+use crate::model::bo::system::{
+    CacheEntryBo, CacheKindStatsBo, InfoBo, InfoDatabaseBo, InfoDiskBo, InfoResourceBo,
+    InfoSystemBo, InspectCacheBo, RemoveCacheBo, SearchIndexCheckBo,
+};
 use crate::state::AppState;
+use crate::storage::cache::storage::CacheStorage;
+use crate::storage::db::DbConn;
+use crate::storage::db::fts::{FTS_TABLES, FtsTable};
 
 static SYSTEM_INFO: LazyLock<Mutex<System>> = LazyLock::new(|| Mutex::new(System::new_all()));
 static DISKS_INFO: LazyLock<Mutex<Disks>> =
@@ -42,9 +48,101 @@ pub async fn info(state: Arc<AppState>) -> Result<InfoBo, AppError> {
             total_connections,
         }
     };
+    let resource = {
+        let mut db = state.db.acquire().await?;
+        InfoResourceBo {
+            used_space: crate::service::resource::storage_usage(&mut db).await?,
+            quota: crate::config::get().resource.quota,
+        }
+    };
     Ok(InfoBo {
         system,
         disks,
         database,
+        resource,
     })
 }
+
+/// 按缓存类型统计缓存数据
+pub async fn cache_stats() -> Result<Vec<CacheKindStatsBo>, AppError> {
+    let stats = crate::storage::cache::storage::get().stats().await?;
+    Ok(stats.into_iter().map(CacheKindStatsBo::from).collect())
+}
+
+/// 查看缓存数据，已过期但尚未清理的数据也会返回
+pub async fn inspect_cache(bo: &InspectCacheBo<'_>) -> Result<CacheEntryBo, AppError> {
+    let Some(cache) = crate::storage::cache::storage::get()
+        .inspect(&bo.kind, &bo.id)
+        .await?
+    else {
+        return Err(AppErrorMeta::NotFound.with_message("缓存不存在"));
+    };
+    Ok(CacheEntryBo::from(cache))
+}
+
+/// 删除指定类型的缓存数据，返回删除的条数
+pub async fn remove_cache(bo: &RemoveCacheBo<'_>) -> Result<u64, AppError> {
+    let rows = crate::storage::cache::storage::get()
+        .remove_by_kind(&bo.kind, &bo.id_prefix)
+        .await?;
+    tracing::info!(
+        "删除缓存数据（类型：{}，ID前缀：{}，条数：{rows}）",
+        bo.kind,
+        bo.id_prefix
+    );
+    Ok(rows)
+}
+
+/// 清理所有过期的缓存数据，返回清理的条数
+pub async fn purge_expired_cache() -> Result<u64, AppError> {
+    let rows = crate::storage::cache::storage::get()
+        .purge_expired()
+        .await?;
+    tracing::info!("清理过期缓存数据（条数：{rows}）");
+    Ok(rows)
+}
+
+/// 根据文章与附件数据重新生成全文索引，并为尚未写入索引的附件补充索引
+pub async fn rebuild_search_index(db: &mut DbConn) -> Result<(), AppError> {
+    crate::storage::db::transaction(db, async |tx| {
+        // 文章索引根据文章表重新写入，附件文本只保存在索引中，只能根据索引保存的文本重建
+        crate::storage::db::fts::reindex_all_articles(tx).await?;
+        crate::storage::db::fts::rebuild(FtsTable::ArticleAttachment.name(), tx).await?;
+        Ok::<_, AppError>(())
+    })
+    .await?;
+    // 重建只能使用已写入索引的文本，尚未写入索引的附件需要重新提取文本
+    let indexed = crate::service::article::index_unindexed_attachments(db).await?;
+    tracing::info!("全文索引重建完成，补充索引的附件数量：{indexed}");
+    Ok(())
+}
+
+/// 合并全文索引的分段，减少索引占用的空间并提升查询速度
+pub async fn optimize_search_index(db: &mut DbConn) -> Result<(), AppError> {
+    for table in FTS_TABLES {
+        crate::storage::db::fts::optimize(table, db).await?;
+    }
+    tracing::info!("全文索引优化完成");
+    Ok(())
+}
+
+/// 检查全文索引是否完整，以及文章的全文索引是否与文章数据一致
+pub async fn check_search_index(db: &mut DbConn) -> Result<Vec<SearchIndexCheckBo>, AppError> {
+    let mut results = vec![];
+    for table in FTS_TABLES {
+        let mut message = crate::storage::db::fts::integrity_check(table, db).await?;
+        if message.is_none() && table == FtsTable::Article.name() {
+            message = crate::storage::db::fts::check_articles(db).await?;
+        }
+        match &message {
+            Some(message) => tracing::warn!("全文索引检查未通过（表：{table}）：{message}"),
+            None => tracing::info!("全文索引检查通过（表：{table}）"),
+        }
+        results.push(SearchIndexCheckBo {
+            table: table.to_owned(),
+            ok: message.is_none(),
+            message,
+        });
+    }
+    Ok(results)
+}
@@ -1,16 +1,216 @@
+use std::net::IpAddr;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use boluo::http::HeaderMap;
+use boluo::http::header::{ACCEPT_LANGUAGE, USER_AGENT};
+use regex::{RegexSet, RegexSetBuilder};
+use sha2::{Digest, Sha256};
+
+use crate::config::VisitorConfig;
 use crate::error::AppError;
 use crate::model::bo::visitor::VisitorBo;
+use crate::model::co::visitor::VisitorHashSaltCo;
+use crate::storage::cache::storage::CacheSetMode;
+use crate::storage::cache::{Cache, CacheData};
+use crate::util::time::UnixTimestampSecs;
+
+static BOT_USER_AGENTS: OnceLock<RegexSet> = OnceLock::new();
+
+/// 当日的访客哈希盐值
+static HASH_SALT: Mutex<Option<VisitorHashSaltCo>> = Mutex::new(None);
+
+/// 初始化爬虫识别规则
+pub fn init(config: &VisitorConfig) -> anyhow::Result<()> {
+    BOT_USER_AGENTS
+        .set(build_bot_user_agents(&config.bot_user_agents)?)
+        .map_err(|_| anyhow::anyhow!("重复初始化爬虫识别规则"))
+}
+
+/// 根据 User-Agent 判断请求是否来自爬虫，缺少 User-Agent 的请求视为爬虫
+pub fn is_bot(user_agent: Option<&str>) -> bool {
+    let Some(patterns) = BOT_USER_AGENTS.get() else {
+        return false;
+    };
+    is_bot_user_agent(patterns, user_agent)
+}
 
-pub async fn keep_or_create_visitor(visitor_id: &str) -> Result<VisitorBo, AppError> {
-    if VisitorBo::keep(visitor_id).await? {
-        let Some(visitor) = VisitorBo::from_cache(visitor_id).await? else {
-            return Ok(VisitorBo::create_and_cache().await?);
-        };
-        return Ok(visitor);
+/// 判断请求是否拒绝跟踪（`DNT: 1` 或 `Sec-GPC: 1`），未启用相关配置时总是返回 `false`
+pub fn is_do_not_track(headers: &HeaderMap) -> bool {
+    if !crate::config::get().visitor.privacy.honor_dnt {
+        return false;
     }
-    Ok(VisitorBo::create_and_cache().await?)
+    ["dnt", "sec-gpc"].into_iter().any(|name| {
+        headers
+            .get(name)
+            .is_some_and(|value| value.as_bytes() == b"1")
+    })
+}
+
+/// 计算访客哈希，哈希由当日盐值、客户端 IP 与部分请求头计算得出，盐值每日轮换，因此无法跨日关联访客
+///
+/// 未配置获取客户端 IP 的请求头时，使用连接的对端地址作为客户端 IP。
+pub async fn visitor_hash(headers: &HeaderMap, peer_ip: Option<IpAddr>) -> anyhow::Result<String> {
+    let salt = daily_salt().await?;
+    let peer_ip = peer_ip.map(|ip| ip.to_string());
+    let client_ip = match crate::config::get()
+        .visitor
+        .privacy
+        .client_ip_header
+        .as_deref()
+    {
+        Some(name) => client_ip_from_header(headers, name),
+        None => peer_ip.as_deref(),
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(salt.as_bytes());
+    for value in [
+        client_ip,
+        headers.get(USER_AGENT).and_then(|v| v.to_str().ok()),
+        headers.get(ACCEPT_LANGUAGE).and_then(|v| v.to_str().ok()),
+    ] {
+        hasher.update([0]);
+        hasher.update(value.unwrap_or_default().as_bytes());
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// 从请求头中获取客户端 IP
+///
+/// 代理只会在 `X-Forwarded-For` 等请求头的末尾追加地址，左侧的地址可以被客户端任意伪造，因此取最右侧、
+/// 由最近一级代理添加的地址。
+fn client_ip_from_header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(name)
+        .iter()
+        .next_back()
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// 刷新访客有效期，访客不存在时返回 `None`
+///
+/// 访客携带 Cookie 再次请求，说明客户端接受 Cookie，此前暂存的访问记录可以计入统计。
+pub async fn keep_visitor(visitor_id: &str) -> Result<Option<VisitorBo>, AppError> {
+    if !VisitorBo::keep(visitor_id).await? {
+        return Ok(None);
+    }
+    let Some(mut visitor) = VisitorBo::from_cache(visitor_id).await? else {
+        return Ok(None);
+    };
+    if !visitor.is_confirmed() && visitor.confirm().await? {
+        crate::service::article_stats::confirm_visitor(visitor.visitor_id());
+    }
+    Ok(Some(visitor))
 }
 
 pub async fn create_visitor() -> Result<VisitorBo, AppError> {
     Ok(VisitorBo::create_and_cache().await?)
 }
+
+/// 获取当日的访客哈希盐值，盐值保存在缓存中，以便重启后当日的访客哈希保持不变
+async fn daily_salt() -> anyhow::Result<String> {
+    let date = UnixTimestampSecs::now().into_str_date_utc()?;
+    if let Some(salt) = HASH_SALT
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .as_ref()
+        .filter(|salt| salt.date == date)
+    {
+        return Ok(salt.salt.clone());
+    }
+
+    let data = VisitorHashSaltCo {
+        date: date.clone(),
+        salt: format!("{}{}", crate::util::uuid::v4(), crate::util::uuid::v4()),
+    };
+    // 盐值过期后无法再计算出旧的访客哈希
+    data.with_ttl(Duration::from_secs(3600 * 24 * 2))
+        .set(CacheSetMode::OnlyIfNotExists)
+        .await?;
+    let Some(salt) = Cache::<VisitorHashSaltCo>::get(&date).await? else {
+        return Err(anyhow::anyhow!("访客哈希盐值写入缓存失败（日期：{date}）"));
+    };
+    let salt = salt.data;
+    let value = salt.salt.clone();
+    *HASH_SALT.lock().unwrap_or_else(|err| err.into_inner()) = Some(salt);
+    Ok(value)
+}
+
+fn build_bot_user_agents(patterns: &[String]) -> anyhow::Result<RegexSet> {
+    RegexSetBuilder::new(patterns)
+        .case_insensitive(true)
+        .build()
+        .map_err(|e| anyhow::anyhow!("爬虫 User-Agent 正则表达式错误：{e}"))
+}
+
+fn is_bot_user_agent(patterns: &RegexSet, user_agent: Option<&str>) -> bool {
+    match user_agent.map(str::trim) {
+        None | Some("") => true,
+        Some(user_agent) => patterns.is_match(user_agent),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use boluo::http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn client_ip_from_forwarded_header() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(client_ip_from_header(&headers, "x-forwarded-for"), None);
+
+        headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.1"));
+        assert_eq!(
+            client_ip_from_header(&headers, "x-forwarded-for"),
+            Some("203.0.113.1")
+        );
+
+        // 客户端伪造的地址位于左侧
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("10.0.0.1, 198.51.100.7 , 203.0.113.1"),
+        );
+        assert_eq!(
+            client_ip_from_header(&headers, "x-forwarded-for"),
+            Some("203.0.113.1")
+        );
+
+        headers.append("x-forwarded-for", HeaderValue::from_static("203.0.113.2"));
+        assert_eq!(
+            client_ip_from_header(&headers, "x-forwarded-for"),
+            Some("203.0.113.2")
+        );
+
+        headers.insert("x-forwarded-for", HeaderValue::from_static("203.0.113.1,"));
+        assert_eq!(client_ip_from_header(&headers, "x-forwarded-for"), None);
+    }
+
+    #[test]
+    fn bot_user_agent() {
+        let patterns =
+            build_bot_user_agents(&["bot".to_owned(), "spider".to_owned(), "^curl/".to_owned()])
+                .unwrap();
+        assert!(is_bot_user_agent(&patterns, None));
+        assert!(is_bot_user_agent(&patterns, Some("  ")));
+        assert!(is_bot_user_agent(
+            &patterns,
+            Some("Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)")
+        ));
+        assert!(is_bot_user_agent(&patterns, Some("Baiduspider")));
+        assert!(is_bot_user_agent(&patterns, Some("curl/8.5.0")));
+        assert!(!is_bot_user_agent(
+            &patterns,
+            Some("Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0")
+        ));
+        assert!(!is_bot_user_agent(
+            &patterns,
+            Some("Mozilla/5.0 curl/8.5.0")
+        ));
+    }
+}
@@ -34,6 +34,7 @@ pub fn timeout() -> Option<Duration> {
 
 pub async fn graceful() -> Option<Duration> {
     signal().await;
+    crate::service::article_stats::flush_now().await;
     let timeout = timeout();
     if let Some(timeout) = timeout {
         tracing::info!("HTTP 服务开始优雅关闭，等待活跃请求处理完成（超时时间：{timeout:?}）");
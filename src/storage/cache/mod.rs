@@ -6,7 +6,7 @@ use std::time::Duration;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-use crate::storage::cache::storage::{CacheSetMode, CacheStorage};
+use crate::storage::cache::storage::{CacheScanPage, CacheSetMode, CacheStorage};
 use crate::util::time::UnixTimestampSecs;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -127,6 +127,58 @@ where
         storage.batch_remove::<T>(id_prefix).await
     }
 
+    pub async fn incr_by_in<S>(
+        id: &str,
+        delta: i64,
+        ttl: Duration,
+        storage: &S,
+    ) -> anyhow::Result<i64>
+    where
+        S: CacheStorage,
+    {
+        let expires_at = UnixTimestampSecs::now().add(ttl).as_i64();
+        storage.incr_by::<T>(id, delta, expires_at).await
+    }
+
+    pub async fn decr_by_in<S>(
+        id: &str,
+        delta: i64,
+        ttl: Duration,
+        storage: &S,
+    ) -> anyhow::Result<i64>
+    where
+        S: CacheStorage,
+    {
+        let expires_at = UnixTimestampSecs::now().add(ttl).as_i64();
+        storage.decr_by::<T>(id, delta, expires_at).await
+    }
+
+    pub async fn compare_and_set_in<S>(&self, expected: &T, storage: &S) -> anyhow::Result<bool>
+    where
+        S: CacheStorage,
+    {
+        storage.compare_and_set(self, expected).await
+    }
+
+    pub async fn scan_prefix_in<S>(
+        id_prefix: &str,
+        cursor: Option<&str>,
+        limit: u64,
+        storage: &S,
+    ) -> anyhow::Result<CacheScanPage>
+    where
+        S: CacheStorage,
+    {
+        storage.scan_prefix::<T>(id_prefix, cursor, limit).await
+    }
+
+    pub async fn get_many_in<S>(ids: &[&str], storage: &S) -> anyhow::Result<Vec<Option<Self>>>
+    where
+        S: CacheStorage,
+    {
+        storage.get_many(ids).await
+    }
+
     pub async fn get(id: &str) -> anyhow::Result<Option<Self>> {
         Self::get_in(id, storage::get()).await
     }
@@ -163,6 +215,30 @@ where
         Self::batch_remove_in(id_prefix, storage::get()).await
     }
 
+    pub async fn incr_by(id: &str, delta: i64, ttl: Duration) -> anyhow::Result<i64> {
+        Self::incr_by_in(id, delta, ttl, storage::get()).await
+    }
+
+    pub async fn decr_by(id: &str, delta: i64, ttl: Duration) -> anyhow::Result<i64> {
+        Self::decr_by_in(id, delta, ttl, storage::get()).await
+    }
+
+    pub async fn compare_and_set(&self, expected: &T) -> anyhow::Result<bool> {
+        Self::compare_and_set_in(self, expected, storage::get()).await
+    }
+
+    pub async fn scan_prefix(
+        id_prefix: &str,
+        cursor: Option<&str>,
+        limit: u64,
+    ) -> anyhow::Result<CacheScanPage> {
+        Self::scan_prefix_in(id_prefix, cursor, limit, storage::get()).await
+    }
+
+    pub async fn get_many(ids: &[&str]) -> anyhow::Result<Vec<Option<Self>>> {
+        Self::get_many_in(ids, storage::get()).await
+    }
+
     pub fn is_expired(&self) -> bool {
         UnixTimestampSecs::now().as_i64() > self.expires_at
     }
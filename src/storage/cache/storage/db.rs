@@ -1,26 +1,24 @@
-use std::sync::OnceLock;
+use std::collections::HashMap;
 
 use crate::model::po::cache::CachePo;
-use crate::storage::cache::storage::{CacheSetMode, CacheStorage};
+use crate::storage::cache::storage::{
+    CacheKindStats, CacheScanEntry, CacheScanPage, CacheSetMode, CacheStorage,
+};
 use crate::storage::cache::{Cache, CacheData};
 use crate::storage::db::DbPool;
+use crate::util::time::UnixTimestampSecs;
 
-static STORAGE: OnceLock<DbCacheStorage> = OnceLock::new();
-
-pub fn init(db: DbPool) -> anyhow::Result<()> {
-    STORAGE
-        .set(DbCacheStorage { db })
-        .map_err(|_| anyhow::anyhow!("重复初始化数据库缓存存储器"))
-}
-
-pub fn get() -> &'static DbCacheStorage {
-    STORAGE.get().expect("数据库缓存存储器未初始化")
-}
-
+/// 数据库缓存存储器，缓存存储在 cache 表中
 pub struct DbCacheStorage {
     db: DbPool,
 }
 
+impl DbCacheStorage {
+    pub fn new(db: DbPool) -> Self {
+        Self { db }
+    }
+}
+
 impl CacheStorage for DbCacheStorage {
     async fn get<T>(&self, id: &str) -> anyhow::Result<Option<Cache<T>>>
     where
@@ -114,6 +112,128 @@ impl CacheStorage for DbCacheStorage {
             .await
             .map(|_| ())
     }
+
+    async fn incr_by<T>(&self, id: &str, delta: i64, expires_at: i64) -> anyhow::Result<i64>
+    where
+        T: CacheData,
+    {
+        let mut db = self.db.acquire().await?;
+        crate::storage::db::cache::increment_or_create(
+            T::kind(),
+            id,
+            delta,
+            UnixTimestampSecs::now().as_i64(),
+            expires_at,
+            &mut db,
+        )
+        .await
+    }
+
+    async fn compare_and_set<T>(&self, cache: &Cache<T>, expected: &T) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        let mut db = self.db.acquire().await?;
+        let po = CachePo::try_from(cache)?;
+        let expected = serde_json::to_string(expected)?;
+        crate::storage::db::cache::compare_and_update_active(&po, &expected, &mut db)
+            .await
+            .map(|rows| rows == 1)
+    }
+
+    async fn scan_prefix<T>(
+        &self,
+        id_prefix: &str,
+        cursor: Option<&str>,
+        limit: u64,
+    ) -> anyhow::Result<CacheScanPage>
+    where
+        T: CacheData,
+    {
+        let mut db = self.db.acquire().await?;
+        let entries = crate::storage::db::cache::list_active_id_by_id_prefix(
+            T::kind(),
+            id_prefix,
+            cursor,
+            limit.saturating_add(1),
+            &mut db,
+        )
+        .await?
+        .into_iter()
+        .map(|(id, expires_at)| CacheScanEntry { id, expires_at })
+        .collect();
+        Ok(CacheScanPage::from_entries(entries, limit))
+    }
+
+    async fn get_many<T>(&self, ids: &[&str]) -> anyhow::Result<Vec<Option<Cache<T>>>>
+    where
+        T: CacheData,
+    {
+        let mut db = self.db.acquire().await?;
+        let found = crate::storage::db::cache::list_active_by_ids(T::kind(), ids, &mut db)
+            .await?
+            .into_iter()
+            .map(|po| (po.id.clone(), po))
+            .collect::<HashMap<_, _>>();
+        ids.iter()
+            .map(|id| found.get(*id).cloned().map(Cache::try_from).transpose())
+            .collect()
+    }
+
+    async fn stats(&self) -> anyhow::Result<Vec<CacheKindStats>> {
+        let mut db = self.db.acquire().await?;
+        crate::storage::db::cache::stats_by_kind(&mut db)
+            .await?
+            .into_iter()
+            .map(|(kind, total, expired, data_size)| {
+                Ok(CacheKindStats {
+                    kind,
+                    total: u64::try_from(total)?,
+                    expired: u64::try_from(expired)?,
+                    data_size: u64::try_from(data_size)?,
+                })
+            })
+            .collect()
+    }
+
+    async fn inspect(
+        &self,
+        kind: &str,
+        id: &str,
+    ) -> anyhow::Result<Option<Cache<serde_json::Value>>> {
+        let mut db = self.db.acquire().await?;
+        let Some(po) = crate::storage::db::cache::find(kind, id, &mut db).await? else {
+            return Ok(None);
+        };
+        Ok(Some(Cache {
+            id: po.id,
+            created_at: po.created_at,
+            expires_at: po.expires_at,
+            kind: po.kind,
+            data: serde_json::from_str(&po.data)?,
+        }))
+    }
+
+    async fn remove_by_kind(&self, kind: &str, id_prefix: &str) -> anyhow::Result<u64> {
+        let mut db = self.db.acquire().await?;
+        crate::storage::db::cache::remove_by_id_prefix(kind, id_prefix, &mut db).await
+    }
+
+    async fn purge_expired(&self) -> anyhow::Result<u64> {
+        /// 每批清理的条数，分批清理以避免长时间占用数据库写锁
+        const PURGE_BATCH: u64 = 1000;
+
+        let mut rows = 0;
+        loop {
+            let mut db = self.db.acquire().await?;
+            let removed =
+                crate::storage::db::cache::remove_all_expired(PURGE_BATCH, &mut db).await?;
+            rows += removed;
+            if removed < PURGE_BATCH {
+                return Ok(rows);
+            }
+        }
+    }
 }
 
 impl<T> TryFrom<&Cache<T>> for CachePo
@@ -149,3 +269,25 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn conformance() {
+        // 内存数据库的每个连接相互独立，因此只使用一个连接
+        let db = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::raw_sql(include_str!("../../../../sqlite/migrations/cache.sql"))
+            .execute(&db)
+            .await
+            .unwrap();
+        super::super::conformance::run(&DbCacheStorage::new(db)).await;
+    }
+}
@@ -0,0 +1,368 @@
+use std::collections::BTreeMap;
+use std::hash::{BuildHasher, RandomState};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::storage::cache::storage::{
+    CacheKindStats, CacheScanEntry, CacheScanPage, CacheSetMode, CacheStorage,
+};
+use crate::storage::cache::{Cache, CacheData};
+use crate::util::time::UnixTimestampSecs;
+
+/// 分片数量
+const SHARD_COUNT: usize = 16;
+
+type Shard = BTreeMap<(String, String), MemoryCacheEntry>;
+
+/// 内存缓存存储器，按缓存键分片加锁以降低并发竞争
+///
+/// 过期数据在访问时视为不存在，并由定时任务调用 [`MemoryCacheStorage::remove_expired`] 清理。
+pub struct MemoryCacheStorage {
+    shards: Box<[Mutex<Shard>]>,
+    hasher: RandomState,
+}
+
+/// 内存缓存条目
+#[derive(Debug, Clone)]
+struct MemoryCacheEntry {
+    /// 缓存数据（JSON）
+    data: String,
+    /// 创建时间
+    created_at: i64,
+    /// 过期时间
+    expires_at: i64,
+}
+
+impl MemoryCacheEntry {
+    /// 创建值为 0 的计数器
+    fn counter(created_at: i64, expires_at: i64) -> Self {
+        Self {
+            data: "0".to_owned(),
+            created_at,
+            expires_at,
+        }
+    }
+
+    fn is_active(&self, now: i64) -> bool {
+        self.expires_at >= now
+    }
+}
+
+impl MemoryCacheStorage {
+    pub fn new() -> Self {
+        Self {
+            shards: (0..SHARD_COUNT).map(|_| Mutex::default()).collect(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// 清理所有过期的缓存数据，返回清理的条数
+    pub fn remove_expired(&self) -> u64 {
+        let now = UnixTimestampSecs::now().as_i64();
+        let mut rows = 0;
+        for shard in self.shards.iter() {
+            let mut shard = lock(shard);
+            let len = shard.len();
+            shard.retain(|_, entry| entry.is_active(now));
+            rows += (len - shard.len()) as u64;
+        }
+        rows
+    }
+
+    fn shard(&self, kind: &str, id: &str) -> MutexGuard<'_, Shard> {
+        let index = self.hasher.hash_one((kind, id)) as usize % self.shards.len();
+        lock(&self.shards[index])
+    }
+
+    fn find_active<T>(&self, id: &str) -> Option<MemoryCacheEntry>
+    where
+        T: CacheData,
+    {
+        let now = UnixTimestampSecs::now().as_i64();
+        self.shard(T::kind(), id)
+            .get(&key::<T>(id))
+            .filter(|entry| entry.is_active(now))
+            .cloned()
+    }
+}
+
+impl Default for MemoryCacheStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CacheStorage for MemoryCacheStorage {
+    async fn get<T>(&self, id: &str) -> anyhow::Result<Option<Cache<T>>>
+    where
+        T: CacheData,
+    {
+        let Some(entry) = self.find_active::<T>(id) else {
+            return Ok(None);
+        };
+        Ok(Some(Cache {
+            id: id.to_owned(),
+            created_at: entry.created_at,
+            expires_at: entry.expires_at,
+            kind: T::kind().to_owned(),
+            data: serde_json::from_str(&entry.data)?,
+        }))
+    }
+
+    async fn get_expires_at<T>(&self, id: &str) -> anyhow::Result<Option<i64>>
+    where
+        T: CacheData,
+    {
+        Ok(self.find_active::<T>(id).map(|entry| entry.expires_at))
+    }
+
+    async fn set<T>(&self, cache: &Cache<T>, mode: CacheSetMode) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        let entry = MemoryCacheEntry {
+            data: serde_json::to_string(&cache.data)?,
+            created_at: cache.created_at,
+            expires_at: cache.expires_at,
+        };
+        let now = UnixTimestampSecs::now().as_i64();
+        let mut shard = self.shard(&cache.kind, &cache.id);
+        let key = (cache.kind.clone(), cache.id.clone());
+        let is_active = shard.get(&key).is_some_and(|entry| entry.is_active(now));
+        let written = match mode {
+            CacheSetMode::Overwrite => true,
+            CacheSetMode::OnlyIfNotExists => !is_active,
+            CacheSetMode::OnlyIfExists => is_active,
+        };
+        if written {
+            shard.insert(key, entry);
+        }
+        Ok(written)
+    }
+
+    async fn set_expires_at<T>(&self, id: &str, expires_at: i64) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        let now = UnixTimestampSecs::now().as_i64();
+        let mut shard = self.shard(T::kind(), id);
+        match shard.get_mut(&key::<T>(id)) {
+            Some(entry) if entry.is_active(now) => {
+                entry.expires_at = expires_at;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn exists<T>(&self, id: &str) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        Ok(self.find_active::<T>(id).is_some())
+    }
+
+    async fn remove<T>(&self, id: &str) -> anyhow::Result<()>
+    where
+        T: CacheData,
+    {
+        self.shard(T::kind(), id).remove(&key::<T>(id));
+        Ok(())
+    }
+
+    async fn batch_remove<T>(&self, id_prefix: &str) -> anyhow::Result<()>
+    where
+        T: CacheData,
+    {
+        self.remove_by_kind(T::kind(), id_prefix).await.map(|_| ())
+    }
+
+    async fn incr_by<T>(&self, id: &str, delta: i64, expires_at: i64) -> anyhow::Result<i64>
+    where
+        T: CacheData,
+    {
+        let now = UnixTimestampSecs::now().as_i64();
+        let mut shard = self.shard(T::kind(), id);
+        let entry = shard
+            .entry(key::<T>(id))
+            .and_modify(|entry| {
+                if !entry.is_active(now) {
+                    *entry = MemoryCacheEntry::counter(now, expires_at);
+                }
+            })
+            .or_insert_with(|| MemoryCacheEntry::counter(now, expires_at));
+        let value = entry
+            .data
+            .parse::<i64>()
+            .map_err(|e| anyhow::anyhow!("缓存数据不是整数（ID：{id}）：{e}"))?
+            .checked_add(delta)
+            .ok_or_else(|| anyhow::anyhow!("计数器数值溢出（ID：{id}）"))?;
+        entry.data = value.to_string();
+        Ok(value)
+    }
+
+    async fn compare_and_set<T>(&self, cache: &Cache<T>, expected: &T) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        let data = serde_json::to_string(&cache.data)?;
+        let expected = serde_json::to_string(expected)?;
+        let now = UnixTimestampSecs::now().as_i64();
+        let mut shard = self.shard(&cache.kind, &cache.id);
+        match shard.get_mut(&(cache.kind.clone(), cache.id.clone())) {
+            Some(entry) if entry.is_active(now) && entry.data == expected => {
+                *entry = MemoryCacheEntry {
+                    data,
+                    created_at: cache.created_at,
+                    expires_at: cache.expires_at,
+                };
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    async fn scan_prefix<T>(
+        &self,
+        id_prefix: &str,
+        cursor: Option<&str>,
+        limit: u64,
+    ) -> anyhow::Result<CacheScanPage>
+    where
+        T: CacheData,
+    {
+        let now = UnixTimestampSecs::now().as_i64();
+        let mut entries = vec![];
+        for shard in self.shards.iter() {
+            let shard = lock(shard);
+            let start = (T::kind().to_owned(), id_prefix.to_owned());
+            entries.extend(
+                shard
+                    .range(start..)
+                    .take_while(|((kind, id), _)| kind == T::kind() && id.starts_with(id_prefix))
+                    .filter(|((_, id), entry)| {
+                        entry.is_active(now) && cursor.is_none_or(|cursor| id.as_str() > cursor)
+                    })
+                    .map(|((_, id), entry)| CacheScanEntry {
+                        id: id.clone(),
+                        expires_at: entry.expires_at,
+                    }),
+            );
+        }
+        entries.sort_unstable_by(|a, b| a.id.cmp(&b.id));
+        entries.truncate(usize::try_from(limit.saturating_add(1)).unwrap_or(usize::MAX));
+        Ok(CacheScanPage::from_entries(entries, limit))
+    }
+
+    async fn get_many<T>(&self, ids: &[&str]) -> anyhow::Result<Vec<Option<Cache<T>>>>
+    where
+        T: CacheData,
+    {
+        let mut caches = Vec::with_capacity(ids.len());
+        for id in ids {
+            caches.push(self.get(id).await?);
+        }
+        Ok(caches)
+    }
+
+    async fn stats(&self) -> anyhow::Result<Vec<CacheKindStats>> {
+        let now = UnixTimestampSecs::now().as_i64();
+        let mut stats = BTreeMap::<String, CacheKindStats>::new();
+        for shard in self.shards.iter() {
+            for ((kind, _), entry) in lock(shard).iter() {
+                let stats = stats.entry(kind.clone()).or_insert_with(|| CacheKindStats {
+                    kind: kind.clone(),
+                    total: 0,
+                    expired: 0,
+                    data_size: 0,
+                });
+                stats.total += 1;
+                stats.expired += u64::from(!entry.is_active(now));
+                stats.data_size += entry.data.len() as u64;
+            }
+        }
+        Ok(stats.into_values().collect())
+    }
+
+    async fn inspect(
+        &self,
+        kind: &str,
+        id: &str,
+    ) -> anyhow::Result<Option<Cache<serde_json::Value>>> {
+        let Some(entry) = self
+            .shard(kind, id)
+            .get(&(kind.to_owned(), id.to_owned()))
+            .cloned()
+        else {
+            return Ok(None);
+        };
+        Ok(Some(Cache {
+            id: id.to_owned(),
+            created_at: entry.created_at,
+            expires_at: entry.expires_at,
+            kind: kind.to_owned(),
+            data: serde_json::from_str(&entry.data)?,
+        }))
+    }
+
+    async fn remove_by_kind(&self, kind: &str, id_prefix: &str) -> anyhow::Result<u64> {
+        let mut rows = 0;
+        for shard in self.shards.iter() {
+            let mut shard = lock(shard);
+            let len = shard.len();
+            shard.retain(|(k, id), _| k != kind || !id.starts_with(id_prefix));
+            rows += (len - shard.len()) as u64;
+        }
+        Ok(rows)
+    }
+
+    async fn purge_expired(&self) -> anyhow::Result<u64> {
+        Ok(self.remove_expired())
+    }
+}
+
+fn key<T>(id: &str) -> (String, String)
+where
+    T: CacheData,
+{
+    (T::kind().to_owned(), id.to_owned())
+}
+
+fn lock(shard: &Mutex<Shard>) -> MutexGuard<'_, Shard> {
+    // 持有锁期间不会发生 panic 导致数据不一致，因此可以忽略锁中毒
+    shard.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn conformance() {
+        super::super::conformance::run(&MemoryCacheStorage::new()).await;
+    }
+
+    #[derive(serde::Deserialize, serde::Serialize)]
+    struct TestCo;
+
+    impl CacheData for TestCo {
+        fn kind() -> &'static str {
+            "test"
+        }
+    }
+
+    #[tokio::test]
+    async fn remove_expired() {
+        let storage = MemoryCacheStorage::new();
+        let now = UnixTimestampSecs::now().as_i64();
+        for expires_at in [now - 10, now + 3600, now - 1] {
+            let cache = Cache::new(TestCo, now, expires_at);
+            storage.set(&cache, CacheSetMode::Overwrite).await.unwrap();
+        }
+        assert_eq!(storage.remove_expired(), 2);
+        assert_eq!(storage.remove_expired(), 0);
+        assert_eq!(
+            storage.shards.iter().map(|s| lock(s).len()).sum::<usize>(),
+            1
+        );
+    }
+}
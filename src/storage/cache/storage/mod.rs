@@ -1,7 +1,16 @@
 mod db;
-pub use db::{get, init};
+mod memory;
+mod redis;
 
+pub use self::redis::RedisCacheStorage;
+pub use db::DbCacheStorage;
+pub use memory::MemoryCacheStorage;
+
+use std::sync::OnceLock;
+
+use crate::config::{CacheBackendConfig, CacheConfig};
 use crate::storage::cache::{Cache, CacheData};
+use crate::storage::db::DbPool;
 
 pub trait CacheStorage {
     fn get<T>(&self, id: &str) -> impl Future<Output = anyhow::Result<Option<Cache<T>>>> + Send
@@ -42,6 +51,130 @@ pub trait CacheStorage {
     fn batch_remove<T>(&self, id_prefix: &str) -> impl Future<Output = anyhow::Result<()>> + Send
     where
         T: CacheData;
+
+    /// 原子地增加计数器的值并返回增加后的值，缓存数据需要序列化为整数
+    ///
+    /// 计数器不存在或已过期时从 0 开始计数，并设置过期时间为 `expires_at`，
+    /// 计数器已存在时保持原有的过期时间不变。
+    fn incr_by<T>(
+        &self,
+        id: &str,
+        delta: i64,
+        expires_at: i64,
+    ) -> impl Future<Output = anyhow::Result<i64>> + Send
+    where
+        T: CacheData;
+
+    /// 原子地减少计数器的值并返回减少后的值，规则同 [`CacheStorage::incr_by`]
+    fn decr_by<T>(
+        &self,
+        id: &str,
+        delta: i64,
+        expires_at: i64,
+    ) -> impl Future<Output = anyhow::Result<i64>> + Send
+    where
+        T: CacheData,
+    {
+        self.incr_by::<T>(id, delta.saturating_neg(), expires_at)
+    }
+
+    /// 仅当缓存存在且数据与 `expected` 相同时才更新缓存，数据按序列化后的 JSON 比较
+    fn compare_and_set<T>(
+        &self,
+        cache: &Cache<T>,
+        expected: &T,
+    ) -> impl Future<Output = anyhow::Result<bool>> + Send
+    where
+        T: CacheData;
+
+    /// 按 ID 顺序分页列出指定前缀的缓存 ID 及过期时间
+    ///
+    /// `cursor` 为上一页返回的游标，为空时从第一页开始。
+    fn scan_prefix<T>(
+        &self,
+        id_prefix: &str,
+        cursor: Option<&str>,
+        limit: u64,
+    ) -> impl Future<Output = anyhow::Result<CacheScanPage>> + Send
+    where
+        T: CacheData;
+
+    /// 批量获取缓存，返回结果与 `ids` 一一对应，缓存不存在时对应位置为 `None`
+    fn get_many<T>(
+        &self,
+        ids: &[&str],
+    ) -> impl Future<Output = anyhow::Result<Vec<Option<Cache<T>>>>> + Send
+    where
+        T: CacheData;
+
+    /// 按缓存类型统计缓存数量及数据大小
+    fn stats(&self) -> impl Future<Output = anyhow::Result<Vec<CacheKindStats>>> + Send;
+
+    /// 查看缓存的原始数据，已过期但尚未清理的缓存也会返回
+    fn inspect(
+        &self,
+        kind: &str,
+        id: &str,
+    ) -> impl Future<Output = anyhow::Result<Option<Cache<serde_json::Value>>>> + Send;
+
+    /// 删除指定缓存类型下指定前缀的缓存，返回删除的条数
+    fn remove_by_kind(
+        &self,
+        kind: &str,
+        id_prefix: &str,
+    ) -> impl Future<Output = anyhow::Result<u64>> + Send;
+
+    /// 清理所有过期的缓存，返回清理的条数
+    fn purge_expired(&self) -> impl Future<Output = anyhow::Result<u64>> + Send;
+}
+
+/// 缓存类型的统计信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKindStats {
+    /// 缓存类型
+    pub kind: String,
+    /// 缓存数量
+    pub total: u64,
+    /// 已过期但尚未清理的缓存数量
+    pub expired: u64,
+    /// 缓存数据大小，不同存储器的统计口径可能包含缓存的元数据
+    pub data_size: u64,
+}
+
+/// 缓存扫描结果
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheScanPage {
+    /// 本页的缓存
+    pub entries: Vec<CacheScanEntry>,
+    /// 下一页的游标，为空时表示没有更多数据
+    pub next_cursor: Option<String>,
+}
+
+impl CacheScanPage {
+    /// 根据多查询一条的结果生成分页，`entries` 需要按 ID 排序
+    fn from_entries(mut entries: Vec<CacheScanEntry>, limit: u64) -> Self {
+        let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+        if entries.len() <= limit {
+            return Self {
+                entries,
+                next_cursor: None,
+            };
+        }
+        entries.truncate(limit);
+        Self {
+            next_cursor: entries.last().map(|entry| entry.id.clone()),
+            entries,
+        }
+    }
+}
+
+/// 缓存扫描条目
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheScanEntry {
+    /// 缓存ID
+    pub id: String,
+    /// 过期时间
+    pub expires_at: i64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -56,3 +189,697 @@ pub enum CacheSetMode {
     /// 用于仅更新已有的缓存数据，避免新增无效缓存
     OnlyIfExists,
 }
+
+/// 根据配置选择的缓存存储器
+pub enum AnyCacheStorage {
+    Db(DbCacheStorage),
+    Memory(MemoryCacheStorage),
+    Redis(RedisCacheStorage),
+}
+
+impl AnyCacheStorage {
+    pub async fn from_config(config: &CacheConfig, db: DbPool) -> anyhow::Result<Self> {
+        Ok(match &config.backend {
+            CacheBackendConfig::Database => Self::Db(DbCacheStorage::new(db)),
+            CacheBackendConfig::Memory => Self::Memory(MemoryCacheStorage::new()),
+            CacheBackendConfig::Redis(redis) => {
+                Self::Redis(RedisCacheStorage::from_config(redis).await?)
+            }
+        })
+    }
+}
+
+impl CacheStorage for AnyCacheStorage {
+    async fn get<T>(&self, id: &str) -> anyhow::Result<Option<Cache<T>>>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.get(id).await,
+            Self::Memory(storage) => storage.get(id).await,
+            Self::Redis(storage) => storage.get(id).await,
+        }
+    }
+
+    async fn get_expires_at<T>(&self, id: &str) -> anyhow::Result<Option<i64>>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.get_expires_at::<T>(id).await,
+            Self::Memory(storage) => storage.get_expires_at::<T>(id).await,
+            Self::Redis(storage) => storage.get_expires_at::<T>(id).await,
+        }
+    }
+
+    async fn set<T>(&self, cache: &Cache<T>, mode: CacheSetMode) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.set(cache, mode).await,
+            Self::Memory(storage) => storage.set(cache, mode).await,
+            Self::Redis(storage) => storage.set(cache, mode).await,
+        }
+    }
+
+    async fn set_expires_at<T>(&self, id: &str, expires_at: i64) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.set_expires_at::<T>(id, expires_at).await,
+            Self::Memory(storage) => storage.set_expires_at::<T>(id, expires_at).await,
+            Self::Redis(storage) => storage.set_expires_at::<T>(id, expires_at).await,
+        }
+    }
+
+    async fn exists<T>(&self, id: &str) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.exists::<T>(id).await,
+            Self::Memory(storage) => storage.exists::<T>(id).await,
+            Self::Redis(storage) => storage.exists::<T>(id).await,
+        }
+    }
+
+    async fn remove<T>(&self, id: &str) -> anyhow::Result<()>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.remove::<T>(id).await,
+            Self::Memory(storage) => storage.remove::<T>(id).await,
+            Self::Redis(storage) => storage.remove::<T>(id).await,
+        }
+    }
+
+    async fn batch_remove<T>(&self, id_prefix: &str) -> anyhow::Result<()>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.batch_remove::<T>(id_prefix).await,
+            Self::Memory(storage) => storage.batch_remove::<T>(id_prefix).await,
+            Self::Redis(storage) => storage.batch_remove::<T>(id_prefix).await,
+        }
+    }
+
+    async fn incr_by<T>(&self, id: &str, delta: i64, expires_at: i64) -> anyhow::Result<i64>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.incr_by::<T>(id, delta, expires_at).await,
+            Self::Memory(storage) => storage.incr_by::<T>(id, delta, expires_at).await,
+            Self::Redis(storage) => storage.incr_by::<T>(id, delta, expires_at).await,
+        }
+    }
+
+    async fn compare_and_set<T>(&self, cache: &Cache<T>, expected: &T) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.compare_and_set(cache, expected).await,
+            Self::Memory(storage) => storage.compare_and_set(cache, expected).await,
+            Self::Redis(storage) => storage.compare_and_set(cache, expected).await,
+        }
+    }
+
+    async fn scan_prefix<T>(
+        &self,
+        id_prefix: &str,
+        cursor: Option<&str>,
+        limit: u64,
+    ) -> anyhow::Result<CacheScanPage>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.scan_prefix::<T>(id_prefix, cursor, limit).await,
+            Self::Memory(storage) => storage.scan_prefix::<T>(id_prefix, cursor, limit).await,
+            Self::Redis(storage) => storage.scan_prefix::<T>(id_prefix, cursor, limit).await,
+        }
+    }
+
+    async fn get_many<T>(&self, ids: &[&str]) -> anyhow::Result<Vec<Option<Cache<T>>>>
+    where
+        T: CacheData,
+    {
+        match self {
+            Self::Db(storage) => storage.get_many(ids).await,
+            Self::Memory(storage) => storage.get_many(ids).await,
+            Self::Redis(storage) => storage.get_many(ids).await,
+        }
+    }
+
+    async fn stats(&self) -> anyhow::Result<Vec<CacheKindStats>> {
+        match self {
+            Self::Db(storage) => storage.stats().await,
+            Self::Memory(storage) => storage.stats().await,
+            Self::Redis(storage) => storage.stats().await,
+        }
+    }
+
+    async fn inspect(
+        &self,
+        kind: &str,
+        id: &str,
+    ) -> anyhow::Result<Option<Cache<serde_json::Value>>> {
+        match self {
+            Self::Db(storage) => storage.inspect(kind, id).await,
+            Self::Memory(storage) => storage.inspect(kind, id).await,
+            Self::Redis(storage) => storage.inspect(kind, id).await,
+        }
+    }
+
+    async fn remove_by_kind(&self, kind: &str, id_prefix: &str) -> anyhow::Result<u64> {
+        match self {
+            Self::Db(storage) => storage.remove_by_kind(kind, id_prefix).await,
+            Self::Memory(storage) => storage.remove_by_kind(kind, id_prefix).await,
+            Self::Redis(storage) => storage.remove_by_kind(kind, id_prefix).await,
+        }
+    }
+
+    async fn purge_expired(&self) -> anyhow::Result<u64> {
+        match self {
+            Self::Db(storage) => storage.purge_expired().await,
+            Self::Memory(storage) => storage.purge_expired().await,
+            Self::Redis(storage) => storage.purge_expired().await,
+        }
+    }
+}
+
+static STORAGE: OnceLock<AnyCacheStorage> = OnceLock::new();
+
+pub async fn init(config: &CacheConfig, db: DbPool) -> anyhow::Result<()> {
+    STORAGE
+        .set(AnyCacheStorage::from_config(config, db).await?)
+        .map_err(|_| anyhow::anyhow!("重复初始化缓存存储器"))
+}
+
+pub fn get() -> &'static AnyCacheStorage {
+    STORAGE.get().expect("缓存存储器未初始化")
+}
+
+/// 各缓存存储器共用的一致性测试，保证不同后端的行为相同
+#[cfg(test)]
+mod conformance {
+    use std::borrow::Cow;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::util::time::UnixTimestampSecs;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    struct TestCo {
+        id: String,
+        value: u32,
+    }
+
+    impl CacheData for TestCo {
+        fn kind() -> &'static str {
+            "test"
+        }
+
+        fn generate_id(&self) -> Cow<'_, str> {
+            self.id.as_str().into()
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    struct OtherTestCo {
+        id: String,
+    }
+
+    impl CacheData for OtherTestCo {
+        fn kind() -> &'static str {
+            "other_test"
+        }
+
+        fn generate_id(&self) -> Cow<'_, str> {
+            self.id.as_str().into()
+        }
+    }
+
+    fn active(id: &str, value: u32) -> Cache<TestCo> {
+        let now = UnixTimestampSecs::now().as_i64();
+        let data = TestCo {
+            id: id.to_owned(),
+            value,
+        };
+        Cache::new(data, now, now + 3600)
+    }
+
+    fn expired(id: &str, value: u32) -> Cache<TestCo> {
+        let now = UnixTimestampSecs::now().as_i64();
+        let data = TestCo {
+            id: id.to_owned(),
+            value,
+        };
+        Cache::new(data, now - 3600, now - 10)
+    }
+
+    async fn value<S: CacheStorage>(storage: &S, id: &str) -> Option<u32> {
+        storage
+            .get::<TestCo>(id)
+            .await
+            .unwrap()
+            .map(|cache| cache.data.value)
+    }
+
+    pub(super) async fn run<S: CacheStorage>(storage: &S) {
+        overwrite(storage).await;
+        only_if_not_exists(storage).await;
+        only_if_exists(storage).await;
+        expires_at(storage).await;
+        expired_entry(storage).await;
+        remove(storage).await;
+        batch_remove(storage).await;
+        counter(storage).await;
+        compare_and_set(storage).await;
+        scan_prefix(storage).await;
+        get_many(storage).await;
+        admin(storage).await;
+    }
+
+    async fn overwrite<S: CacheStorage>(storage: &S) {
+        let cache = active("overwrite", 1);
+        assert!(storage.set(&cache, CacheSetMode::Overwrite).await.unwrap());
+        let found = storage.get::<TestCo>("overwrite").await.unwrap().unwrap();
+        assert_eq!(found.data, cache.data);
+        assert_eq!(found.kind, "test");
+        assert_eq!(found.created_at, cache.created_at);
+        assert_eq!(found.expires_at, cache.expires_at);
+
+        assert!(
+            storage
+                .set(&active("overwrite", 2), CacheSetMode::Overwrite)
+                .await
+                .unwrap()
+        );
+        assert_eq!(value(storage, "overwrite").await, Some(2));
+        assert!(!storage.exists::<OtherTestCo>("overwrite").await.unwrap());
+    }
+
+    async fn only_if_not_exists<S: CacheStorage>(storage: &S) {
+        let mode = CacheSetMode::OnlyIfNotExists;
+        assert!(storage.set(&active("nx", 1), mode).await.unwrap());
+        assert!(!storage.set(&active("nx", 2), mode).await.unwrap());
+        assert_eq!(value(storage, "nx").await, Some(1));
+
+        let overwrite = CacheSetMode::Overwrite;
+        assert!(
+            storage
+                .set(&expired("nx_expired", 1), overwrite)
+                .await
+                .unwrap()
+        );
+        assert!(storage.set(&active("nx_expired", 2), mode).await.unwrap());
+        assert_eq!(value(storage, "nx_expired").await, Some(2));
+    }
+
+    async fn only_if_exists<S: CacheStorage>(storage: &S) {
+        let mode = CacheSetMode::OnlyIfExists;
+        assert!(!storage.set(&active("xx", 1), mode).await.unwrap());
+        assert_eq!(value(storage, "xx").await, None);
+
+        let overwrite = CacheSetMode::Overwrite;
+        assert!(storage.set(&active("xx", 1), overwrite).await.unwrap());
+        assert!(storage.set(&active("xx", 2), mode).await.unwrap());
+        assert_eq!(value(storage, "xx").await, Some(2));
+
+        assert!(
+            storage
+                .set(&expired("xx_expired", 1), overwrite)
+                .await
+                .unwrap()
+        );
+        assert!(!storage.set(&active("xx_expired", 2), mode).await.unwrap());
+        assert_eq!(value(storage, "xx_expired").await, None);
+    }
+
+    async fn expires_at<S: CacheStorage>(storage: &S) {
+        let cache = active("expires_at", 1);
+        storage.set(&cache, CacheSetMode::Overwrite).await.unwrap();
+        assert_eq!(
+            storage
+                .get_expires_at::<TestCo>("expires_at")
+                .await
+                .unwrap(),
+            Some(cache.expires_at)
+        );
+
+        let expires_at = cache.expires_at + 60;
+        assert!(
+            storage
+                .set_expires_at::<TestCo>("expires_at", expires_at)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            storage
+                .get_expires_at::<TestCo>("expires_at")
+                .await
+                .unwrap(),
+            Some(expires_at)
+        );
+        assert_eq!(value(storage, "expires_at").await, Some(1));
+
+        assert!(
+            !storage
+                .set_expires_at::<TestCo>("expires_at_missing", expires_at)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !storage
+                .exists::<TestCo>("expires_at_missing")
+                .await
+                .unwrap()
+        );
+    }
+
+    async fn expired_entry<S: CacheStorage>(storage: &S) {
+        let cache = expired("expired", 1);
+        storage.set(&cache, CacheSetMode::Overwrite).await.unwrap();
+        assert_eq!(value(storage, "expired").await, None);
+        assert!(!storage.exists::<TestCo>("expired").await.unwrap());
+        assert_eq!(
+            storage.get_expires_at::<TestCo>("expired").await.unwrap(),
+            None
+        );
+        assert!(
+            !storage
+                .set_expires_at::<TestCo>("expired", cache.expires_at + 7200)
+                .await
+                .unwrap()
+        );
+    }
+
+    async fn remove<S: CacheStorage>(storage: &S) {
+        storage
+            .set(&active("remove", 1), CacheSetMode::Overwrite)
+            .await
+            .unwrap();
+        storage.remove::<TestCo>("remove").await.unwrap();
+        storage.remove::<TestCo>("remove").await.unwrap();
+        assert!(!storage.exists::<TestCo>("remove").await.unwrap());
+    }
+
+    async fn batch_remove<S: CacheStorage>(storage: &S) {
+        let mode = CacheSetMode::Overwrite;
+        for id in ["batch:1", "batch:2", "batch%:3", "batch_4", "other:1"] {
+            storage.set(&active(id, 1), mode).await.unwrap();
+        }
+        let other = Cache::with_ttl(
+            OtherTestCo {
+                id: "batch:1".to_owned(),
+            },
+            std::time::Duration::from_secs(3600),
+        );
+        storage.set(&other, mode).await.unwrap();
+
+        storage.batch_remove::<TestCo>("batch:").await.unwrap();
+        assert!(!storage.exists::<TestCo>("batch:1").await.unwrap());
+        assert!(!storage.exists::<TestCo>("batch:2").await.unwrap());
+        assert!(storage.exists::<TestCo>("batch%:3").await.unwrap());
+        assert!(storage.exists::<TestCo>("other:1").await.unwrap());
+        assert!(storage.exists::<OtherTestCo>("batch:1").await.unwrap());
+
+        storage.batch_remove::<TestCo>("batch%").await.unwrap();
+        assert!(!storage.exists::<TestCo>("batch%:3").await.unwrap());
+        assert!(storage.exists::<TestCo>("batch_4").await.unwrap());
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+    struct CounterCo(i64);
+
+    impl CacheData for CounterCo {
+        fn kind() -> &'static str {
+            "counter"
+        }
+    }
+
+    /// 与 [`CounterCo`] 类型相同但数据可以不是整数的缓存
+    #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+    struct CounterTextCo(serde_json::Value);
+
+    impl CacheData for CounterTextCo {
+        fn kind() -> &'static str {
+            CounterCo::kind()
+        }
+    }
+
+    async fn counter<S: CacheStorage>(storage: &S) {
+        let now = UnixTimestampSecs::now().as_i64();
+        let expires_at = now + 3600;
+        assert_eq!(
+            storage
+                .incr_by::<CounterCo>("counter", 2, expires_at)
+                .await
+                .unwrap(),
+            2
+        );
+        assert_eq!(
+            storage
+                .incr_by::<CounterCo>("counter", 3, expires_at + 60)
+                .await
+                .unwrap(),
+            5
+        );
+        assert_eq!(
+            storage
+                .decr_by::<CounterCo>("counter", 7, expires_at + 60)
+                .await
+                .unwrap(),
+            -2
+        );
+        let cache = storage.get::<CounterCo>("counter").await.unwrap().unwrap();
+        assert_eq!(cache.data, CounterCo(-2));
+        assert_eq!(cache.expires_at, expires_at);
+
+        let expired = Cache::new(CounterCo(100), now - 3600, now - 10);
+        let id = expired.id.clone();
+        storage
+            .set(&expired, CacheSetMode::Overwrite)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage
+                .incr_by::<CounterCo>(&id, 1, expires_at)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            storage.get_expires_at::<CounterCo>(&id).await.unwrap(),
+            Some(expires_at)
+        );
+
+        // 未过期的数据不是整数时报错，且数据保持不变
+        for data in [
+            serde_json::json!("text"),
+            serde_json::json!("12"),
+            serde_json::json!(1.5),
+            serde_json::json!(1e3),
+        ] {
+            let text = Cache::new(CounterTextCo(data), now, expires_at);
+            storage.set(&text, CacheSetMode::Overwrite).await.unwrap();
+            assert!(
+                storage
+                    .incr_by::<CounterCo>(&text.id, 1, expires_at)
+                    .await
+                    .is_err(),
+                "{:?}",
+                text.data
+            );
+            let cache = storage
+                .get::<CounterTextCo>(&text.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(cache.data, text.data);
+        }
+
+        // 计数器数值溢出时报错，且数据保持不变
+        for (value, delta) in [(i64::MAX, 1), (i64::MIN, -1)] {
+            let counter = Cache::new(CounterCo(value), now, expires_at);
+            storage
+                .set(&counter, CacheSetMode::Overwrite)
+                .await
+                .unwrap();
+            assert!(
+                storage
+                    .incr_by::<CounterCo>(&counter.id, delta, expires_at)
+                    .await
+                    .is_err()
+            );
+            let cache = storage
+                .get::<CounterCo>(&counter.id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(cache.data, counter.data);
+        }
+
+        let handles = (0..20)
+            .map(|_| storage.incr_by::<CounterCo>("concurrent", 1, expires_at))
+            .collect::<Vec<_>>();
+        let mut results = futures_util::future::try_join_all(handles).await.unwrap();
+        results.sort();
+        assert_eq!(results, (1..=20).collect::<Vec<_>>());
+    }
+
+    async fn compare_and_set<S: CacheStorage>(storage: &S) {
+        let old = active("cas", 1);
+        assert!(
+            !storage
+                .compare_and_set(&active("cas", 2), &old.data)
+                .await
+                .unwrap()
+        );
+        storage.set(&old, CacheSetMode::Overwrite).await.unwrap();
+
+        let new = active("cas", 2);
+        assert!(
+            !storage
+                .compare_and_set(&new, &active("cas", 3).data)
+                .await
+                .unwrap()
+        );
+        assert_eq!(value(storage, "cas").await, Some(1));
+        assert!(storage.compare_and_set(&new, &old.data).await.unwrap());
+        assert_eq!(value(storage, "cas").await, Some(2));
+        assert!(!storage.compare_and_set(&new, &old.data).await.unwrap());
+
+        let expired = expired("cas_expired", 1);
+        storage
+            .set(&expired, CacheSetMode::Overwrite)
+            .await
+            .unwrap();
+        assert!(
+            !storage
+                .compare_and_set(&active("cas_expired", 2), &expired.data)
+                .await
+                .unwrap()
+        );
+    }
+
+    async fn scan_prefix<S: CacheStorage>(storage: &S) {
+        let mode = CacheSetMode::Overwrite;
+        for id in ["scan:3", "scan:1", "scan:2", "scan:4", "scan;", "scan"] {
+            storage.set(&active(id, 1), mode).await.unwrap();
+        }
+        storage.set(&expired("scan:0", 1), mode).await.unwrap();
+
+        let page = storage
+            .scan_prefix::<TestCo>("scan:", None, 3)
+            .await
+            .unwrap();
+        let ids = page
+            .entries
+            .iter()
+            .map(|e| e.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["scan:1", "scan:2", "scan:3"]);
+        assert!(page.entries.iter().all(|e| e.expires_at > 0));
+        let cursor = page.next_cursor.unwrap();
+
+        let page = storage
+            .scan_prefix::<TestCo>("scan:", Some(&cursor), 3)
+            .await
+            .unwrap();
+        let ids = page
+            .entries
+            .iter()
+            .map(|e| e.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, ["scan:4"]);
+        assert_eq!(page.next_cursor, None);
+
+        let page = storage
+            .scan_prefix::<TestCo>("scan:", None, 4)
+            .await
+            .unwrap();
+        assert_eq!(page.entries.len(), 4);
+        assert_eq!(page.next_cursor, None);
+
+        let page = storage
+            .scan_prefix::<OtherTestCo>("scan:", None, 10)
+            .await
+            .unwrap();
+        assert_eq!(page, CacheScanPage::default());
+    }
+
+    async fn get_many<S: CacheStorage>(storage: &S) {
+        let mode = CacheSetMode::Overwrite;
+        storage.set(&active("many:1", 1), mode).await.unwrap();
+        storage.set(&active("many:2", 2), mode).await.unwrap();
+        storage.set(&expired("many:3", 3), mode).await.unwrap();
+
+        let values = storage
+            .get_many::<TestCo>(&["many:2", "many:3", "many:4", "many:1"])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|cache| cache.map(|cache| cache.data.value))
+            .collect::<Vec<_>>();
+        assert_eq!(values, [Some(2), None, None, Some(1)]);
+        assert!(storage.get_many::<TestCo>(&[]).await.unwrap().is_empty());
+    }
+
+    async fn admin<S: CacheStorage>(storage: &S) {
+        let mode = CacheSetMode::Overwrite;
+        storage.remove_by_kind("other_test", "").await.unwrap();
+        for id in ["admin:1", "admin:2", "admin:3"] {
+            let other = OtherTestCo { id: id.to_owned() };
+            storage
+                .set(&other.with_ttl(std::time::Duration::from_secs(3600)), mode)
+                .await
+                .unwrap();
+        }
+
+        let stats = storage.stats().await.unwrap();
+        let other = stats.iter().find(|s| s.kind == "other_test").unwrap();
+        assert_eq!(other.total, 3);
+        // Redis 存储的数据包含创建时间等元数据，因此只检查下限
+        assert!(other.data_size >= 3 * r#"{"id":"admin:1"}"#.len() as u64);
+        assert!(stats.iter().any(|s| s.kind == "test"));
+
+        let cache = storage
+            .inspect("other_test", "admin:1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cache.kind, "other_test");
+        assert_eq!(cache.data, serde_json::json!({ "id": "admin:1" }));
+        assert!(
+            storage
+                .inspect("other_test", "admin:4")
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        assert_eq!(
+            storage
+                .remove_by_kind("other_test", "admin:1")
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(storage.remove_by_kind("other_test", "").await.unwrap(), 2);
+        let stats = storage.stats().await.unwrap();
+        assert!(stats.iter().all(|s| s.kind != "other_test"));
+
+        storage.set(&expired("purge", 1), mode).await.unwrap();
+        storage.purge_expired().await.unwrap();
+        let stats = storage.stats().await.unwrap();
+        assert!(stats.iter().all(|s| s.expired == 0));
+    }
+}
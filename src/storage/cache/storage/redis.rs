@@ -0,0 +1,485 @@
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+use redis::Script;
+use redis::aio::ConnectionManager;
+use serde::{Deserialize, Serialize};
+
+use crate::config::RedisCacheConfig;
+use crate::storage::cache::storage::{
+    CacheKindStats, CacheScanEntry, CacheScanPage, CacheSetMode, CacheStorage,
+};
+use crate::storage::cache::{Cache, CacheData};
+use crate::util::time::UnixTimestampSecs;
+
+/// 按前缀扫描时每次扫描的键数量
+const SCAN_COUNT: usize = 500;
+
+// 缓存值的格式固定为 `{"created_at":...,"data":...}`，脚本中直接按字符串截取数据部分
+
+/// 增加计数器的值
+/// KEYS[1]：缓存键，ARGV[1]：增加的值，ARGV[2]：过期时间，ARGV[3]：创建时间
+///
+/// Lua 的数字为双精度浮点数，计数器的值限制在 ±(2^53 - 1) 以内以保证精确计算，超出时报错。
+static INCR_BY_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+        local limit = 9007199254740991
+        local delta = tonumber(ARGV[1])
+        if math.abs(delta) > limit then
+            return redis.error_reply('counter overflow')
+        end
+        local raw = redis.call('GET', KEYS[1])
+        if not raw then
+            redis.call('SET', KEYS[1], '{"created_at":' .. ARGV[3] .. ',"data":' .. ARGV[1] .. '}', 'EXAT', ARGV[2])
+            return delta
+        end
+        local pos = string.find(raw, ',"data":', 1, true)
+        local text = pos and string.sub(raw, pos + 8, -2)
+        if not text or not string.match(text, '^-?%d+$') then
+            return redis.error_reply('cache data is not an integer')
+        end
+        local data = tonumber(text)
+        if math.abs(data) > limit or math.abs(data + delta) > limit then
+            return redis.error_reply('counter overflow')
+        end
+        data = data + delta
+        redis.call('SET', KEYS[1], string.sub(raw, 1, pos + 7) .. string.format('%d', data) .. '}', 'KEEPTTL')
+        return data
+        "#,
+    )
+});
+
+/// 比较并更新缓存
+/// KEYS[1]：缓存键，ARGV[1]：期望的缓存数据，ARGV[2]：新的缓存值，ARGV[3]：过期时间
+static COMPARE_AND_SET_SCRIPT: LazyLock<Script> = LazyLock::new(|| {
+    Script::new(
+        r#"
+        local raw = redis.call('GET', KEYS[1])
+        if not raw then
+            return 0
+        end
+        local pos = string.find(raw, ',"data":', 1, true)
+        if not pos or string.sub(raw, pos + 8, -2) ~= ARGV[1] then
+            return 0
+        end
+        redis.call('SET', KEYS[1], ARGV[2], 'EXAT', ARGV[3])
+        return 1
+        "#,
+    )
+});
+
+/// Redis 缓存存储器，兼容 Redis 协议的服务均可使用（需要支持 `EXPIRETIME` 命令）
+///
+/// 缓存键格式为 `{key_prefix}{kind}:{id}`，过期时间交由服务端维护。
+pub struct RedisCacheStorage {
+    conn: ConnectionManager,
+    key_prefix: String,
+}
+
+/// Redis 中存储的缓存值
+#[derive(Deserialize, Serialize)]
+struct RedisCacheValue<T> {
+    /// 创建时间
+    created_at: i64,
+    /// 缓存数据
+    data: T,
+}
+
+impl RedisCacheStorage {
+    pub async fn from_config(config: &RedisCacheConfig) -> anyhow::Result<Self> {
+        // 统计、清理缓存时会扫描前缀下的所有键，前缀为空时会扫描整个数据库
+        if config.key_prefix.is_empty() {
+            return Err(anyhow::anyhow!("Redis 缓存键前缀不能为空"));
+        }
+        let client = redis::Client::open(config.url.as_str())?;
+        Ok(Self {
+            conn: client.get_connection_manager().await?,
+            key_prefix: config.key_prefix.clone(),
+        })
+    }
+
+    fn key<T>(&self, id: &str) -> String
+    where
+        T: CacheData,
+    {
+        self.kind_key(T::kind(), id)
+    }
+
+    fn kind_key(&self, kind: &str, id: &str) -> String {
+        format!("{}{kind}:{id}", self.key_prefix)
+    }
+
+    /// 扫描指定前缀的所有键，结果可能包含重复的键
+    async fn scan_keys(&self, key_prefix: &str) -> anyhow::Result<Vec<String>> {
+        let pattern = format!("{}*", escape_glob(key_prefix));
+        let mut conn = self.conn.clone();
+        let mut cursor = 0u64;
+        let mut keys = vec![];
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(SCAN_COUNT)
+                .query_async(&mut conn)
+                .await?;
+            keys.extend(batch);
+            if next == 0 {
+                return Ok(keys);
+            }
+            cursor = next;
+        }
+    }
+
+    /// 获取键的过期时间，键不存在时返回 `None`
+    async fn expire_time(&self, key: &str) -> anyhow::Result<Option<i64>> {
+        let time: i64 = redis::cmd("EXPIRETIME")
+            .arg(key)
+            .query_async(&mut self.conn.clone())
+            .await?;
+        // -2 表示键不存在，-1 表示键没有设置过期时间（不会出现）
+        Ok((time >= 0).then(|| from_expire_time(time)))
+    }
+}
+
+impl CacheStorage for RedisCacheStorage {
+    async fn get<T>(&self, id: &str) -> anyhow::Result<Option<Cache<T>>>
+    where
+        T: CacheData,
+    {
+        let key = self.key::<T>(id);
+        let (value, time): (Option<String>, i64) = redis::pipe()
+            .atomic()
+            .get(&key)
+            .cmd("EXPIRETIME")
+            .arg(&key)
+            .query_async(&mut self.conn.clone())
+            .await?;
+        let Some(value) = value.filter(|_| time >= 0) else {
+            return Ok(None);
+        };
+        let value: RedisCacheValue<T> = serde_json::from_str(&value)?;
+        Ok(Some(Cache {
+            id: id.to_owned(),
+            created_at: value.created_at,
+            expires_at: from_expire_time(time),
+            kind: T::kind().to_owned(),
+            data: value.data,
+        }))
+    }
+
+    async fn get_expires_at<T>(&self, id: &str) -> anyhow::Result<Option<i64>>
+    where
+        T: CacheData,
+    {
+        self.expire_time(&self.key::<T>(id)).await
+    }
+
+    async fn set<T>(&self, cache: &Cache<T>, mode: CacheSetMode) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        let value = serde_json::to_string(&RedisCacheValue {
+            created_at: cache.created_at,
+            data: &cache.data,
+        })?;
+        let mut cmd = redis::cmd("SET");
+        cmd.arg(self.key::<T>(&cache.id))
+            .arg(value)
+            .arg("EXAT")
+            .arg(to_expire_time(cache.expires_at));
+        match mode {
+            CacheSetMode::Overwrite => {}
+            CacheSetMode::OnlyIfNotExists => {
+                cmd.arg("NX");
+            }
+            CacheSetMode::OnlyIfExists => {
+                cmd.arg("XX");
+            }
+        }
+        // 写入成功返回 OK，未满足写入条件时返回 nil
+        let reply: Option<()> = cmd.query_async(&mut self.conn.clone()).await?;
+        Ok(reply.is_some())
+    }
+
+    async fn set_expires_at<T>(&self, id: &str, expires_at: i64) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        let updated: i64 = redis::cmd("EXPIREAT")
+            .arg(self.key::<T>(id))
+            .arg(to_expire_time(expires_at))
+            .query_async(&mut self.conn.clone())
+            .await?;
+        Ok(updated == 1)
+    }
+
+    async fn exists<T>(&self, id: &str) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        let count: i64 = redis::cmd("EXISTS")
+            .arg(self.key::<T>(id))
+            .query_async(&mut self.conn.clone())
+            .await?;
+        Ok(count != 0)
+    }
+
+    async fn remove<T>(&self, id: &str) -> anyhow::Result<()>
+    where
+        T: CacheData,
+    {
+        redis::cmd("DEL")
+            .arg(self.key::<T>(id))
+            .query_async::<()>(&mut self.conn.clone())
+            .await?;
+        Ok(())
+    }
+
+    async fn batch_remove<T>(&self, id_prefix: &str) -> anyhow::Result<()>
+    where
+        T: CacheData,
+    {
+        self.remove_by_kind(T::kind(), id_prefix).await.map(|_| ())
+    }
+
+    async fn incr_by<T>(&self, id: &str, delta: i64, expires_at: i64) -> anyhow::Result<i64>
+    where
+        T: CacheData,
+    {
+        INCR_BY_SCRIPT
+            .key(self.key::<T>(id))
+            .arg(delta)
+            .arg(to_expire_time(expires_at))
+            .arg(UnixTimestampSecs::now().as_i64())
+            .invoke_async(&mut self.conn.clone())
+            .await
+            .map_err(From::from)
+    }
+
+    async fn compare_and_set<T>(&self, cache: &Cache<T>, expected: &T) -> anyhow::Result<bool>
+    where
+        T: CacheData,
+    {
+        let value = serde_json::to_string(&RedisCacheValue {
+            created_at: cache.created_at,
+            data: &cache.data,
+        })?;
+        let updated: i64 = COMPARE_AND_SET_SCRIPT
+            .key(self.key::<T>(&cache.id))
+            .arg(serde_json::to_string(expected)?)
+            .arg(value)
+            .arg(to_expire_time(cache.expires_at))
+            .invoke_async(&mut self.conn.clone())
+            .await?;
+        Ok(updated == 1)
+    }
+
+    /// Redis 的 `SCAN` 命令无法按顺序遍历，因此每次都会扫描该前缀下的所有键后排序分页
+    async fn scan_prefix<T>(
+        &self,
+        id_prefix: &str,
+        cursor: Option<&str>,
+        limit: u64,
+    ) -> anyhow::Result<CacheScanPage>
+    where
+        T: CacheData,
+    {
+        let key_prefix = self.key::<T>("");
+        let mut ids = self
+            .scan_keys(&self.key::<T>(id_prefix))
+            .await?
+            .into_iter()
+            .filter_map(|key| key.strip_prefix(&key_prefix).map(ToOwned::to_owned))
+            .filter(|id| cursor.is_none_or(|cursor| id.as_str() > cursor))
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+
+        // 先排除扫描后被删除或没有过期时间的键，再截取分页所需的条目，避免分页提前结束
+        let size = usize::try_from(limit.saturating_add(1)).unwrap_or(usize::MAX);
+        let mut entries = vec![];
+        for ids in ids.chunks(SCAN_COUNT) {
+            let mut pipe = redis::pipe();
+            for id in ids {
+                pipe.cmd("EXPIRETIME").arg(self.key::<T>(id));
+            }
+            let times: Vec<i64> = pipe.query_async(&mut self.conn.clone()).await?;
+            entries.extend(ids.iter().zip(times).filter(|(_, time)| *time >= 0).map(
+                |(id, time)| CacheScanEntry {
+                    id: id.clone(),
+                    expires_at: from_expire_time(time),
+                },
+            ));
+            if entries.len() >= size {
+                break;
+            }
+        }
+        entries.truncate(size);
+        Ok(CacheScanPage::from_entries(entries, limit))
+    }
+
+    async fn get_many<T>(&self, ids: &[&str]) -> anyhow::Result<Vec<Option<Cache<T>>>>
+    where
+        T: CacheData,
+    {
+        if ids.is_empty() {
+            return Ok(vec![]);
+        }
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for id in ids {
+            let key = self.key::<T>(id);
+            pipe.get(&key).cmd("EXPIRETIME").arg(&key);
+        }
+        let values: Vec<(Option<String>, i64)> = pipe.query_async(&mut self.conn.clone()).await?;
+        ids.iter()
+            .zip(values)
+            .map(|(id, (value, time))| {
+                let Some(value) = value.filter(|_| time >= 0) else {
+                    return Ok(None);
+                };
+                let value: RedisCacheValue<T> = serde_json::from_str(&value)?;
+                Ok(Some(Cache {
+                    id: (*id).to_owned(),
+                    created_at: value.created_at,
+                    expires_at: from_expire_time(time),
+                    kind: T::kind().to_owned(),
+                    data: value.data,
+                }))
+            })
+            .collect()
+    }
+
+    async fn stats(&self) -> anyhow::Result<Vec<CacheKindStats>> {
+        let mut keys = self.scan_keys(&self.key_prefix).await?;
+        keys.sort_unstable();
+        keys.dedup();
+
+        let mut stats = BTreeMap::<String, CacheKindStats>::new();
+        for keys in keys.chunks(SCAN_COUNT) {
+            let mut pipe = redis::pipe();
+            for key in keys {
+                pipe.cmd("STRLEN").arg(key);
+            }
+            let sizes: Vec<u64> = pipe.query_async(&mut self.conn.clone()).await?;
+            for (key, size) in keys.iter().zip(sizes) {
+                let Some((kind, _)) = key
+                    .strip_prefix(&self.key_prefix)
+                    .and_then(|key| key.split_once(':'))
+                else {
+                    continue;
+                };
+                // 键在扫描后被删除时长度为 0
+                if size == 0 {
+                    continue;
+                }
+                let stats = stats
+                    .entry(kind.to_owned())
+                    .or_insert_with(|| CacheKindStats {
+                        kind: kind.to_owned(),
+                        total: 0,
+                        expired: 0,
+                        data_size: 0,
+                    });
+                stats.total += 1;
+                stats.data_size += size;
+            }
+        }
+        Ok(stats.into_values().collect())
+    }
+
+    async fn inspect(
+        &self,
+        kind: &str,
+        id: &str,
+    ) -> anyhow::Result<Option<Cache<serde_json::Value>>> {
+        let key = self.kind_key(kind, id);
+        let (value, time): (Option<String>, i64) = redis::pipe()
+            .atomic()
+            .get(&key)
+            .cmd("EXPIRETIME")
+            .arg(&key)
+            .query_async(&mut self.conn.clone())
+            .await?;
+        let Some(value) = value.filter(|_| time >= 0) else {
+            return Ok(None);
+        };
+        let value: RedisCacheValue<serde_json::Value> = serde_json::from_str(&value)?;
+        Ok(Some(Cache {
+            id: id.to_owned(),
+            created_at: value.created_at,
+            expires_at: from_expire_time(time),
+            kind: kind.to_owned(),
+            data: value.data,
+        }))
+    }
+
+    async fn remove_by_kind(&self, kind: &str, id_prefix: &str) -> anyhow::Result<u64> {
+        let mut keys = self.scan_keys(&self.kind_key(kind, id_prefix)).await?;
+        keys.sort_unstable();
+        keys.dedup();
+        let mut rows = 0;
+        for keys in keys.chunks(SCAN_COUNT) {
+            let removed: u64 = redis::cmd("UNLINK")
+                .arg(keys)
+                .query_async(&mut self.conn.clone())
+                .await?;
+            rows += removed;
+        }
+        Ok(rows)
+    }
+
+    /// 过期的键由服务端自动清理，无需手动清理
+    async fn purge_expired(&self) -> anyhow::Result<u64> {
+        Ok(0)
+    }
+}
+
+/// 缓存在过期时间当秒内仍然有效，因此服务端的过期时间比缓存的过期时间晚一秒
+fn to_expire_time(expires_at: i64) -> i64 {
+    expires_at.saturating_add(1).max(1)
+}
+
+fn from_expire_time(time: i64) -> i64 {
+    time - 1
+}
+
+/// 转义 Redis 匹配模式中的特殊字符
+fn escape_glob(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// 需要一个 Redis 协议兼容服务才能执行，通过环境变量 TEST_REDIS_URL 配置连接地址，未配置时跳过测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn conformance() {
+        let Ok(url) = std::env::var("TEST_REDIS_URL") else {
+            return;
+        };
+        let config = RedisCacheConfig {
+            url,
+            key_prefix: format!("test:{}:", crate::util::uuid::v4()),
+        };
+        let storage = RedisCacheStorage::from_config(&config).await.unwrap();
+        super::super::conformance::run(&storage).await;
+    }
+
+    #[test]
+    fn escape_glob_pattern() {
+        assert_eq!(escape_glob("a*b?c[d]e\\f"), "a\\*b\\?c\\[d\\]e\\\\f");
+        assert_eq!(escape_glob("plain:id"), "plain:id");
+    }
+}
@@ -3,75 +3,98 @@ use std::fmt::Write;
 use sqlx::sqlite::SqliteArguments;
 use sqlx::{Arguments, AssertSqlSafe};
 
-use crate::model::po::article::{ArticlePo, SearchArticle};
+use crate::model::common::article::{ArticleSort, ArticleStatus};
+use crate::model::po::article::{
+    Adjacent, ArchiveMonth, ArticleCursor, ArticlePo, SearchArticle, SearchArticleItem,
+};
 use crate::storage::db::DbConn;
+use crate::storage::db::fts::FtsTable;
+use crate::util::fts::{self, SearchQuery};
 use crate::util::pagination::Offset;
 use crate::util::result::ResultExt;
 
+/// 创建文章并写入全文索引
 pub async fn create(article: &ArticlePo, db: &mut DbConn) -> anyhow::Result<()> {
-    sqlx::query(
-        "
-        INSERT INTO article (
-            `id`,
-            `title`,
-            `excerpt`,
-            `markdown_content`,
-            `plain_content`,
-            `password`,
-            `status`,
-            `created_at`,
-            `updated_at`,
-            `published_at`
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-        ",
-    )
-    .bind(&article.id)
-    .bind(&article.title)
-    .bind(&article.excerpt)
-    .bind(&article.markdown_content)
-    .bind(&article.plain_content)
-    .bind(&article.password)
-    .bind(&article.status)
-    .bind(&article.created_at)
-    .bind(&article.updated_at)
-    .bind(&article.published_at)
-    .execute(db)
+    crate::storage::db::transaction(db, async |tx| {
+        sqlx::query(
+            "
+            INSERT INTO article (
+                `id`,
+                `title`,
+                `excerpt`,
+                `markdown_content`,
+                `plain_content`,
+                `password`,
+                `status`,
+                `created_at`,
+                `updated_at`,
+                `published_at`,
+                `pinned_at`,
+                `featured`
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ",
+        )
+        .bind(&article.id)
+        .bind(&article.title)
+        .bind(&article.excerpt)
+        .bind(&article.markdown_content)
+        .bind(&article.plain_content)
+        .bind(&article.password)
+        .bind(&article.status)
+        .bind(&article.created_at)
+        .bind(&article.updated_at)
+        .bind(&article.published_at)
+        .bind(&article.pinned_at)
+        .bind(&article.featured)
+        .execute(&mut **tx)
+        .await?;
+        crate::storage::db::fts::index_article(article, tx).await
+    })
     .await
-    .map(|_| ())
-    .map_err(From::from)
 }
 
+/// 修改文章并更新全文索引
 pub async fn update(article: &ArticlePo, db: &mut DbConn) -> anyhow::Result<u64> {
-    sqlx::query(
-        "
-        UPDATE article SET
-            `title` = ?,
-            `excerpt` = ?,
-            `markdown_content` = ?,
-            `plain_content` = ?,
-            `password` = ?,
-            `status` = ?,
-            `created_at` = ?,
-            `updated_at` = ?,
-            `published_at` = ?
-        WHERE
-            `id` = ?
-        ",
-    )
-    .bind(&article.title)
-    .bind(&article.excerpt)
-    .bind(&article.markdown_content)
-    .bind(&article.plain_content)
-    .bind(&article.password)
-    .bind(&article.status)
-    .bind(&article.created_at)
-    .bind(&article.updated_at)
-    .bind(&article.published_at)
-    .bind(&article.id)
-    .execute(db)
+    crate::storage::db::transaction(db, async |tx| {
+        let rows = sqlx::query(
+            "
+            UPDATE article SET
+                `title` = ?,
+                `excerpt` = ?,
+                `markdown_content` = ?,
+                `plain_content` = ?,
+                `password` = ?,
+                `status` = ?,
+                `created_at` = ?,
+                `updated_at` = ?,
+                `published_at` = ?,
+                `pinned_at` = ?,
+                `featured` = ?
+            WHERE
+                `id` = ?
+            ",
+        )
+        .bind(&article.title)
+        .bind(&article.excerpt)
+        .bind(&article.markdown_content)
+        .bind(&article.plain_content)
+        .bind(&article.password)
+        .bind(&article.status)
+        .bind(&article.created_at)
+        .bind(&article.updated_at)
+        .bind(&article.published_at)
+        .bind(&article.pinned_at)
+        .bind(&article.featured)
+        .bind(&article.id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+        if rows > 0 {
+            crate::storage::db::fts::index_article(article, tx).await?;
+        }
+        Ok(rows)
+    })
     .await
-    .map(|res| res.rows_affected())
-    .map_err(From::from)
 }
 
 pub async fn remove(id: &str, db: &mut DbConn) -> anyhow::Result<u64> {
@@ -114,44 +137,163 @@ pub async fn find(id: &str, db: &mut DbConn) -> anyhow::Result<Option<ArticlePo>
         .map_err(From::from)
 }
 
-pub async fn search(
-    params: &SearchArticle<'_>,
-    offset: Offset,
+/// 按发布时间查找相邻的文章，未发布过的文章不参与排序，发布时间相同时按文章ID排序
+pub async fn find_adjacent(
+    article: &ArticlePo,
+    adjacent: Adjacent,
+    status: Option<ArticleStatus>,
     db: &mut DbConn,
-) -> anyhow::Result<Vec<ArticlePo>> {
+) -> anyhow::Result<Option<ArticlePo>> {
+    let Some(published_at) = article.published_at else {
+        return Ok(None);
+    };
+
     let mut sql = String::new();
     let mut sql_params = SqliteArguments::default();
 
+    let (cmp, order) = match adjacent {
+        Adjacent::Previous => ("<", "DESC"),
+        Adjacent::Next => (">", "ASC"),
+    };
     writeln!(&mut sql, "SELECT * FROM article")?;
-
-    let where_conditions = search_where_conditions(params, &mut sql_params)?;
-    if !where_conditions.is_empty() {
-        writeln!(&mut sql, "WHERE {}", where_conditions.join(" AND "))?;
+    writeln!(
+        &mut sql,
+        "WHERE (published_at {cmp} ? OR (published_at = ? AND id {cmp} ?))"
+    )?;
+    sql_params.add(published_at).anyhow()?;
+    sql_params.add(published_at).anyhow()?;
+    sql_params.add(&article.id).anyhow()?;
+    if let Some(status) = status {
+        writeln!(&mut sql, "AND status = ?")?;
+        sql_params.add(status).anyhow()?;
     }
+    writeln!(
+        &mut sql,
+        "ORDER BY published_at {order}, id {order} LIMIT 1"
+    )?;
+
+    sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+        .fetch_optional(db)
+        .await
+        .map_err(From::from)
+}
+
+/// 列出精选的已发布文章，按发布时间从新到旧排序
+pub async fn list_featured(limit: u64, db: &mut DbConn) -> anyhow::Result<Vec<ArticlePo>> {
+    sqlx::query_as(
+        "
+        SELECT * FROM article
+        WHERE featured = 1 AND status = ?
+        ORDER BY published_at DESC, id
+        LIMIT ?
+        ",
+    )
+    .bind(ArticleStatus::Published)
+    .bind(i64::try_from(limit)?)
+    .fetch_all(db)
+    .await
+    .map_err(From::from)
+}
 
-    sqlx::query_as_with(
-        AssertSqlSafe(format!(
-            "{sql} ORDER BY published_at DESC NULLS FIRST, updated_at DESC LIMIT ? OFFSET ?"
-        )),
-        sql_params,
+/// 按发布月份（UTC）统计已发布文章数量，结果按月份从新到旧排序
+pub async fn list_archive_months(db: &mut DbConn) -> anyhow::Result<Vec<ArchiveMonth>> {
+    sqlx::query_as(
+        "
+        SELECT
+            CAST(strftime('%Y', published_at, 'unixepoch') AS INTEGER) AS year,
+            CAST(strftime('%m', published_at, 'unixepoch') AS INTEGER) AS month,
+            COUNT(*) AS count
+        FROM article
+        WHERE status = ? AND published_at IS NOT NULL
+        GROUP BY year, month
+        ORDER BY year DESC, month DESC
+        ",
     )
-    .bind(i64::try_from(offset.size)?)
-    .bind(i64::try_from(offset.offset)?)
+    .bind(ArticleStatus::Published)
     .fetch_all(db)
     .await
     .map_err(From::from)
 }
 
-pub async fn search_count(params: &SearchArticle<'_>, db: &mut DbConn) -> anyhow::Result<u64> {
+/// 通过全文检索列出与关键词相关的已发布文章，任一关键词匹配即可，结果按相关度排序
+pub async fn list_related(
+    article_id: &str,
+    keywords: &[&str],
+    limit: u64,
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<ArticlePo>> {
+    if keywords.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let Some(condition) = crate::storage::db::fts::match_any(FtsTable::Article, keywords) else {
+        return Ok(vec![]);
+    };
+
     let mut sql = String::new();
     let mut sql_params = SqliteArguments::default();
 
-    writeln!(&mut sql, "SELECT COUNT(*) FROM article")?;
+    writeln!(sql, "SELECT a.* FROM article_fts")?;
+    writeln!(sql, "INNER JOIN article a ON a.rowid = article_fts.rowid")?;
+    writeln!(sql, "WHERE {}", condition.sql)?;
+    writeln!(sql, "AND a.id != ? AND a.status = ? AND a.password IS NULL")?;
+    writeln!(sql, "ORDER BY article_fts.rank LIMIT ?")?;
 
-    let where_conditions = search_where_conditions(params, &mut sql_params)?;
-    if !where_conditions.is_empty() {
-        writeln!(&mut sql, "WHERE {}", where_conditions.join(" AND "))?;
+    condition.add_params(&mut sql_params)?;
+    sql_params.add(article_id).anyhow()?;
+    sql_params.add(ArticleStatus::Published).anyhow()?;
+    sql_params.add(i64::try_from(limit)?).anyhow()?;
+
+    sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+        .fetch_all(db)
+        .await
+        .map_err(From::from)
+}
+
+pub async fn search(
+    params: &SearchArticle,
+    offset: Offset,
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<SearchArticleItem>> {
+    let mut sql = String::new();
+    let mut sql_params = SqliteArguments::default();
+
+    write_search_matched(&mut sql, params, true, &mut sql_params)?;
+    writeln!(
+        &mut sql,
+        "SELECT m.* FROM matched m LEFT JOIN article_stats s ON s.article_id = m.id"
+    )?;
+    if let Some(cursor) = &params.cursor {
+        let condition = cursor_condition(cursor, &mut sql_params)?;
+        writeln!(&mut sql, "WHERE {condition}")?;
     }
+    // 置顶文章按置顶时间从新到旧排在最前
+    let pinned_first = if params.pinned_first {
+        "m.pinned_at IS NULL, m.pinned_at DESC, "
+    } else {
+        ""
+    };
+    writeln!(
+        &mut sql,
+        "ORDER BY {pinned_first}{}",
+        search_order_by(params.sort)
+    )?;
+    writeln!(&mut sql, "LIMIT ? OFFSET ?")?;
+
+    sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+        .bind(i64::try_from(offset.size)?)
+        .bind(i64::try_from(offset.offset)?)
+        .fetch_all(db)
+        .await
+        .map_err(From::from)
+}
+
+pub async fn search_count(params: &SearchArticle, db: &mut DbConn) -> anyhow::Result<u64> {
+    let mut sql = String::new();
+    let mut sql_params = SqliteArguments::default();
+
+    write_search_matched(&mut sql, params, false, &mut sql_params)?;
+    writeln!(&mut sql, "SELECT COUNT(*) FROM matched")?;
 
     sqlx::query_scalar_with(AssertSqlSafe(sql), sql_params)
         .fetch_one(db)
@@ -159,23 +301,173 @@ pub async fn search_count(params: &SearchArticle<'_>, db: &mut DbConn) -> anyhow
         .map_err(From::from)
 }
 
+/// 生成名为 `matched` 的公共表表达式，包含所有符合搜索条件的文章
+///
+/// 全文搜索同时匹配文章与文章附件的文本，并计算相关度（BM25），排序与分页由外层查询完成。`highlight`
+/// 为真时同时生成高亮标题与正文片段，高亮标记在输出前转换为 HTML。
+fn write_search_matched(
+    sql: &mut String,
+    params: &SearchArticle,
+    highlight: bool,
+    sql_params: &mut SqliteArguments,
+) -> anyhow::Result<()> {
+    let config = &crate::config::get().article;
+
+    writeln!(sql, "WITH matched AS (")?;
+    if let Some(query) = search_includes(params) {
+        writeln!(sql, "SELECT article.*, MIN(f.score) AS score,")?;
+        writeln!(
+            sql,
+            "MAX(f.title_highlight) AS title_highlight, MAX(f.snippet) AS snippet FROM ("
+        )?;
+        // 匹配文章，只有通过全文索引匹配时才能计算相关度与高亮
+        let condition = crate::storage::db::fts::match_all(FtsTable::Article, query.includes())
+            .ok_or_else(|| anyhow::anyhow!("缺少需要匹配的搜索词"))?;
+        if condition.ranked {
+            writeln!(
+                sql,
+                "SELECT rowid, bm25(article_fts, 0.0, ?, ?, ?) AS score,"
+            )?;
+            sql_params.add(config.search_weights.title).anyhow()?;
+            sql_params.add(config.search_weights.excerpt).anyhow()?;
+            sql_params.add(config.search_weights.content).anyhow()?;
+        } else {
+            writeln!(sql, "SELECT rowid, 0.0 AS score,")?;
+        }
+        if highlight && condition.ranked {
+            writeln!(sql, "highlight(article_fts, 1, ?, ?) AS title_highlight,")?;
+            writeln!(
+                sql,
+                "snippet(article_fts, 3, ?, ?, ?, {}) AS snippet",
+                fts::SNIPPET_TOKENS
+            )?;
+            sql_params.add(fts::HIGHLIGHT_START).anyhow()?;
+            sql_params.add(fts::HIGHLIGHT_END).anyhow()?;
+            sql_params.add(fts::HIGHLIGHT_START).anyhow()?;
+            sql_params.add(fts::HIGHLIGHT_END).anyhow()?;
+            sql_params.add(fts::SNIPPET_ELLIPSIS).anyhow()?;
+        } else {
+            writeln!(sql, "NULL AS title_highlight, NULL AS snippet")?;
+        }
+        writeln!(sql, "FROM article_fts WHERE {}", condition.sql)?;
+        condition.add_params(sql_params)?;
+        // 匹配文章附件
+        writeln!(sql, "UNION ALL")?;
+        let condition =
+            crate::storage::db::fts::match_all(FtsTable::ArticleAttachment, query.includes())
+                .ok_or_else(|| anyhow::anyhow!("缺少需要匹配的搜索词"))?;
+        if condition.ranked {
+            writeln!(
+                sql,
+                "SELECT a.rowid, bm25(article_attachment_fts, 0.0, 0.0, ?, ?) AS score,"
+            )?;
+            sql_params.add(config.search_weights.attachment).anyhow()?;
+            sql_params.add(config.search_weights.attachment).anyhow()?;
+        } else {
+            writeln!(sql, "SELECT a.rowid, 0.0 AS score,")?;
+        }
+        writeln!(sql, "NULL AS title_highlight, NULL AS snippet")?;
+        writeln!(
+            sql,
+            "FROM article_attachment_fts INNER JOIN article a ON a.id = article_attachment_fts.article_id"
+        )?;
+        writeln!(sql, "WHERE {}", condition.sql)?;
+        condition.add_params(sql_params)?;
+        writeln!(sql, ") f INNER JOIN article ON article.rowid = f.rowid")?;
+    } else {
+        writeln!(
+            sql,
+            "SELECT article.*, NULL AS score, NULL AS title_highlight, NULL AS snippet FROM article"
+        )?;
+    }
+
+    let where_conditions = search_where_conditions(params, sql_params)?;
+    if !where_conditions.is_empty() {
+        writeln!(sql, "WHERE {}", where_conditions.join(" AND "))?;
+    }
+    if search_includes(params).is_some() {
+        writeln!(sql, "GROUP BY article.rowid")?;
+    }
+    writeln!(sql, ")")?;
+    Ok(())
+}
+
+fn search_order_by(sort: ArticleSort) -> &'static str {
+    match sort {
+        // BM25 得分越小表示越相关
+        ArticleSort::Relevance => {
+            "m.score, m.published_at DESC NULLS FIRST, m.updated_at DESC, m.id"
+        }
+        ArticleSort::Newest => "m.published_at DESC NULLS FIRST, m.updated_at DESC, m.id",
+        ArticleSort::Oldest => "m.published_at ASC NULLS LAST, m.updated_at ASC, m.id",
+        ArticleSort::MostViewed => {
+            "COALESCE(s.pv, 0) DESC, m.published_at DESC NULLS FIRST, m.updated_at DESC, m.id"
+        }
+    }
+}
+
+/// 游标分页的过滤条件，列出按 [`search_order_by`] 排序时位于游标之后的文章
+fn cursor_condition(
+    cursor: &ArticleCursor,
+    sql_params: &mut SqliteArguments,
+) -> anyhow::Result<String> {
+    let cmp = match cursor.sort {
+        ArticleSort::Newest => "<",
+        ArticleSort::Oldest => ">",
+        sort => return Err(anyhow::anyhow!("排序方式不支持游标分页：{sort:?}")),
+    };
+    // 发布时间相同时按修改时间排序，修改时间相同时按文章ID升序排序
+    let tie = format!("(m.updated_at {cmp} ? OR (m.updated_at = ? AND m.id > ?))");
+    let condition = match (cursor.sort, cursor.published_at) {
+        // 未发布的文章排在最前
+        (ArticleSort::Newest, None) => format!("(m.published_at IS NOT NULL OR {tie})"),
+        (ArticleSort::Newest, Some(_)) => {
+            format!("(m.published_at {cmp} ? OR (m.published_at = ? AND {tie}))")
+        }
+        // 未发布的文章排在最后
+        (_, None) => format!("(m.published_at IS NULL AND {tie})"),
+        (_, Some(_)) => format!(
+            "(m.published_at IS NULL OR m.published_at {cmp} ? OR (m.published_at = ? AND {tie}))"
+        ),
+    };
+    if let Some(published_at) = cursor.published_at {
+        sql_params.add(published_at).anyhow()?;
+        sql_params.add(published_at).anyhow()?;
+    }
+    sql_params.add(cursor.updated_at).anyhow()?;
+    sql_params.add(cursor.updated_at).anyhow()?;
+    sql_params.add(cursor.id.clone()).anyhow()?;
+    Ok(condition)
+}
+
+/// 包含需要匹配的搜索词的搜索语句
+fn search_includes(params: &SearchArticle) -> Option<&SearchQuery> {
+    params
+        .query
+        .as_ref()
+        .filter(|query| query.includes().next().is_some())
+}
+
+/// 全文搜索以外的搜索条件
 fn search_where_conditions(
-    params: &SearchArticle<'_>,
+    params: &SearchArticle,
     sql_params: &mut SqliteArguments,
 ) -> anyhow::Result<Vec<String>> {
     let mut conditions = vec![];
 
-    if let Some(full_text) = params
-        .full_text
-        .as_deref()
-        .map(|v| v.trim())
-        .filter(|v| !v.is_empty())
-    {
-        sql_params.add(full_text).anyhow()?;
-        conditions.push(format!(
-            "id IN (SELECT id FROM article_fts WHERE article_fts MATCH simple_query(?) LIMIT {})",
-            crate::config::get().article.full_text_search_limit
-        ));
+    // 排除文章内容匹配任一排除词的文章
+    if let Some(query) = &params.query {
+        for term in query.excludes() {
+            let Some(condition) = crate::storage::db::fts::match_all(FtsTable::Article, [term])
+            else {
+                continue;
+            };
+            conditions.push(format!(
+                "article.rowid NOT IN (SELECT rowid FROM article_fts WHERE {})",
+                condition.sql
+            ));
+            condition.add_params(sql_params)?;
+        }
     }
     if let Some(status) = &params.status {
         sql_params.add(status).anyhow()?;
@@ -199,3 +491,142 @@ fn search_where_conditions(
 
     Ok(conditions)
 }
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+    use crate::config::FtsTokenizer;
+
+    async fn setup() -> crate::storage::db::DbPoolConn {
+        // 内存数据库的每个连接相互独立，因此只使用一个连接
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let mut db = pool.acquire().await.unwrap();
+        // 测试环境没有加载 libsimple 扩展
+        let sql = include_str!("../../../sqlite/migrations/article.sql").replace(
+            super::super::fts_tokenize_option(FtsTokenizer::Simple),
+            super::super::fts_tokenize_option(FtsTokenizer::Unicode61),
+        );
+        sqlx::raw_sql(AssertSqlSafe(sql))
+            .execute(&mut *db)
+            .await
+            .unwrap();
+
+        for (id, published_at, updated_at) in [
+            ("a", Some(100), 10),
+            ("b", Some(100), 10),
+            ("c", Some(100), 20),
+            ("d", Some(200), 5),
+            ("e", None, 30),
+            ("f", None, 30),
+            ("g", None, 10),
+            ("h", Some(50), 10),
+            ("i", Some(50), 40),
+        ] {
+            sqlx::query(
+                "
+                INSERT INTO article (
+                    id, title, excerpt, markdown_content, plain_content,
+                    status, created_at, updated_at, published_at
+                ) VALUES (?, '', '', '', '', ?, 0, ?, ?)
+                ",
+            )
+            .bind(id)
+            .bind(ArticleStatus::Published)
+            .bind(updated_at)
+            .bind(published_at)
+            .execute(&mut *db)
+            .await
+            .unwrap();
+        }
+        db
+    }
+
+    /// 按排序方式列出位于游标之后的文章ID
+    async fn list_after(
+        sort: ArticleSort,
+        cursor: Option<&ArticleCursor>,
+        limit: i64,
+        db: &mut DbConn,
+    ) -> Vec<ArticlePo> {
+        let mut sql = String::new();
+        let mut sql_params = SqliteArguments::default();
+        writeln!(sql, "SELECT m.* FROM article m").unwrap();
+        if let Some(cursor) = cursor {
+            let condition = cursor_condition(cursor, &mut sql_params).unwrap();
+            writeln!(sql, "WHERE {condition}").unwrap();
+        }
+        writeln!(sql, "ORDER BY {} LIMIT ?", search_order_by(sort)).unwrap();
+        sql_params.add(limit).unwrap();
+        sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+            .fetch_all(db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn cursor_pages_match_offset_pages() {
+        let mut db = setup().await;
+        for sort in [ArticleSort::Newest, ArticleSort::Oldest] {
+            let expected = ordered_ids(sort, &mut db).await;
+            assert_eq!(expected.len(), 9);
+
+            for size in 1..=4 {
+                let mut ids = vec![];
+                let mut cursor = None;
+                loop {
+                    let page = list_after(sort, cursor.as_ref(), size, &mut db).await;
+                    let Some(last) = page.last() else {
+                        break;
+                    };
+                    cursor = Some(ArticleCursor {
+                        sort,
+                        published_at: last.published_at,
+                        updated_at: last.updated_at,
+                        id: last.id.clone(),
+                    });
+                    ids.extend(page.into_iter().map(|article| article.id));
+                }
+                assert_eq!(ids, expected, "sort: {sort:?}, size: {size}");
+            }
+        }
+    }
+
+    async fn ordered_ids(sort: ArticleSort, db: &mut DbConn) -> Vec<String> {
+        list_after(sort, None, i64::MAX, db)
+            .await
+            .into_iter()
+            .map(|article| article.id)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn cursor_sort_order() {
+        let mut db = setup().await;
+        // 未发布的文章在最新排序中排在最前，在最早排序中排在最后
+        assert_eq!(
+            ordered_ids(ArticleSort::Newest, &mut db).await,
+            ["e", "f", "g", "d", "c", "a", "b", "i", "h"]
+        );
+        assert_eq!(
+            ordered_ids(ArticleSort::Oldest, &mut db).await,
+            ["h", "i", "a", "b", "c", "d", "g", "e", "f"]
+        );
+    }
+
+    #[test]
+    fn cursor_unsupported_sort() {
+        let cursor = ArticleCursor {
+            sort: ArticleSort::Relevance,
+            published_at: None,
+            updated_at: 0,
+            id: "a".to_owned(),
+        };
+        assert!(cursor_condition(&cursor, &mut SqliteArguments::default()).is_err());
+    }
+}
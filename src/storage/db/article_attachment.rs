@@ -1,5 +1,13 @@
-use crate::model::po::article_attachment::ArticleAttachmentPo;
+use std::fmt::Write;
+
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Arguments, AssertSqlSafe};
+
+use crate::model::po::article_attachment::{ArticleAttachmentPo, MatchedAttachment};
 use crate::storage::db::DbConn;
+use crate::storage::db::fts::FtsTable;
+use crate::util::fts::{self, SearchQuery};
+use crate::util::result::ResultExt;
 
 pub async fn create(attachment: &ArticleAttachmentPo, db: &mut DbConn) -> anyhow::Result<()> {
     sqlx::query(
@@ -58,3 +66,88 @@ pub async fn find(id: &str, db: &mut DbConn) -> anyhow::Result<Option<ArticleAtt
         .await
         .map_err(From::from)
 }
+
+/// 列出尚未写入全文索引的附件
+pub async fn list_unindexed(db: &mut DbConn) -> anyhow::Result<Vec<ArticleAttachmentPo>> {
+    sqlx::query_as(
+        "
+        SELECT * FROM article_attachment
+        WHERE rowid NOT IN (SELECT rowid FROM article_attachment_fts)
+        ORDER BY created_at, id
+        ",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(From::from)
+}
+
+/// 将附件名与附件文本写入全文索引，附件删除时索引会通过触发器一同删除
+pub async fn index_text(
+    attachment_id: &str,
+    name: &str,
+    text: &str,
+    db: &mut DbConn,
+) -> anyhow::Result<u64> {
+    sqlx::query(
+        "
+        INSERT INTO article_attachment_fts (
+            `rowid`,
+            `attachment_id`,
+            `article_id`,
+            `title`,
+            `plain_content`
+        )
+        SELECT `rowid`, `id`, `article_id`, ?, ? FROM article_attachment WHERE `id` = ?
+        ",
+    )
+    .bind(crate::storage::db::fts::index_text(name))
+    .bind(crate::storage::db::fts::index_text(text))
+    .bind(attachment_id)
+    .execute(db)
+    .await
+    .map(|res| res.rows_affected())
+    .map_err(From::from)
+}
+
+/// 列出指定文章中与搜索语句匹配的附件，按相关度排序
+pub async fn list_matched(
+    query: &SearchQuery,
+    article_ids: &[&str],
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<MatchedAttachment>> {
+    if article_ids.is_empty() {
+        return Ok(vec![]);
+    }
+    let Some(condition) =
+        crate::storage::db::fts::match_all(FtsTable::ArticleAttachment, query.includes())
+    else {
+        return Ok(vec![]);
+    };
+
+    let mut sql = String::new();
+    let mut sql_params = SqliteArguments::default();
+
+    // 使用 unicode61 分词器时附件名中插入了分隔符，输出前移除
+    writeln!(
+        &mut sql,
+        "SELECT attachment_id, article_id, replace(title, ?, '') AS name FROM article_attachment_fts"
+    )?;
+    sql_params.add(fts::TOKEN_SEPARATOR.to_string()).anyhow()?;
+    writeln!(&mut sql, "WHERE {}", condition.sql)?;
+    writeln!(
+        &mut sql,
+        "AND article_id IN (SELECT value FROM json_each(?))"
+    )?;
+    if condition.ranked {
+        writeln!(&mut sql, "ORDER BY rank")?;
+    }
+    condition.add_params(&mut sql_params)?;
+    sql_params
+        .add(serde_json::to_string(article_ids)?)
+        .anyhow()?;
+
+    sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+        .fetch_all(db)
+        .await
+        .map_err(From::from)
+}
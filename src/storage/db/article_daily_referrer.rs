@@ -0,0 +1,90 @@
+use std::fmt::Write;
+
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Arguments, AssertSqlSafe};
+
+use crate::model::po::article_daily_referrer::ReferrerVisits;
+use crate::storage::db::DbConn;
+use crate::util::result::ResultExt;
+
+pub async fn increment(
+    article_id: &str,
+    date: &str,
+    domain: &str,
+    visits_add: u64,
+    db: &mut DbConn,
+) -> anyhow::Result<u64> {
+    sqlx::query(
+        "
+        INSERT INTO article_daily_referrer (
+            `article_id`,
+            `date`,
+            `domain`,
+            `visits`
+        ) VALUES (?, ?, ?, ?)
+        ON CONFLICT (`article_id`, `date`, `domain`) DO UPDATE SET
+            `visits` = `visits` + excluded.`visits`
+        ",
+    )
+    .bind(article_id)
+    .bind(date)
+    .bind(domain)
+    .bind(&i64::try_from(visits_add)?)
+    .execute(db)
+    .await
+    .map(|r| r.rows_affected())
+    .map_err(Into::into)
+}
+
+pub async fn remove_by_article_id(article_id: &str, db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query("DELETE FROM article_daily_referrer WHERE article_id = ?")
+        .bind(article_id)
+        .execute(db)
+        .await
+        .map(|res| res.rows_affected())
+        .map_err(From::from)
+}
+
+/// 删除指定日期之前的统计数据
+pub async fn remove_before(date: &str, db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query("DELETE FROM article_daily_referrer WHERE date < ?")
+        .bind(date)
+        .execute(db)
+        .await
+        .map(|res| res.rows_affected())
+        .map_err(From::from)
+}
+
+/// 按访问次数降序列出来源域名，未指定文章时汇总所有文章
+pub async fn list_top_domains(
+    article_id: Option<&str>,
+    start_date: &str,
+    end_date: &str,
+    limit: u64,
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<ReferrerVisits>> {
+    let mut sql = String::new();
+    let mut sql_params = SqliteArguments::default();
+
+    writeln!(
+        &mut sql,
+        "SELECT `domain`, SUM(`visits`) AS `visits` FROM article_daily_referrer"
+    )?;
+    writeln!(&mut sql, "WHERE `date` >= ? AND `date` <= ?")?;
+    sql_params.add(start_date).anyhow()?;
+    sql_params.add(end_date).anyhow()?;
+    if let Some(article_id) = article_id {
+        writeln!(&mut sql, "AND `article_id` = ?")?;
+        sql_params.add(article_id).anyhow()?;
+    }
+    writeln!(
+        &mut sql,
+        "GROUP BY `domain` ORDER BY `visits` DESC, `domain` LIMIT ?"
+    )?;
+    sql_params.add(i64::try_from(limit)?).anyhow()?;
+
+    sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+        .fetch_all(db)
+        .await
+        .map_err(From::from)
+}
@@ -0,0 +1,146 @@
+use std::fmt::Write;
+
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Arguments, AssertSqlSafe};
+
+use crate::model::common::article::ArticleStatus;
+use crate::model::po::article_daily_stats::{ArticleVisits, DailyVisits};
+use crate::storage::db::DbConn;
+use crate::util::result::ResultExt;
+
+pub async fn increment(
+    article_id: &str,
+    date: &str,
+    pv_add: u64,
+    uv_add: u64,
+    db: &mut DbConn,
+) -> anyhow::Result<u64> {
+    sqlx::query(
+        "
+        INSERT INTO article_daily_stats (
+            `article_id`,
+            `date`,
+            `pv`,
+            `uv`
+        ) VALUES (?, ?, ?, ?)
+        ON CONFLICT (`article_id`, `date`) DO UPDATE SET
+            `pv` = `pv` + excluded.`pv`,
+            `uv` = `uv` + excluded.`uv`
+        ",
+    )
+    .bind(article_id)
+    .bind(date)
+    .bind(&i64::try_from(pv_add)?)
+    .bind(&i64::try_from(uv_add)?)
+    .execute(db)
+    .await
+    .map(|r| r.rows_affected())
+    .map_err(Into::into)
+}
+
+pub async fn remove_by_article_id(article_id: &str, db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query("DELETE FROM article_daily_stats WHERE article_id = ?")
+        .bind(article_id)
+        .execute(db)
+        .await
+        .map(|res| res.rows_affected())
+        .map_err(From::from)
+}
+
+/// 删除指定日期之前的统计数据
+pub async fn remove_before(date: &str, db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query("DELETE FROM article_daily_stats WHERE date < ?")
+        .bind(date)
+        .execute(db)
+        .await
+        .map(|res| res.rows_affected())
+        .map_err(From::from)
+}
+
+/// 按日期汇总访问量，未指定文章时汇总所有文章
+pub async fn list_daily(
+    article_id: Option<&str>,
+    start_date: &str,
+    end_date: &str,
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<DailyVisits>> {
+    let mut sql = String::new();
+    let mut sql_params = SqliteArguments::default();
+
+    writeln!(
+        &mut sql,
+        "SELECT `date`, SUM(`pv`) AS `pv`, SUM(`uv`) AS `uv` FROM article_daily_stats"
+    )?;
+    writeln!(&mut sql, "WHERE `date` >= ? AND `date` <= ?")?;
+    sql_params.add(start_date).anyhow()?;
+    sql_params.add(end_date).anyhow()?;
+    if let Some(article_id) = article_id {
+        writeln!(&mut sql, "AND `article_id` = ?")?;
+        sql_params.add(article_id).anyhow()?;
+    }
+    writeln!(&mut sql, "GROUP BY `date` ORDER BY `date`")?;
+
+    sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+        .fetch_all(db)
+        .await
+        .map_err(From::from)
+}
+
+/// 按页面访问量降序列出访问量最高的文章
+pub async fn list_top_articles(
+    start_date: &str,
+    end_date: &str,
+    limit: u64,
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<ArticleVisits>> {
+    sqlx::query_as(
+        "
+        SELECT
+            s.`article_id`,
+            a.`title`,
+            SUM(s.`pv`) AS `pv`,
+            SUM(s.`uv`) AS `uv`
+        FROM article_daily_stats s
+        INNER JOIN article a ON a.`id` = s.`article_id`
+        WHERE s.`date` >= ? AND s.`date` <= ?
+        GROUP BY s.`article_id`
+        ORDER BY `pv` DESC, s.`article_id`
+        LIMIT ?
+        ",
+    )
+    .bind(start_date)
+    .bind(end_date)
+    .bind(i64::try_from(limit)?)
+    .fetch_all(db)
+    .await
+    .map_err(From::from)
+}
+
+/// 列出指定日期以来访问量最高的已发布文章，不包含需要密码访问的文章
+pub async fn list_popular(
+    start_date: &str,
+    limit: u64,
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<ArticleVisits>> {
+    sqlx::query_as(
+        "
+        SELECT
+            s.`article_id`,
+            a.`title`,
+            SUM(s.`pv`) AS `pv`,
+            SUM(s.`uv`) AS `uv`
+        FROM article_daily_stats s
+        INNER JOIN article a ON a.`id` = s.`article_id`
+        WHERE s.`date` >= ? AND a.`status` = ? AND a.`password` IS NULL
+        GROUP BY s.`article_id`
+        ORDER BY `pv` DESC, s.`article_id`
+        LIMIT ?
+        ",
+    )
+    .bind(start_date)
+    .bind(ArticleStatus::Published)
+    .bind(i64::try_from(limit)?)
+    .fetch_all(db)
+    .await
+    .map_err(From::from)
+}
@@ -1,5 +1,11 @@
+use std::fmt::Write;
+
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Arguments, AssertSqlSafe};
+
 use crate::model::po::cache::CachePo;
 use crate::storage::db::DbConn;
+use crate::util::result::ResultExt;
 use crate::util::time::UnixTimestampSecs;
 
 pub async fn create(cache: &CachePo, db: &mut DbConn) -> anyhow::Result<()> {
@@ -121,6 +127,77 @@ pub async fn create_or_update(cache: &CachePo, db: &mut DbConn) -> anyhow::Resul
     .map_err(From::from)
 }
 
+pub async fn increment_or_create(
+    kind: &str,
+    id: &str,
+    delta: i64,
+    created_at: i64,
+    expires_at: i64,
+    db: &mut DbConn,
+) -> anyhow::Result<i64> {
+    // 已过期的计数器视为不存在，重新从 0 开始计数；未过期的数据不是整数或增加后溢出时不更新，
+    // 此时不返回任何行（整数溢出时 SQLite 会将结果转换为浮点数）
+    let value: Option<i64> = sqlx::query_scalar(
+        "
+        INSERT INTO cache (
+            `id`,
+            `kind`,
+            `data`,
+            `created_at`,
+            `expires_at`
+        ) VALUES (?1, ?2, ?3, ?4, ?5)
+        ON CONFLICT (`kind`, `id`) DO UPDATE SET
+            `data` = CASE WHEN cache.expires_at >= ?6
+                THEN CAST(cache.data AS INTEGER) + ?3 ELSE excluded.data END,
+            `created_at` = CASE WHEN cache.expires_at >= ?6
+                THEN cache.created_at ELSE excluded.created_at END,
+            `expires_at` = CASE WHEN cache.expires_at >= ?6
+                THEN cache.expires_at ELSE excluded.expires_at END
+        WHERE cache.expires_at < ?6
+            OR (CAST(CAST(cache.data AS INTEGER) AS TEXT) = cache.data
+                AND typeof(CAST(cache.data AS INTEGER) + ?3) = 'integer')
+        RETURNING CAST(`data` AS INTEGER)
+        ",
+    )
+    .bind(id)
+    .bind(kind)
+    .bind(delta)
+    .bind(created_at)
+    .bind(expires_at)
+    .bind(UnixTimestampSecs::now().as_i64())
+    .fetch_optional(db)
+    .await?;
+    value.ok_or_else(|| anyhow::anyhow!("缓存数据不是整数或计数器数值溢出（ID：{id}）"))
+}
+
+pub async fn compare_and_update_active(
+    cache: &CachePo,
+    expected_data: &str,
+    db: &mut DbConn,
+) -> anyhow::Result<u64> {
+    sqlx::query(
+        "
+        UPDATE cache SET
+            `data` = ?,
+            `created_at` = ?,
+            `expires_at` = ?
+        WHERE
+            `kind` = ? AND `id` = ? AND expires_at >= ? AND `data` = ?
+        ",
+    )
+    .bind(&cache.data)
+    .bind(&cache.created_at)
+    .bind(&cache.expires_at)
+    .bind(&cache.kind)
+    .bind(&cache.id)
+    .bind(UnixTimestampSecs::now().as_i64())
+    .bind(expected_data)
+    .execute(db)
+    .await
+    .map(|res| res.rows_affected())
+    .map_err(From::from)
+}
+
 pub async fn remove(kind: &str, id: &str, db: &mut DbConn) -> anyhow::Result<u64> {
     sqlx::query("DELETE FROM cache WHERE kind = ? AND id = ?")
         .bind(kind)
@@ -136,15 +213,88 @@ pub async fn remove_by_id_prefix(
     id_prefix: &str,
     db: &mut DbConn,
 ) -> anyhow::Result<u64> {
-    sqlx::query("DELETE FROM cache WHERE kind = ? AND id LIKE CONCAT(?, '%')")
-        .bind(kind)
-        .bind(crate::util::sqlx::escape_like_special_chars(id_prefix))
+    let mut sql = String::from("DELETE FROM cache WHERE kind = ? AND id >= ?");
+    let mut sql_params = SqliteArguments::default();
+    sql_params.add(kind).anyhow()?;
+    sql_params.add(id_prefix).anyhow()?;
+    if let Some(upper_bound) = crate::util::sqlx::prefix_upper_bound(id_prefix) {
+        sql.push_str(" AND id < ?");
+        sql_params.add(upper_bound).anyhow()?;
+    }
+
+    sqlx::query_with(AssertSqlSafe(sql), sql_params)
         .execute(db)
         .await
         .map(|res| res.rows_affected())
         .map_err(From::from)
 }
 
+/// 按 ID 顺序列出指定前缀的有效缓存的 ID 和过期时间，`after` 不为空时只列出排在其后的缓存
+pub async fn list_active_id_by_id_prefix(
+    kind: &str,
+    id_prefix: &str,
+    after: Option<&str>,
+    limit: u64,
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<(String, i64)>> {
+    let mut sql = String::from("SELECT id, expires_at FROM cache WHERE kind = ?");
+    let mut sql_params = SqliteArguments::default();
+    sql_params.add(kind).anyhow()?;
+    match after {
+        Some(after) if after >= id_prefix => {
+            sql.push_str(" AND id > ?");
+            sql_params.add(after).anyhow()?;
+        }
+        _ => {
+            sql.push_str(" AND id >= ?");
+            sql_params.add(id_prefix).anyhow()?;
+        }
+    }
+    if let Some(upper_bound) = crate::util::sqlx::prefix_upper_bound(id_prefix) {
+        sql.push_str(" AND id < ?");
+        sql_params.add(upper_bound).anyhow()?;
+    }
+    sql.push_str(" AND expires_at >= ? ORDER BY id LIMIT ?");
+    sql_params.add(UnixTimestampSecs::now().as_i64()).anyhow()?;
+    sql_params.add(i64::try_from(limit)?).anyhow()?;
+
+    sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+        .fetch_all(db)
+        .await
+        .map_err(From::from)
+}
+
+pub async fn list_active_by_ids(
+    kind: &str,
+    ids: &[&str],
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<CachePo>> {
+    if ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut sql = String::new();
+    let mut sql_params = SqliteArguments::default();
+
+    let placeholders = std::iter::repeat_n("?", ids.len())
+        .collect::<Vec<_>>()
+        .join(", ");
+    writeln!(
+        &mut sql,
+        "SELECT * FROM cache WHERE kind = ? AND id IN ({placeholders}) AND expires_at >= ?",
+    )?;
+    sql_params.add(kind).anyhow()?;
+    for id in ids {
+        sql_params.add(*id).anyhow()?;
+    }
+    sql_params.add(UnixTimestampSecs::now().as_i64()).anyhow()?;
+
+    sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+        .fetch_all(db)
+        .await
+        .map_err(From::from)
+}
+
 pub async fn remove_single_expired(kind: &str, id: &str, db: &mut DbConn) -> anyhow::Result<u64> {
     sqlx::query("DELETE FROM cache WHERE kind = ? AND id = ? AND expires_at < ?")
         .bind(kind)
@@ -166,6 +316,35 @@ pub async fn remove_all_expired(limit: u64, db: &mut DbConn) -> anyhow::Result<u
         .map_err(From::from)
 }
 
+pub async fn find(kind: &str, id: &str, db: &mut DbConn) -> anyhow::Result<Option<CachePo>> {
+    sqlx::query_as("SELECT * FROM cache WHERE kind = ? AND id = ?")
+        .bind(kind)
+        .bind(id)
+        .fetch_optional(db)
+        .await
+        .map_err(From::from)
+}
+
+/// 按缓存类型统计缓存数量、过期数量及数据大小
+pub async fn stats_by_kind(db: &mut DbConn) -> anyhow::Result<Vec<(String, i64, i64, i64)>> {
+    sqlx::query_as(
+        "
+        SELECT
+            `kind`,
+            COUNT(*),
+            COALESCE(SUM(`expires_at` < ?), 0),
+            COALESCE(SUM(LENGTH(CAST(`data` AS BLOB))), 0)
+        FROM cache
+        GROUP BY `kind`
+        ORDER BY `kind`
+        ",
+    )
+    .bind(UnixTimestampSecs::now().as_i64())
+    .fetch_all(db)
+    .await
+    .map_err(From::from)
+}
+
 pub async fn find_active(kind: &str, id: &str, db: &mut DbConn) -> anyhow::Result<Option<CachePo>> {
     sqlx::query_as("SELECT * FROM cache WHERE kind = ? AND id = ? AND expires_at >= ?")
         .bind(kind)
@@ -0,0 +1,631 @@
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Arguments, AssertSqlSafe};
+
+use crate::config::FtsTokenizer;
+use crate::model::po::article::ArticlePo;
+use crate::storage::db::DbConn;
+use crate::util::fts::{self, SearchColumn, SearchTerm};
+use crate::util::result::ResultExt;
+
+/// 全文索引表
+pub const FTS_TABLES: [&str; 2] = ["article_fts", "article_attachment_fts"];
+
+/// 三元组分词器能够通过全文索引匹配的最少字符数
+const TRIGRAM_MIN_CHARS: usize = 3;
+
+/// 可搜索的全文索引表
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FtsTable {
+    /// 文章的全文索引
+    Article,
+    /// 文章附件的全文索引
+    ArticleAttachment,
+}
+
+impl FtsTable {
+    /// 表名
+    pub fn name(self) -> &'static str {
+        match self {
+            FtsTable::Article => "article_fts",
+            FtsTable::ArticleAttachment => "article_attachment_fts",
+        }
+    }
+
+    /// 未限定字段的搜索词匹配的列
+    fn columns(self) -> &'static [&'static str] {
+        match self {
+            FtsTable::Article => &["title", "excerpt", "plain_content"],
+            FtsTable::ArticleAttachment => &["title", "plain_content"],
+        }
+    }
+}
+
+/// 全文检索条件，条件中的参数需要在写入 SQL 后通过 [`MatchCondition::add_params`] 按顺序添加
+#[derive(Debug, Clone, Default)]
+pub struct MatchCondition {
+    /// WHERE 子句中的条件
+    pub sql: String,
+    /// 是否包含 MATCH 表达式，只有包含时才能使用 bm25、highlight 等辅助函数以及 rank 列
+    pub ranked: bool,
+    /// 条件中的参数
+    params: Vec<String>,
+}
+
+impl MatchCondition {
+    /// 按顺序添加条件中的参数
+    pub fn add_params(self, sql_params: &mut SqliteArguments) -> anyhow::Result<()> {
+        for param in self.params {
+            sql_params.add(param).anyhow()?;
+        }
+        Ok(())
+    }
+}
+
+/// 搜索词转换后的匹配方式
+#[derive(Debug, Default)]
+struct TermMatch {
+    /// MATCH 表达式的 SQL 片段及其参数，片段之间为 AND 关系
+    expressions: Vec<(String, String)>,
+    /// 无法通过全文索引匹配、需要逐行查找的子串及其限定的字段
+    substrings: Vec<(String, Option<SearchColumn>)>,
+}
+
+/// 生成所有搜索词都需要匹配的全文检索条件，没有搜索词时返回 `None`
+///
+/// 使用三元组分词器时，少于 3 个字符的搜索词无法通过全文索引匹配，退化为逐行查找子串。
+pub fn match_all<'a>(
+    table: FtsTable,
+    terms: impl IntoIterator<Item = &'a SearchTerm>,
+) -> Option<MatchCondition> {
+    match_all_with(super::fts_tokenizer(), table, terms)
+}
+
+fn match_all_with<'a>(
+    tokenizer: FtsTokenizer,
+    table: FtsTable,
+    terms: impl IntoIterator<Item = &'a SearchTerm>,
+) -> Option<MatchCondition> {
+    let mut matched = TermMatch::default();
+    let mut empty = true;
+    for term in terms {
+        term_match(tokenizer, term, &mut matched);
+        empty = false;
+    }
+    if empty {
+        return None;
+    }
+
+    let name = table.name();
+    let mut condition = MatchCondition::default();
+    let mut conditions = vec![];
+    if !matched.expressions.is_empty() {
+        let (expressions, params): (Vec<_>, Vec<_>) = matched.expressions.into_iter().unzip();
+        conditions.push(format!(
+            "{name} MATCH ({})",
+            expressions.join(" || ' AND ' || ")
+        ));
+        condition.params.extend(params);
+        condition.ranked = true;
+    }
+    for (text, column) in matched.substrings {
+        let columns = match column {
+            Some(column) => vec![column.column_name()],
+            None => table.columns().to_vec(),
+        };
+        let substring = columns
+            .iter()
+            .map(|column| format!("instr(lower({name}.{column}), lower(?)) > 0"))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+        conditions.push(format!("({substring})"));
+        condition
+            .params
+            .extend(std::iter::repeat_n(text, columns.len()));
+    }
+    condition.sql = conditions.join(" AND ");
+    Some(condition)
+}
+
+/// 生成任一关键词匹配即可的全文检索条件，结果可按相关度排序
+///
+/// 关键词中无法通过全文索引匹配的部分会被忽略，没有可匹配的关键词时返回 `None`。
+pub fn match_any(table: FtsTable, keywords: &[&str]) -> Option<MatchCondition> {
+    match_any_with(super::fts_tokenizer(), table, keywords)
+}
+
+fn match_any_with(
+    tokenizer: FtsTokenizer,
+    table: FtsTable,
+    keywords: &[&str],
+) -> Option<MatchCondition> {
+    let mut condition = MatchCondition::default();
+    let mut expressions = vec![];
+    for keyword in keywords {
+        let term = SearchTerm {
+            text: (*keyword).to_owned(),
+            phrase: false,
+            column: None,
+            exclude: false,
+        };
+        let mut matched = TermMatch::default();
+        term_match(tokenizer, &term, &mut matched);
+        if matched.expressions.is_empty() {
+            continue;
+        }
+        let (keyword_expressions, params): (Vec<_>, Vec<_>) =
+            matched.expressions.into_iter().unzip();
+        expressions.push(format!(
+            "'(' || {} || ')'",
+            keyword_expressions.join(" || ' AND ' || ")
+        ));
+        condition.params.extend(params);
+    }
+    if expressions.is_empty() {
+        return None;
+    }
+    condition.sql = format!(
+        "{} MATCH ({})",
+        table.name(),
+        expressions.join(" || ' OR ' || ")
+    );
+    condition.ranked = true;
+    Some(condition)
+}
+
+/// 将搜索词转换为 MATCH 表达式或需要逐行查找的子串
+///
+/// - simple 分词器：关键词通过 `simple_query` 分词，短语由分词器按短语匹配
+/// - trigram 分词器：关键词切分后的片段按子串匹配，少于 3 个字符的片段与短语退化为逐行查找子串
+/// - unicode61 分词器：索引中的中日韩文字逐字切分，查询时同样逐字切分后按短语匹配，其他片段按前缀匹配
+fn term_match(tokenizer: FtsTokenizer, term: &SearchTerm, matched: &mut TermMatch) {
+    let column = term
+        .column
+        .map(|column| format!("{} : ", column.column_name()))
+        .unwrap_or_default();
+    let expression = format!("'{column}(' || ? || ')'");
+    match tokenizer {
+        FtsTokenizer::Simple if term.phrase => {
+            matched.expressions.push((expression, quote(&term.text)));
+        }
+        FtsTokenizer::Simple => {
+            let expression = format!("'{column}(' || simple_query(?) || ')'");
+            matched.expressions.push((expression, term.text.clone()));
+        }
+        FtsTokenizer::Trigram => {
+            let segments = match term.phrase {
+                true => vec![term.text.as_str()],
+                false => fts::segment_keyword(&term.text),
+            };
+            let mut long = vec![];
+            for segment in segments {
+                if segment.chars().count() >= TRIGRAM_MIN_CHARS {
+                    long.push(quote(segment));
+                } else {
+                    matched.substrings.push((segment.to_owned(), term.column));
+                }
+            }
+            if !long.is_empty() {
+                matched.expressions.push((expression, long.join(" AND ")));
+            }
+        }
+        FtsTokenizer::Unicode61 if term.phrase => {
+            let phrase = quote(&fts::separate_cjk(&term.text));
+            matched.expressions.push((expression, phrase));
+        }
+        FtsTokenizer::Unicode61 => {
+            let segments = fts::segment_keyword(&term.text)
+                .into_iter()
+                .map(|segment| match segment.starts_with(fts::is_cjk) {
+                    true => quote(&fts::separate_cjk(segment)),
+                    false => format!("{} *", quote(segment)),
+                })
+                .collect::<Vec<_>>();
+            if !segments.is_empty() {
+                matched
+                    .expressions
+                    .push((expression, segments.join(" AND ")));
+            }
+        }
+    }
+}
+
+/// 将文本转换为 FTS5 的字符串
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\"\""))
+}
+
+/// 写入全文索引的文本，使用 unicode61 分词器时在相邻的中日韩文字之间插入分隔符，使其逐字索引
+pub fn index_text(text: &str) -> String {
+    index_text_with(super::fts_tokenizer(), text)
+}
+
+fn index_text_with(tokenizer: FtsTokenizer, text: &str) -> String {
+    match tokenizer {
+        FtsTokenizer::Unicode61 => fts::separate_cjk(text).into_owned(),
+        FtsTokenizer::Simple | FtsTokenizer::Trigram => text.to_owned(),
+    }
+}
+
+/// 写入文章的全文索引，替换已存在的索引，文章删除时索引会通过触发器一同删除
+pub async fn index_article(article: &ArticlePo, db: &mut DbConn) -> anyhow::Result<()> {
+    index_article_with(super::fts_tokenizer(), article, db).await
+}
+
+async fn index_article_with(
+    tokenizer: FtsTokenizer,
+    article: &ArticlePo,
+    db: &mut DbConn,
+) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM article_fts WHERE rowid = (SELECT rowid FROM article WHERE id = ?)")
+        .bind(&article.id)
+        .execute(&mut *db)
+        .await?;
+    sqlx::query(
+        "
+        INSERT INTO article_fts (
+            `rowid`,
+            `id`,
+            `title`,
+            `excerpt`,
+            `plain_content`
+        )
+        SELECT `rowid`, `id`, ?, ?, ? FROM article WHERE `id` = ?
+        ",
+    )
+    .bind(index_text_with(tokenizer, &article.title))
+    .bind(index_text_with(tokenizer, &article.excerpt))
+    .bind(index_text_with(tokenizer, &article.plain_content))
+    .bind(&article.id)
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// 重新写入所有文章的全文索引
+pub async fn index_all_articles(db: &mut DbConn) -> anyhow::Result<()> {
+    index_all_articles_with(super::fts_tokenizer(), db).await
+}
+
+async fn index_all_articles_with(tokenizer: FtsTokenizer, db: &mut DbConn) -> anyhow::Result<()> {
+    let articles: Vec<ArticlePo> = sqlx::query_as("SELECT * FROM article")
+        .fetch_all(&mut *db)
+        .await?;
+    for article in &articles {
+        index_article_with(tokenizer, article, db).await?;
+    }
+    Ok(())
+}
+
+/// 删除文章的所有全文索引后根据文章表重新写入，修复文章数据被直接修改后与索引不一致的问题
+pub async fn reindex_all_articles(db: &mut DbConn) -> anyhow::Result<()> {
+    reindex_all_articles_with(super::fts_tokenizer(), db).await
+}
+
+async fn reindex_all_articles_with(tokenizer: FtsTokenizer, db: &mut DbConn) -> anyhow::Result<()> {
+    sqlx::query("DELETE FROM article_fts")
+        .execute(&mut *db)
+        .await?;
+    index_all_articles_with(tokenizer, db).await
+}
+
+/// 检查文章的全文索引是否与文章表一致
+///
+/// 检查缺少索引的文章、没有对应文章的索引、文章ID不一致以及文本内容不一致的索引，一致时返回 `None`，
+/// 否则返回错误信息。
+pub async fn check_articles(db: &mut DbConn) -> anyhow::Result<Option<String>> {
+    check_articles_with(super::fts_tokenizer(), db).await
+}
+
+async fn check_articles_with(
+    tokenizer: FtsTokenizer,
+    db: &mut DbConn,
+) -> anyhow::Result<Option<String>> {
+    let (missing, orphaned, mismatched): (i64, i64, i64) = sqlx::query_as(
+        "
+        SELECT
+            (SELECT COUNT(*) FROM article a
+                WHERE NOT EXISTS (SELECT 1 FROM article_fts f WHERE f.rowid = a.rowid)),
+            (SELECT COUNT(*) FROM article_fts f
+                WHERE NOT EXISTS (SELECT 1 FROM article a WHERE a.rowid = f.rowid)),
+            (SELECT COUNT(*) FROM article_fts f
+                INNER JOIN article a ON a.rowid = f.rowid WHERE a.id IS NOT f.id)
+        ",
+    )
+    .fetch_one(&mut *db)
+    .await?;
+
+    let rows: Vec<(String, String, String, String, String, String)> = sqlx::query_as(
+        "
+        SELECT a.title, a.excerpt, a.plain_content, f.title, f.excerpt, f.plain_content
+        FROM article_fts f INNER JOIN article a ON a.rowid = f.rowid
+        ",
+    )
+    .fetch_all(db)
+    .await?;
+    let stale = rows
+        .iter()
+        .filter(
+            |(title, excerpt, plain_content, fts_title, fts_excerpt, fts_plain_content)| {
+                index_text_with(tokenizer, title) != *fts_title
+                    || index_text_with(tokenizer, excerpt) != *fts_excerpt
+                    || index_text_with(tokenizer, plain_content) != *fts_plain_content
+            },
+        )
+        .count();
+
+    if missing == 0 && orphaned == 0 && mismatched == 0 && stale == 0 {
+        return Ok(None);
+    }
+    Ok(Some(format!(
+        "缺少索引的文章：{missing}，没有对应文章的索引：{orphaned}，文章ID不一致的索引：{mismatched}，\
+         内容不一致的索引：{stale}"
+    )))
+}
+
+/// 根据索引保存的文本重新生成全文索引，表名必须取自 [`FTS_TABLES`]
+pub async fn rebuild(table: &'static str, db: &mut DbConn) -> anyhow::Result<()> {
+    sqlx::query(AssertSqlSafe(format!(
+        "INSERT INTO {table} ({table}) VALUES ('rebuild')"
+    )))
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// 合并全文索引的所有分段，表名必须取自 [`FTS_TABLES`]
+pub async fn optimize(table: &'static str, db: &mut DbConn) -> anyhow::Result<()> {
+    sqlx::query(AssertSqlSafe(format!(
+        "INSERT INTO {table} ({table}) VALUES ('optimize')"
+    )))
+    .execute(db)
+    .await?;
+    Ok(())
+}
+
+/// 检查全文索引的结构是否完整且与索引保存的文本一致，表名必须取自 [`FTS_TABLES`]
+///
+/// 检查通过时返回 `None`，否则返回错误信息。
+pub async fn integrity_check(
+    table: &'static str,
+    db: &mut DbConn,
+) -> anyhow::Result<Option<String>> {
+    let result = sqlx::query(AssertSqlSafe(format!(
+        "INSERT INTO {table} ({table}, rank) VALUES ('integrity-check', 1)"
+    )))
+    .execute(db)
+    .await;
+    match result {
+        Ok(_) => Ok(None),
+        // SQLITE_CORRUPT、SQLITE_CORRUPT_VTAB
+        Err(sqlx::Error::Database(e)) if matches!(e.code().as_deref(), Some("11" | "267")) => {
+            Ok(Some(e.message().to_owned()))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlx::pool::PoolConnection;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+    use crate::model::common::article::ArticleStatus;
+    use crate::storage::db::Db;
+    use crate::util::fts::SearchQuery;
+
+    /// 使用指定分词器创建文章表与全文索引，并写入测试文章
+    async fn setup(tokenizer: FtsTokenizer) -> PoolConnection<Db> {
+        // 内存数据库的每个连接相互独立，因此只使用一个连接
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let mut db = pool.acquire().await.unwrap();
+        let sql = include_str!("../../../sqlite/migrations/article.sql").replace(
+            super::super::fts_tokenize_option(FtsTokenizer::Simple),
+            super::super::fts_tokenize_option(tokenizer),
+        );
+        sqlx::raw_sql(AssertSqlSafe(sql))
+            .execute(&mut *db)
+            .await
+            .unwrap();
+
+        for (id, title, plain_content) in [
+            ("a1", "Rust 异步编程指南", "使用 tokio 运行时编写异步程序"),
+            ("a2", "数据库索引", "SQLite 全文检索与分词"),
+        ] {
+            let article = ArticlePo {
+                id: id.to_owned(),
+                title: title.to_owned(),
+                excerpt: plain_content.to_owned(),
+                markdown_content: plain_content.to_owned(),
+                plain_content: plain_content.to_owned(),
+                password: None,
+                status: ArticleStatus::Published,
+                created_at: 0,
+                updated_at: 0,
+                published_at: Some(0),
+                pinned_at: None,
+                featured: false,
+            };
+            sqlx::query(
+                "
+                INSERT INTO article (
+                    id, title, excerpt, markdown_content, plain_content, status, created_at, updated_at
+                ) VALUES (?, ?, ?, ?, ?, ?, 0, 0)
+                ",
+            )
+            .bind(&article.id)
+            .bind(&article.title)
+            .bind(&article.excerpt)
+            .bind(&article.markdown_content)
+            .bind(&article.plain_content)
+            .bind(article.status)
+            .execute(&mut *db)
+            .await
+            .unwrap();
+            index_article_with(tokenizer, &article, &mut db)
+                .await
+                .unwrap();
+        }
+        db
+    }
+
+    async fn search(db: &mut DbConn, tokenizer: FtsTokenizer, input: &str) -> Vec<String> {
+        let query = SearchQuery::parse(input);
+        let condition = match_all_with(tokenizer, FtsTable::Article, query.includes()).unwrap();
+        let sql = format!(
+            "SELECT id FROM article_fts WHERE {} ORDER BY id",
+            condition.sql
+        );
+        let mut sql_params = SqliteArguments::default();
+        condition.add_params(&mut sql_params).unwrap();
+        sqlx::query_scalar_with(AssertSqlSafe(sql), sql_params)
+            .fetch_all(db)
+            .await
+            .unwrap()
+    }
+
+    async fn related(db: &mut DbConn, tokenizer: FtsTokenizer, keywords: &[&str]) -> Vec<String> {
+        let Some(condition) = match_any_with(tokenizer, FtsTable::Article, keywords) else {
+            return vec![];
+        };
+        let sql = format!(
+            "SELECT id FROM article_fts WHERE {} ORDER BY id",
+            condition.sql
+        );
+        let mut sql_params = SqliteArguments::default();
+        condition.add_params(&mut sql_params).unwrap();
+        sqlx::query_scalar_with(AssertSqlSafe(sql), sql_params)
+            .fetch_all(db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn match_cjk_keywords() {
+        for tokenizer in [FtsTokenizer::Trigram, FtsTokenizer::Unicode61] {
+            let mut db = setup(tokenizer).await;
+            let cases: [(&str, &[&str]); 9] = [
+                ("异步", &["a1"]),
+                ("编程", &["a1"]),
+                ("全文检索", &["a2"]),
+                ("编程指南", &["a1"]),
+                ("tokio 程序", &["a1"]),
+                (r#""异步编程""#, &["a1"]),
+                ("title:索引", &["a2"]),
+                ("title:分词", &[]),
+                ("异步 索引", &[]),
+            ];
+            for (input, expected) in cases {
+                assert_eq!(
+                    search(&mut db, tokenizer, input).await,
+                    expected,
+                    "tokenizer: {tokenizer:?}, input: {input}"
+                );
+            }
+            assert_eq!(
+                related(&mut db, tokenizer, &["异步编程", "数据库"]).await,
+                ["a1", "a2"],
+                "tokenizer: {tokenizer:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn match_trigram_short_terms() {
+        let tokenizer = FtsTokenizer::Trigram;
+        let mut db = setup(tokenizer).await;
+        let query = SearchQuery::parse("异步");
+        let condition = match_all_with(tokenizer, FtsTable::Article, query.includes()).unwrap();
+        assert!(!condition.ranked);
+        // 少于 3 个字符的子串逐行查找，不区分 ASCII 字母大小写
+        assert_eq!(search(&mut db, tokenizer, "IO").await, ["a1"]);
+        assert_eq!(search(&mut db, tokenizer, "oki").await, ["a1"]);
+        // 相关文章只使用可以通过全文索引匹配的关键词
+        assert!(match_any_with(tokenizer, FtsTable::Article, &["异步"]).is_none());
+    }
+
+    #[tokio::test]
+    async fn match_unicode61_prefix() {
+        let tokenizer = FtsTokenizer::Unicode61;
+        let mut db = setup(tokenizer).await;
+        assert_eq!(search(&mut db, tokenizer, "tok").await, ["a1"]);
+        assert_eq!(search(&mut db, tokenizer, "sql").await, ["a2"]);
+        assert!(search(&mut db, tokenizer, "oki").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn check_and_reindex_articles() {
+        let tokenizer = FtsTokenizer::Unicode61;
+        let mut db = setup(tokenizer).await;
+        assert_eq!(check_articles_with(tokenizer, &mut db).await.unwrap(), None);
+
+        // 直接修改文章数据，索引不会同步更新
+        sqlx::query("UPDATE article SET title = '搜索服务' WHERE id = 'a2'")
+            .execute(&mut *db)
+            .await
+            .unwrap();
+        sqlx::query(
+            "
+            INSERT INTO article (
+                id, title, excerpt, markdown_content, plain_content, status, created_at, updated_at
+            ) VALUES ('a3', '未索引', '', '', '', ?, 0, 0)
+            ",
+        )
+        .bind(ArticleStatus::Published)
+        .execute(&mut *db)
+        .await
+        .unwrap();
+        sqlx::query("UPDATE article_fts SET id = 'other' WHERE id = 'a1'")
+            .execute(&mut *db)
+            .await
+            .unwrap();
+        sqlx::query(
+            "
+            INSERT INTO article_fts (rowid, id, title, excerpt, plain_content)
+            VALUES (100, 'gone', '', '', '')
+            ",
+        )
+        .execute(&mut *db)
+        .await
+        .unwrap();
+        let message = check_articles_with(tokenizer, &mut db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(message.contains("缺少索引的文章：1"), "{message}");
+        assert!(message.contains("没有对应文章的索引：1"), "{message}");
+        assert!(message.contains("文章ID不一致的索引：1"), "{message}");
+        assert!(message.contains("内容不一致的索引：1"), "{message}");
+
+        reindex_all_articles_with(tokenizer, &mut db).await.unwrap();
+        assert_eq!(check_articles_with(tokenizer, &mut db).await.unwrap(), None);
+        assert_eq!(search(&mut db, tokenizer, "服务").await, ["a2"]);
+        assert_eq!(search(&mut db, tokenizer, "未索引").await, ["a3"]);
+    }
+
+    #[tokio::test]
+    async fn reindex_article() {
+        let tokenizer = FtsTokenizer::Unicode61;
+        let mut db = setup(tokenizer).await;
+        let mut article: ArticlePo = sqlx::query_as("SELECT * FROM article WHERE id = 'a2'")
+            .fetch_one(&mut *db)
+            .await
+            .unwrap();
+        article.title = "搜索服务".to_owned();
+        index_article_with(tokenizer, &article, &mut db)
+            .await
+            .unwrap();
+        assert_eq!(search(&mut db, tokenizer, "服务").await, ["a2"]);
+        assert!(search(&mut db, tokenizer, "title:索引").await.is_empty());
+
+        sqlx::query("DELETE FROM article WHERE id = 'a2'")
+            .execute(&mut *db)
+            .await
+            .unwrap();
+        assert!(search(&mut db, tokenizer, "服务").await.is_empty());
+    }
+}
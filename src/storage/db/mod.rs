@@ -1,29 +1,64 @@
 pub mod article;
 pub mod article_attachment;
+pub mod article_daily_referrer;
+pub mod article_daily_stats;
 pub mod article_stats;
 pub mod cache;
+pub mod fts;
 pub mod resource;
+pub mod resource_stats;
+pub mod series;
+pub mod series_article;
 
 use std::path::Path;
+use std::sync::OnceLock;
 
 use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
-use sqlx::{Acquire, AssertSqlSafe, Transaction};
+use sqlx::{Acquire, AssertSqlSafe, ConnectOptions, Transaction};
 
-use crate::config::DatabaseConfig;
+use crate::config::{DatabaseConfig, FtsTokenizer};
+use crate::util::fts::strip_separators;
 
 pub type Db = sqlx::Sqlite;
 pub type DbPool = sqlx::Pool<Db>;
 pub type DbConn = <Db as sqlx::Database>::Connection;
 pub type DbPoolConn = sqlx::pool::PoolConnection<Db>;
 
+/// 启动时选定的全文索引分词器
+static FTS_TOKENIZER: OnceLock<FtsTokenizer> = OnceLock::new();
+
+/// 选定全文索引分词器，配置为 simple 分词器但扩展无法加载时使用备用分词器
+pub async fn init_fts_tokenizer(config: &DatabaseConfig) -> anyhow::Result<()> {
+    let mut tokenizer = config.sqlite.fts_tokenizer;
+    if tokenizer == FtsTokenizer::Simple
+        && let Err(e) = probe_sqlite_extensions(config).await
+    {
+        let Some(fallback) = config.sqlite.fts_fallback_tokenizer else {
+            return Err(anyhow::anyhow!("加载 SQLite 扩展失败：{e}"));
+        };
+        tracing::warn!("加载 SQLite 扩展失败：{e}，全文索引改用 {fallback:?} 分词器");
+        tokenizer = fallback;
+    }
+    FTS_TOKENIZER
+        .set(tokenizer)
+        .map_err(|_| anyhow::anyhow!("重复初始化全文索引分词器"))
+}
+
+/// 当前使用的全文索引分词器
+pub fn fts_tokenizer() -> FtsTokenizer {
+    FTS_TOKENIZER.get().copied().unwrap_or_default()
+}
+
 pub fn build_pool(config: &DatabaseConfig) -> anyhow::Result<DbPool> {
     let mut conn_opts = config.url.parse::<SqliteConnectOptions>()?;
 
     conn_opts = conn_opts.journal_mode(SqliteJournalMode::Wal);
 
-    // 添加扩展
-    for extension in sqlite_extensions(config) {
-        conn_opts = unsafe { conn_opts.extension(extension.clone()) };
+    // 添加扩展，仅 simple 分词器需要加载扩展
+    if fts_tokenizer() == FtsTokenizer::Simple {
+        for extension in sqlite_extensions(config) {
+            conn_opts = unsafe { conn_opts.extension(extension.clone()) };
+        }
     }
 
     let mut pool_opts = SqlitePoolOptions::new();
@@ -44,16 +79,171 @@ pub fn build_pool(config: &DatabaseConfig) -> anyhow::Result<DbPool> {
     Ok(pool_opts.connect_lazy_with(conn_opts))
 }
 
-/// 初始化数据库
+/// 初始化数据库，全文索引的分词器变更时重建全文索引
 pub async fn init(db: &mut DbConn) -> anyhow::Result<()> {
     transaction(db, async |tx| {
+        add_missing_columns(tx).await?;
+        let rebuild_fts = fts_rebuild_required(tx).await?;
+        let mut fts_backup = false;
+        if rebuild_fts {
+            tracing::info!("全文索引分词器或结构已变更，重建全文索引");
+            fts_backup = drop_fts_tables(tx).await?;
+        }
         let sql = build_init_sql()?;
         sqlx::raw_sql(sql).execute(tx as &mut DbConn).await?;
+        if rebuild_fts {
+            restore_fts_tables(fts_backup, tx).await?;
+        }
+        strip_legacy_resource_paths(tx).await?;
         Ok(())
     })
     .await
 }
 
+/// 为已存在的表补充后续版本新增的列，迁移脚本中的建表语句不会修改已存在的表
+async fn add_missing_columns(db: &mut DbConn) -> anyhow::Result<()> {
+    /// 后续版本新增的列：（表名，列名，列定义，补充列后回填数据的语句）
+    const ADDED_COLUMNS: [(&str, &str, &str, Option<&str>); 3] = [
+        (
+            "resource",
+            "detected_mime_type",
+            "TEXT NOT NULL DEFAULT 'application/octet-stream'",
+            // 历史资源未做内容检测，以上传时声明的 MIME 类型作为检测结果
+            Some("UPDATE resource SET detected_mime_type = mime_type"),
+        ),
+        ("article", "pinned_at", "INTEGER", None),
+        ("article", "featured", "INTEGER NOT NULL DEFAULT 0", None),
+    ];
+
+    for (table, column, definition, backfill) in ADDED_COLUMNS {
+        let columns: Vec<String> = sqlx::query_scalar("SELECT name FROM pragma_table_info(?)")
+            .bind(table)
+            .fetch_all(&mut *db)
+            .await?;
+        // 表不存在时由迁移脚本创建
+        if columns.is_empty() || columns.iter().any(|name| name == column) {
+            continue;
+        }
+        tracing::info!("为数据表 {table} 补充列 {column}");
+        sqlx::raw_sql(AssertSqlSafe(format!(
+            "ALTER TABLE {table} ADD COLUMN {column} {definition}"
+        )))
+        .execute(&mut *db)
+        .await?;
+        if let Some(backfill) = backfill {
+            sqlx::raw_sql(backfill).execute(&mut *db).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 早期版本的资源存储路径包含上传目录，改为对象存储后只保存相对于上传目录的存储键
+async fn strip_legacy_resource_paths(db: &mut DbConn) -> anyhow::Result<()> {
+    let prefix = crate::util::path::root(&crate::config::get().resource.upload_dir)
+        .join("")
+        .into_string();
+    let rows = resource::strip_path_prefix(&prefix, db).await?;
+    if rows > 0 {
+        tracing::info!("已将 {rows} 个资源的存储路径转换为存储键");
+    }
+    Ok(())
+}
+
+/// 全文索引分词器的建表参数
+fn fts_tokenize_option(tokenizer: FtsTokenizer) -> &'static str {
+    match tokenizer {
+        FtsTokenizer::Simple => "tokenize='simple'",
+        FtsTokenizer::Trigram => "tokenize='trigram'",
+        FtsTokenizer::Unicode61 => "tokenize='unicode61 remove_diacritics 2'",
+    }
+}
+
+/// 判断已存在的全文索引是否需要重建：使用的分词器与当前分词器不同，或为早期版本通过触发器同步的
+/// 外部内容表
+async fn fts_rebuild_required(db: &mut DbConn) -> anyhow::Result<bool> {
+    let sql: Option<String> = sqlx::query_scalar(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'article_fts'",
+    )
+    .fetch_optional(db)
+    .await?;
+    Ok(sql.is_some_and(|sql| {
+        !sql.contains(fts_tokenize_option(fts_tokenizer())) || sql.contains("content='article'")
+    }))
+}
+
+/// 删除全文索引，附件的全文索引内容无法从其他表恢复，删除前先暂存到临时表
+///
+/// 早期版本创建的数据库可能不存在附件的全文索引，只处理已存在的表。返回是否暂存了附件的全文索引。
+async fn drop_fts_tables(db: &mut DbConn) -> anyhow::Result<bool> {
+    let backup = table_exists("article_attachment_fts", db).await?;
+    if backup {
+        sqlx::raw_sql(
+            "
+            CREATE TEMP TABLE article_attachment_fts_backup AS
+            SELECT rowid, attachment_id, article_id, title, plain_content FROM article_attachment_fts;
+            DROP TABLE article_attachment_fts;
+            ",
+        )
+        .execute(&mut *db)
+        .await?;
+    }
+    sqlx::raw_sql("DROP TABLE IF EXISTS article_fts")
+        .execute(&mut *db)
+        .await?;
+    Ok(backup)
+}
+
+/// 使用新的分词器重新生成全文索引，`backup` 表示是否暂存了附件的全文索引
+async fn restore_fts_tables(backup: bool, db: &mut DbConn) -> anyhow::Result<()> {
+    fts::index_all_articles(db).await?;
+    if backup {
+        let attachments: Vec<(i64, String, String, String, String)> = sqlx::query_as(
+            "
+            SELECT rowid, attachment_id, article_id, title, plain_content
+            FROM article_attachment_fts_backup
+            ",
+        )
+        .fetch_all(&mut *db)
+        .await?;
+        // 暂存的文本可能包含之前的分词器插入的分隔符，移除后按当前分词器重新处理
+        for (rowid, attachment_id, article_id, title, plain_content) in attachments {
+            sqlx::query(
+                "
+                INSERT INTO article_attachment_fts (
+                    `rowid`,
+                    `attachment_id`,
+                    `article_id`,
+                    `title`,
+                    `plain_content`
+                ) VALUES (?, ?, ?, ?, ?)
+                ",
+            )
+            .bind(rowid)
+            .bind(attachment_id)
+            .bind(article_id)
+            .bind(fts::index_text(&strip_separators(&title)))
+            .bind(fts::index_text(&strip_separators(&plain_content)))
+            .execute(&mut *db)
+            .await?;
+        }
+        sqlx::raw_sql("DROP TABLE article_attachment_fts_backup")
+            .execute(&mut *db)
+            .await?;
+    }
+    Ok(())
+}
+
+/// 判断数据表（包括虚拟表）是否存在
+async fn table_exists(name: &str, db: &mut DbConn) -> anyhow::Result<bool> {
+    sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)",
+    )
+    .bind(name)
+    .fetch_one(db)
+    .await
+    .map_err(From::from)
+}
+
 fn build_init_sql() -> anyhow::Result<AssertSqlSafe<String>> {
     let mut sql = String::new();
     for entry in std::fs::read_dir(&crate::config::get().database.migrations.script_dir)? {
@@ -68,6 +258,11 @@ fn build_init_sql() -> anyhow::Result<AssertSqlSafe<String>> {
             sql.push_str(&std::fs::read_to_string(path)?);
         }
     }
+    // 迁移脚本中的全文索引使用 simple 分词器，替换为当前使用的分词器
+    let sql = sql.replace(
+        fts_tokenize_option(FtsTokenizer::Simple),
+        fts_tokenize_option(fts_tokenizer()),
+    );
     Ok(AssertSqlSafe(sql))
 }
 
@@ -78,6 +273,16 @@ fn file_name_ends_with(path: &Path, extension: &str) -> bool {
         .ends_with(extension)
 }
 
+/// 使用内存数据库尝试加载扩展
+async fn probe_sqlite_extensions(config: &DatabaseConfig) -> anyhow::Result<()> {
+    let mut conn_opts = SqliteConnectOptions::new().in_memory(true);
+    for extension in sqlite_extensions(config) {
+        conn_opts = unsafe { conn_opts.extension(extension) };
+    }
+    conn_opts.connect().await?;
+    Ok(())
+}
+
 #[cfg(unix)]
 fn sqlite_extensions(config: &DatabaseConfig) -> Vec<String> {
     vec![
@@ -109,3 +314,148 @@ where
     }
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    use super::*;
+    use crate::model::po::resource::ResourcePo;
+
+    /// 初始版本中 resource、article 表的建表语句
+    const BASELINE_SCHEMA: &str = "
+        CREATE TABLE resource (
+            id          TEXT    PRIMARY KEY,
+            name        TEXT    NOT NULL,
+            extension   TEXT    NOT NULL,
+            path        TEXT    NOT NULL,
+            size        INTEGER NOT NULL,
+            mime_type   TEXT    NOT NULL,
+            is_public   INTEGER NOT NULL,
+            sha256      TEXT    NOT NULL,
+            created_at  INTEGER NOT NULL
+        );
+        CREATE TABLE article (
+            id                  TEXT    PRIMARY KEY,
+            title               TEXT    NOT NULL,
+            excerpt             TEXT    NOT NULL,
+            markdown_content    TEXT    NOT NULL,
+            plain_content       TEXT    NOT NULL,
+            password            TEXT,
+            status              TEXT    NOT NULL,
+            created_at          INTEGER NOT NULL,
+            updated_at          INTEGER NOT NULL,
+            published_at        INTEGER
+        );
+        INSERT INTO resource VALUES ('r1', 'a', 'png', 'ab/cd/r1', 1, 'image/png', 1, 'x', 0);
+        INSERT INTO article VALUES ('a1', 't', 'e', 'm', 'p', NULL, 'published', 0, 0, 0);
+    ";
+
+    #[tokio::test]
+    async fn add_missing_columns_upgrades_baseline_schema() {
+        // 内存数据库的每个连接相互独立，因此只使用一个连接
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let mut db = pool.acquire().await.unwrap();
+        sqlx::raw_sql(BASELINE_SCHEMA)
+            .execute(&mut *db)
+            .await
+            .unwrap();
+
+        add_missing_columns(&mut db).await.unwrap();
+        // 重复执行时不再修改已补充的列
+        add_missing_columns(&mut db).await.unwrap();
+        sqlx::raw_sql(include_str!("../../../sqlite/migrations/resource.sql"))
+            .execute(&mut *db)
+            .await
+            .unwrap();
+
+        let resource: ResourcePo = sqlx::query_as("SELECT * FROM resource WHERE id = 'r1'")
+            .fetch_one(&mut *db)
+            .await
+            .unwrap();
+        assert_eq!(resource.detected_mime_type, "image/png");
+        let (pinned_at, featured): (Option<i64>, bool) =
+            sqlx::query_as("SELECT pinned_at, featured FROM article WHERE id = 'a1'")
+                .fetch_one(&mut *db)
+                .await
+                .unwrap();
+        assert_eq!(pinned_at, None);
+        assert!(!featured);
+    }
+
+    #[tokio::test]
+    async fn strip_path_prefix_converts_legacy_paths() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let mut db = pool.acquire().await.unwrap();
+        sqlx::raw_sql(include_str!("../../../sqlite/migrations/resource.sql"))
+            .execute(&mut *db)
+            .await
+            .unwrap();
+        for (id, path) in [
+            ("legacy", "data/ab/ab/cd/abcd-1"),
+            ("current", "ab/cd/abcd-2"),
+            ("other", "data/ab/other"),
+        ] {
+            sqlx::query(
+                "INSERT INTO resource VALUES (?, 'a', 'png', ?, 1, 'image/png', 'image/png', 1, 'x', 0)",
+            )
+            .bind(id)
+            .bind(path)
+            .execute(&mut *db)
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(
+            resource::strip_path_prefix("data/ab/", &mut db)
+                .await
+                .unwrap(),
+            1
+        );
+        // 存储键与上传目录同名时也不会被重复处理
+        assert_eq!(
+            resource::strip_path_prefix("ab/", &mut db).await.unwrap(),
+            0
+        );
+
+        let paths: Vec<(String, String)> =
+            sqlx::query_as("SELECT id, path FROM resource ORDER BY id")
+                .fetch_all(&mut *db)
+                .await
+                .unwrap();
+        assert_eq!(
+            paths,
+            [
+                ("current".to_owned(), "ab/cd/abcd-2".to_owned()),
+                ("legacy".to_owned(), "ab/cd/abcd-1".to_owned()),
+                ("other".to_owned(), "data/ab/other".to_owned()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn drop_fts_tables_without_attachment_index() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let mut db = pool.acquire().await.unwrap();
+        // 早期版本的数据库只有文章的全文索引
+        sqlx::raw_sql("CREATE VIRTUAL TABLE article_fts USING fts5 (title, tokenize='trigram')")
+            .execute(&mut *db)
+            .await
+            .unwrap();
+
+        assert!(!drop_fts_tables(&mut db).await.unwrap());
+        assert!(!table_exists("article_fts", &mut db).await.unwrap());
+    }
+}
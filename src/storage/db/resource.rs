@@ -17,10 +17,11 @@ pub async fn create(resource: &ResourcePo, db: &mut DbConn) -> anyhow::Result<()
             `path`,
             `size`,
             `mime_type`,
+            `detected_mime_type`,
             `is_public`,
             `sha256`,
             `created_at`
-        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         ",
     )
     .bind(&resource.id)
@@ -29,6 +30,7 @@ pub async fn create(resource: &ResourcePo, db: &mut DbConn) -> anyhow::Result<()
     .bind(&resource.path)
     .bind(&i64::try_from(resource.size)?)
     .bind(&resource.mime_type)
+    .bind(&resource.detected_mime_type)
     .bind(&resource.is_public)
     .bind(&resource.sha256)
     .bind(&resource.created_at)
@@ -47,6 +49,7 @@ pub async fn update(resource: &ResourcePo, db: &mut DbConn) -> anyhow::Result<u6
             `path` = ?,
             `size` = ?,
             `mime_type` = ?,
+            `detected_mime_type` = ?,
             `is_public` = ?,
             `sha256` = ?,
             `created_at` = ?
@@ -59,6 +62,7 @@ pub async fn update(resource: &ResourcePo, db: &mut DbConn) -> anyhow::Result<u6
     .bind(&resource.path)
     .bind(&i64::try_from(resource.size)?)
     .bind(&resource.mime_type)
+    .bind(&resource.detected_mime_type)
     .bind(&resource.is_public)
     .bind(&resource.sha256)
     .bind(&resource.created_at)
@@ -87,6 +91,24 @@ pub async fn remove_by_path(path: &str, db: &mut DbConn) -> anyhow::Result<u64>
         .map_err(From::from)
 }
 
+/// 去除早期版本在存储路径中保存的目录前缀，将其转换为对象存储键。早期版本的存储路径为
+/// `{prefix}xx/yy/{uuid}`，只处理去除前缀后仍符合该格式的路径，重复执行不会产生影响
+pub async fn strip_path_prefix(prefix: &str, db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query(
+        "
+        UPDATE resource SET path = substr(path, length(?1) + 1)
+        WHERE substr(path, 1, length(?1)) = ?1
+            AND length(path) - length(replace(path, '/', ''))
+                = length(?1) - length(replace(?1, '/', '')) + 2
+        ",
+    )
+    .bind(prefix)
+    .execute(db)
+    .await
+    .map(|res| res.rows_affected())
+    .map_err(From::from)
+}
+
 pub async fn find(id: &str, db: &mut DbConn) -> anyhow::Result<Option<ResourcePo>> {
     sqlx::query_as("SELECT * FROM resource WHERE id = ?")
         .bind(id)
@@ -118,6 +140,20 @@ pub async fn count_by_path(path: &str, db: &mut DbConn) -> anyhow::Result<u64> {
         .map_err(From::from)
 }
 
+/// 统计已占用的存储空间，多个资源共享同一文件时只计算一次
+pub async fn sum_storage_size(db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query_scalar(
+        "
+        SELECT COALESCE(SUM(`size`), 0) FROM (
+            SELECT MAX(`size`) AS `size` FROM resource GROUP BY `path`
+        )
+        ",
+    )
+    .fetch_one(db)
+    .await
+    .map_err(From::from)
+}
+
 pub async fn list_by_ids(
     ids: &[impl AsRef<str>],
     db: &mut DbConn,
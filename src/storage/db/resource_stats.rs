@@ -0,0 +1,91 @@
+use std::fmt::Write;
+
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Arguments, AssertSqlSafe};
+
+use crate::model::po::resource_stats::ResourceStatsPo;
+use crate::storage::db::DbConn;
+use crate::util::result::ResultExt;
+
+pub async fn remove_by_resource_id(resource_id: &str, db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query("DELETE FROM resource_stats WHERE resource_id = ?")
+        .bind(resource_id)
+        .execute(db)
+        .await
+        .map(|res| res.rows_affected())
+        .map_err(From::from)
+}
+
+pub async fn find_by_resource_id(
+    resource_id: &str,
+    db: &mut DbConn,
+) -> anyhow::Result<Option<ResourceStatsPo>> {
+    sqlx::query_as("SELECT * FROM resource_stats WHERE resource_id = ?")
+        .bind(resource_id)
+        .fetch_optional(db)
+        .await
+        .map_err(From::from)
+}
+
+pub async fn list_by_resource_ids(
+    resource_ids: &[impl AsRef<str>],
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<ResourceStatsPo>> {
+    if resource_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut sql = String::new();
+    let mut sql_params = SqliteArguments::default();
+
+    let placeholders = std::iter::repeat_n("?", resource_ids.len())
+        .collect::<Vec<_>>()
+        .join(", ");
+    writeln!(
+        &mut sql,
+        "SELECT * FROM resource_stats WHERE resource_id IN ({placeholders})",
+    )?;
+    for resource_id in resource_ids {
+        sql_params.add(resource_id.as_ref()).anyhow()?;
+    }
+
+    sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+        .fetch_all(db)
+        .await
+        .map_err(From::from)
+}
+
+pub async fn increment_by_resource_id(
+    resource_id: &str,
+    downloads_add: u64,
+    uv_add: u64,
+    bytes_served_add: u64,
+    updated_at: i64,
+    db: &mut DbConn,
+) -> anyhow::Result<u64> {
+    sqlx::query(
+        "
+        INSERT INTO resource_stats (
+            `resource_id`,
+            `downloads`,
+            `uv`,
+            `bytes_served`,
+            `updated_at`
+        ) VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT (`resource_id`) DO UPDATE SET
+            `downloads` = `downloads` + excluded.`downloads`,
+            `uv` = `uv` + excluded.`uv`,
+            `bytes_served` = `bytes_served` + excluded.`bytes_served`,
+            `updated_at` = excluded.`updated_at`
+        ",
+    )
+    .bind(resource_id)
+    .bind(&i64::try_from(downloads_add)?)
+    .bind(&i64::try_from(uv_add)?)
+    .bind(&i64::try_from(bytes_served_add)?)
+    .bind(&updated_at)
+    .execute(db)
+    .await
+    .map(|r| r.rows_affected())
+    .map_err(Into::into)
+}
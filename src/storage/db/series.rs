@@ -0,0 +1,81 @@
+use crate::model::po::series::{SeriesListItem, SeriesPo};
+use crate::storage::db::DbConn;
+
+pub async fn create(series: &SeriesPo, db: &mut DbConn) -> anyhow::Result<()> {
+    sqlx::query(
+        "
+        INSERT INTO series (
+            `id`,
+            `title`,
+            `description`,
+            `created_at`,
+            `updated_at`
+        ) VALUES (?, ?, ?, ?, ?)
+        ",
+    )
+    .bind(&series.id)
+    .bind(&series.title)
+    .bind(&series.description)
+    .bind(&series.created_at)
+    .bind(&series.updated_at)
+    .execute(db)
+    .await
+    .map(|_| ())
+    .map_err(From::from)
+}
+
+pub async fn update(series: &SeriesPo, db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query(
+        "
+        UPDATE series SET
+            `title` = ?,
+            `description` = ?,
+            `created_at` = ?,
+            `updated_at` = ?
+        WHERE
+            `id` = ?
+        ",
+    )
+    .bind(&series.title)
+    .bind(&series.description)
+    .bind(&series.created_at)
+    .bind(&series.updated_at)
+    .bind(&series.id)
+    .execute(db)
+    .await
+    .map(|res| res.rows_affected())
+    .map_err(From::from)
+}
+
+pub async fn remove(id: &str, db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query("DELETE FROM series WHERE id = ?")
+        .bind(id)
+        .execute(db)
+        .await
+        .map(|res| res.rows_affected())
+        .map_err(From::from)
+}
+
+pub async fn find(id: &str, db: &mut DbConn) -> anyhow::Result<Option<SeriesPo>> {
+    sqlx::query_as("SELECT * FROM series WHERE id = ?")
+        .bind(id)
+        .fetch_optional(db)
+        .await
+        .map_err(From::from)
+}
+
+/// 列出所有系列及其文章数量，按修改时间从新到旧排序
+pub async fn list(db: &mut DbConn) -> anyhow::Result<Vec<SeriesListItem>> {
+    sqlx::query_as(
+        "
+        SELECT s.*, (
+            SELECT COUNT(*) FROM series_article sa WHERE sa.series_id = s.id
+        ) AS article_count
+        FROM series s
+        ORDER BY s.updated_at DESC, s.id
+        ",
+    )
+    .fetch_all(db)
+    .await
+    .map_err(From::from)
+}
@@ -0,0 +1,83 @@
+use std::fmt::Write;
+
+use sqlx::sqlite::SqliteArguments;
+use sqlx::{Arguments, AssertSqlSafe};
+
+use crate::model::common::article::ArticleStatus;
+use crate::model::po::article::ArticlePo;
+use crate::model::po::series::SeriesArticlePo;
+use crate::storage::db::DbConn;
+use crate::util::result::ResultExt;
+
+pub async fn create(series_article: &SeriesArticlePo, db: &mut DbConn) -> anyhow::Result<()> {
+    sqlx::query(
+        "
+        INSERT INTO series_article (
+            `series_id`,
+            `article_id`,
+            `position`
+        ) VALUES (?, ?, ?)
+        ",
+    )
+    .bind(&series_article.series_id)
+    .bind(&series_article.article_id)
+    .bind(&series_article.position)
+    .execute(db)
+    .await
+    .map(|_| ())
+    .map_err(From::from)
+}
+
+pub async fn remove_by_series_id(series_id: &str, db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query("DELETE FROM series_article WHERE series_id = ?")
+        .bind(series_id)
+        .execute(db)
+        .await
+        .map(|res| res.rows_affected())
+        .map_err(From::from)
+}
+
+pub async fn remove_by_article_id(article_id: &str, db: &mut DbConn) -> anyhow::Result<u64> {
+    sqlx::query("DELETE FROM series_article WHERE article_id = ?")
+        .bind(article_id)
+        .execute(db)
+        .await
+        .map(|res| res.rows_affected())
+        .map_err(From::from)
+}
+
+pub async fn find_by_article_id(
+    article_id: &str,
+    db: &mut DbConn,
+) -> anyhow::Result<Option<SeriesArticlePo>> {
+    sqlx::query_as("SELECT * FROM series_article WHERE article_id = ?")
+        .bind(article_id)
+        .fetch_optional(db)
+        .await
+        .map_err(From::from)
+}
+
+/// 按系列中的位置列出系列中的文章，可按文章状态过滤
+pub async fn list_articles(
+    series_id: &str,
+    status: Option<ArticleStatus>,
+    db: &mut DbConn,
+) -> anyhow::Result<Vec<ArticlePo>> {
+    let mut sql = String::new();
+    let mut sql_params = SqliteArguments::default();
+
+    writeln!(&mut sql, "SELECT a.* FROM series_article sa")?;
+    writeln!(&mut sql, "INNER JOIN article a ON a.id = sa.article_id")?;
+    writeln!(&mut sql, "WHERE sa.series_id = ?")?;
+    sql_params.add(series_id).anyhow()?;
+    if let Some(status) = status {
+        writeln!(&mut sql, "AND a.status = ?")?;
+        sql_params.add(status).anyhow()?;
+    }
+    writeln!(&mut sql, "ORDER BY sa.position")?;
+
+    sqlx::query_as_with(AssertSqlSafe(sql), sql_params)
+        .fetch_all(db)
+        .await
+        .map_err(From::from)
+}
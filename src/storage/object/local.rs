@@ -0,0 +1,214 @@
+use std::io::ErrorKind;
+use std::ops::Range;
+use std::path::Path;
+
+use futures_util::StreamExt;
+use tokio::fs::File;
+use tokio::io::AsyncWriteExt;
+
+use crate::storage::object::{ObjectData, ObjectStorage, ObjectStream};
+use crate::util::path::Root;
+
+/// 本地文件系统对象存储器
+pub struct LocalObjectStorage {
+    /// 存储根目录
+    root: Root,
+}
+
+impl LocalObjectStorage {
+    pub fn new(root: impl Into<String>) -> Self {
+        Self {
+            root: crate::util::path::root(root),
+        }
+    }
+
+    fn resolve(&self, key: &str) -> anyhow::Result<String> {
+        if !crate::util::path::is_safe(key) {
+            anyhow::bail!("对象键包含不安全的路径（键：{key}）");
+        }
+        Ok(self.root.clone().join(key).into_string())
+    }
+
+    async fn create_parent_dir(path: &str) -> std::io::Result<()> {
+        if let Some(dir) = Path::new(path).parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        Ok(())
+    }
+
+    async fn write_stream(file: &mut File, mut stream: ObjectStream) -> anyhow::Result<()> {
+        while let Some(chunk) = stream
+            .next()
+            .await
+            .transpose()
+            .map_err(|e| anyhow::anyhow!(e))?
+        {
+            file.write_all(&chunk).await?;
+        }
+        file.sync_all().await?;
+        Ok(())
+    }
+}
+
+impl ObjectStorage for LocalObjectStorage {
+    async fn put(&self, key: &str, stream: ObjectStream) -> anyhow::Result<()> {
+        let path = self.resolve(key)?;
+        Self::create_parent_dir(&path).await?;
+        // 原子创建文件，防止覆盖原有的文件
+        let mut file = File::create_new(&path).await?;
+        if let Err(e) = Self::write_stream(&mut file, stream).await {
+            drop(file);
+            if let Err(e) = tokio::fs::remove_file(&path).await {
+                tracing::warn!("文件删除失败，路径：{path}，错误：{e}");
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    async fn put_file(&self, key: &str, from: &str) -> anyhow::Result<()> {
+        let path = self.resolve(key)?;
+        Self::create_parent_dir(&path).await?;
+        // 原子创建文件，防止覆盖原有的文件
+        File::create_new(&path).await?;
+        // 尝试移动文件，失败时退化为复制
+        if tokio::fs::rename(from, &path).await.is_err()
+            && let Err(e) = tokio::fs::copy(from, &path).await
+        {
+            if let Err(e) = tokio::fs::remove_file(&path).await {
+                tracing::warn!("文件删除失败，路径：{path}，错误：{e}");
+            }
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        key: &str,
+        range: Option<Range<u64>>,
+    ) -> anyhow::Result<Option<ObjectData>> {
+        let path = self.resolve(key)?;
+        match super::read_file_range_stream(&path, range).await {
+            Ok((size, range, stream)) => Ok(Some(ObjectData {
+                size,
+                range,
+                stream,
+            })),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> anyhow::Result<bool> {
+        let path = self.resolve(key)?;
+        match tokio::fs::metadata(&path).await {
+            Ok(meta) => Ok(meta.is_file()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn remove(&self, key: &str) -> anyhow::Result<()> {
+        let path = self.resolve(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use boluo::BoxError;
+    use boluo::body::Bytes;
+    use futures_util::TryStreamExt;
+
+    use super::*;
+
+    fn temp_storage() -> (LocalObjectStorage, String) {
+        let root = std::env::temp_dir()
+            .join(crate::util::uuid::v4())
+            .into_os_string()
+            .into_string()
+            .unwrap();
+        (LocalObjectStorage::new(root.clone()), root)
+    }
+
+    fn stream_of(chunks: &[&'static [u8]]) -> ObjectStream {
+        let chunks = chunks
+            .iter()
+            .map(|c| Ok::<_, BoxError>(Bytes::from_static(c)))
+            .collect::<Vec<_>>();
+        Box::pin(futures_util::stream::iter(chunks))
+    }
+
+    async fn read_all(data: ObjectData) -> Vec<u8> {
+        data.stream
+            .try_fold(vec![], |mut acc, chunk| async move {
+                acc.extend_from_slice(&chunk);
+                Ok(acc)
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn put_get_remove() {
+        let (storage, root) = temp_storage();
+
+        storage
+            .put("ab/cd/object", stream_of(&[b"hello ", b"world"]))
+            .await
+            .unwrap();
+        assert!(storage.exists("ab/cd/object").await.unwrap());
+
+        let data = storage.get("ab/cd/object", None).await.unwrap().unwrap();
+        assert_eq!(data.size, 11);
+        assert_eq!(data.range, 0..11);
+        assert_eq!(read_all(data).await, b"hello world");
+
+        let data = storage
+            .get("ab/cd/object", Some(6..11))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(data.range, 6..11);
+        assert_eq!(read_all(data).await, b"world");
+
+        assert!(
+            storage
+                .put("ab/cd/object", stream_of(&[b"x"]))
+                .await
+                .is_err()
+        );
+
+        storage.remove("ab/cd/object").await.unwrap();
+        storage.remove("ab/cd/object").await.unwrap();
+        assert!(!storage.exists("ab/cd/object").await.unwrap());
+        assert!(storage.get("ab/cd/object", None).await.unwrap().is_none());
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn put_file() {
+        let (storage, root) = temp_storage();
+        let from = storage.root.clone().join("temp").into_string();
+        tokio::fs::create_dir_all(&root).await.unwrap();
+        tokio::fs::write(&from, b"content").await.unwrap();
+
+        storage.put_file("ab/cd/object", &from).await.unwrap();
+        let data = storage.get("ab/cd/object", None).await.unwrap().unwrap();
+        assert_eq!(read_all(data).await, b"content");
+
+        tokio::fs::remove_dir_all(root).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reject_unsafe_key() {
+        let (storage, _) = temp_storage();
+        assert!(storage.exists("../object").await.is_err());
+    }
+}
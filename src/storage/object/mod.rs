@@ -0,0 +1,170 @@
+mod local;
+mod s3;
+
+pub use local::LocalObjectStorage;
+pub use s3::S3ObjectStorage;
+
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::OnceLock;
+
+use boluo::BoxError;
+use boluo::body::Bytes;
+use futures_util::{Stream, TryStreamExt};
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use crate::config::{ResourceConfig, ResourceStorageConfig};
+
+/// 文件读取的分块大小
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// 对象数据流
+pub type ObjectStream = Pin<Box<dyn Stream<Item = Result<Bytes, BoxError>> + Send>>;
+
+/// 读取到的对象数据
+pub struct ObjectData {
+    /// 对象总大小
+    pub size: u64,
+    /// 本次读取的字节区间
+    pub range: Range<u64>,
+    /// 对象数据流
+    pub stream: ObjectStream,
+}
+
+pub trait ObjectStorage: Send + Sync {
+    /// 以流的方式写入对象
+    fn put(
+        &self,
+        key: &str,
+        stream: ObjectStream,
+    ) -> impl Future<Output = anyhow::Result<()>> + Send;
+
+    /// 将本地文件写入对象，写入成功后本地文件可能会被移走
+    fn put_file(&self, key: &str, path: &str) -> impl Future<Output = anyhow::Result<()>> + Send {
+        async move { self.put(key, read_file_stream(path).await?).await }
+    }
+
+    /// 读取对象，可指定读取的字节区间，对象不存在时返回 `None`
+    fn get(
+        &self,
+        key: &str,
+        range: Option<Range<u64>>,
+    ) -> impl Future<Output = anyhow::Result<Option<ObjectData>>> + Send;
+
+    /// 判断对象是否存在
+    fn exists(&self, key: &str) -> impl Future<Output = anyhow::Result<bool>> + Send;
+
+    /// 删除对象，对象不存在时不会返回错误
+    fn remove(&self, key: &str) -> impl Future<Output = anyhow::Result<()>> + Send;
+}
+
+/// 根据配置选择的对象存储器
+pub enum AnyObjectStorage {
+    Local(LocalObjectStorage),
+    S3(S3ObjectStorage),
+}
+
+impl AnyObjectStorage {
+    pub fn from_config(config: &ResourceConfig) -> anyhow::Result<Self> {
+        Ok(match &config.storage {
+            ResourceStorageConfig::Local => {
+                Self::Local(LocalObjectStorage::new(&config.upload_dir))
+            }
+            ResourceStorageConfig::S3(s3) => Self::S3(S3ObjectStorage::from_config(s3)?),
+        })
+    }
+}
+
+impl ObjectStorage for AnyObjectStorage {
+    async fn put(&self, key: &str, stream: ObjectStream) -> anyhow::Result<()> {
+        match self {
+            Self::Local(storage) => storage.put(key, stream).await,
+            Self::S3(storage) => storage.put(key, stream).await,
+        }
+    }
+
+    async fn put_file(&self, key: &str, path: &str) -> anyhow::Result<()> {
+        match self {
+            Self::Local(storage) => storage.put_file(key, path).await,
+            Self::S3(storage) => storage.put_file(key, path).await,
+        }
+    }
+
+    async fn get(
+        &self,
+        key: &str,
+        range: Option<Range<u64>>,
+    ) -> anyhow::Result<Option<ObjectData>> {
+        match self {
+            Self::Local(storage) => storage.get(key, range).await,
+            Self::S3(storage) => storage.get(key, range).await,
+        }
+    }
+
+    async fn exists(&self, key: &str) -> anyhow::Result<bool> {
+        match self {
+            Self::Local(storage) => storage.exists(key).await,
+            Self::S3(storage) => storage.exists(key).await,
+        }
+    }
+
+    async fn remove(&self, key: &str) -> anyhow::Result<()> {
+        match self {
+            Self::Local(storage) => storage.remove(key).await,
+            Self::S3(storage) => storage.remove(key).await,
+        }
+    }
+}
+
+static STORAGE: OnceLock<AnyObjectStorage> = OnceLock::new();
+
+pub fn init(config: &ResourceConfig) -> anyhow::Result<()> {
+    STORAGE
+        .set(AnyObjectStorage::from_config(config)?)
+        .map_err(|_| anyhow::anyhow!("重复初始化对象存储器"))
+}
+
+pub fn get() -> &'static AnyObjectStorage {
+    STORAGE.get().expect("对象存储器未初始化")
+}
+
+/// 以流的方式读取本地文件的指定区间
+async fn read_file_range_stream(
+    path: &str,
+    range: Option<Range<u64>>,
+) -> std::io::Result<(u64, Range<u64>, ObjectStream)> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let size = file.metadata().await?.len();
+    let range = range.unwrap_or(0..size);
+    if range.start > range.end || range.end > size {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("读取区间超出文件范围（区间：{range:?}，文件大小：{size}）"),
+        ));
+    }
+    file.seek(std::io::SeekFrom::Start(range.start)).await?;
+
+    let remaining = range.end - range.start;
+    let stream =
+        futures_util::stream::try_unfold((file, remaining), |(mut file, remaining)| async move {
+            if remaining == 0 {
+                return Ok(None);
+            }
+            let len =
+                usize::try_from(remaining).map_or(READ_CHUNK_SIZE, |r| r.min(READ_CHUNK_SIZE));
+            let mut buf = vec![0; len];
+            let n = file.read(&mut buf).await?;
+            if n == 0 {
+                return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
+            }
+            buf.truncate(n);
+            Ok(Some((Bytes::from(buf), (file, remaining - n as u64))))
+        });
+
+    Ok((size, range, Box::pin(stream.map_err(BoxError::from))))
+}
+
+/// 以流的方式读取整个本地文件
+async fn read_file_stream(path: &str) -> anyhow::Result<ObjectStream> {
+    Ok(read_file_range_stream(path, None).await?.2)
+}
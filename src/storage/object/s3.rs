@@ -0,0 +1,185 @@
+use std::ops::Range;
+
+use boluo::BoxError;
+use futures_util::{StreamExt, TryStreamExt};
+use object_store::aws::{AmazonS3, AmazonS3Builder};
+use object_store::path::Path as ObjectPath;
+use object_store::{GetOptions, ObjectStore, WriteMultipart};
+
+use crate::config::S3StorageConfig;
+use crate::storage::object::{ObjectData, ObjectStorage, ObjectStream};
+use crate::util::path::Root;
+
+/// 分片上传的最大并发数
+const MAX_UPLOAD_CONCURRENCY: usize = 4;
+
+/// S3 兼容对象存储器
+pub struct S3ObjectStorage {
+    /// 存储客户端
+    store: AmazonS3,
+    /// 对象键前缀
+    prefix: Root,
+}
+
+impl S3ObjectStorage {
+    pub fn from_config(config: &S3StorageConfig) -> anyhow::Result<Self> {
+        let mut builder = AmazonS3Builder::new()
+            .with_bucket_name(&config.bucket)
+            .with_region(&config.region)
+            .with_access_key_id(&config.access_key_id)
+            .with_secret_access_key(&config.secret_access_key)
+            .with_allow_http(config.allow_http)
+            .with_virtual_hosted_style_request(config.virtual_hosted_style);
+        if let Some(endpoint) = &config.endpoint {
+            builder = builder.with_endpoint(endpoint);
+        }
+        Ok(Self {
+            store: builder.build()?,
+            prefix: crate::util::path::root(config.prefix.as_str()),
+        })
+    }
+
+    fn location(&self, key: &str) -> anyhow::Result<ObjectPath> {
+        let key = self.prefix.clone().join(key).into_string();
+        ObjectPath::parse(&key).map_err(|e| anyhow::anyhow!("对象键格式错误（键：{key}）：{e}"))
+    }
+
+    async fn write_stream(
+        writer: &mut WriteMultipart,
+        mut stream: ObjectStream,
+    ) -> anyhow::Result<()> {
+        while let Some(chunk) = stream
+            .next()
+            .await
+            .transpose()
+            .map_err(|e| anyhow::anyhow!(e))?
+        {
+            writer.wait_for_capacity(MAX_UPLOAD_CONCURRENCY).await?;
+            writer.put(chunk);
+        }
+        Ok(())
+    }
+}
+
+impl ObjectStorage for S3ObjectStorage {
+    async fn put(&self, key: &str, stream: ObjectStream) -> anyhow::Result<()> {
+        let location = self.location(key)?;
+        let upload = self.store.put_multipart(&location).await?;
+        let mut writer = WriteMultipart::new(upload);
+        if let Err(e) = Self::write_stream(&mut writer, stream).await {
+            if let Err(e) = writer.abort().await {
+                tracing::warn!("分片上传取消失败，对象：{location}，错误：{e}");
+            }
+            return Err(e);
+        }
+        writer.finish().await?;
+        Ok(())
+    }
+
+    async fn get(
+        &self,
+        key: &str,
+        range: Option<Range<u64>>,
+    ) -> anyhow::Result<Option<ObjectData>> {
+        let location = self.location(key)?;
+        let options = GetOptions {
+            range: range.map(From::from),
+            ..Default::default()
+        };
+        match self.store.get_opts(&location, options).await {
+            Ok(result) => Ok(Some(ObjectData {
+                size: result.meta.size,
+                range: result.range.clone(),
+                stream: Box::pin(result.into_stream().map_err(BoxError::from)),
+            })),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn exists(&self, key: &str) -> anyhow::Result<bool> {
+        let location = self.location(key)?;
+        match self.store.head(&location).await {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn remove(&self, key: &str) -> anyhow::Result<()> {
+        let location = self.location(key)?;
+        match self.store.delete(&location).await {
+            Ok(()) => Ok(()),
+            Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+// 需要一个 S3 兼容服务（例如本地运行的 MinIO）才能执行，通过以下环境变量配置：
+// TEST_S3_ENDPOINT、TEST_S3_BUCKET、TEST_S3_ACCESS_KEY_ID、TEST_S3_SECRET_ACCESS_KEY
+// 未配置时跳过测试
+#[cfg(test)]
+mod tests {
+    use boluo::body::Bytes;
+
+    use super::*;
+
+    fn test_storage() -> Option<S3ObjectStorage> {
+        let env = |name| std::env::var(name).ok();
+        let config = S3StorageConfig {
+            endpoint: Some(env("TEST_S3_ENDPOINT")?),
+            bucket: env("TEST_S3_BUCKET")?,
+            region: env("TEST_S3_REGION").unwrap_or_else(|| "us-east-1".to_owned()),
+            access_key_id: env("TEST_S3_ACCESS_KEY_ID")?,
+            secret_access_key: env("TEST_S3_SECRET_ACCESS_KEY")?,
+            prefix: format!("test/{}", crate::util::uuid::v4()),
+            allow_http: true,
+            virtual_hosted_style: false,
+        };
+        Some(S3ObjectStorage::from_config(&config).unwrap())
+    }
+
+    async fn read_all(data: ObjectData) -> Vec<u8> {
+        data.stream
+            .try_fold(vec![], |mut acc, chunk| async move {
+                acc.extend_from_slice(&chunk);
+                Ok(acc)
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn put_get_remove() {
+        let Some(storage) = test_storage() else {
+            return;
+        };
+
+        let chunks: Vec<Result<Bytes, BoxError>> = vec![
+            Ok(Bytes::from_static(b"hello ")),
+            Ok(Bytes::from_static(b"world")),
+        ];
+        storage
+            .put("ab/cd/object", Box::pin(futures_util::stream::iter(chunks)))
+            .await
+            .unwrap();
+        assert!(storage.exists("ab/cd/object").await.unwrap());
+
+        let data = storage.get("ab/cd/object", None).await.unwrap().unwrap();
+        assert_eq!(data.size, 11);
+        assert_eq!(read_all(data).await, b"hello world");
+
+        let data = storage
+            .get("ab/cd/object", Some(6..11))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(data.range, 6..11);
+        assert_eq!(read_all(data).await, b"world");
+
+        storage.remove("ab/cd/object").await.unwrap();
+        assert!(!storage.exists("ab/cd/object").await.unwrap());
+        assert!(storage.get("ab/cd/object", None).await.unwrap().is_none());
+    }
+}
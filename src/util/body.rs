@@ -0,0 +1,58 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use boluo::BoxError;
+use boluo::body::{Bytes, Frame, HttpBody, SizeHint};
+use futures_util::{Stream, StreamExt};
+
+/// 基于数据流的 HTTP 主体
+pub struct StreamBody {
+    stream: Pin<Box<dyn Stream<Item = Result<Bytes, BoxError>> + Send>>,
+    /// 剩余未读取的字节数
+    remaining: Option<u64>,
+}
+
+impl StreamBody {
+    pub fn new(stream: Pin<Box<dyn Stream<Item = Result<Bytes, BoxError>> + Send>>) -> Self {
+        Self {
+            stream,
+            remaining: None,
+        }
+    }
+
+    /// 设置数据流的总字节数，用于提供准确的大小提示
+    pub fn with_size(mut self, size: u64) -> Self {
+        self.remaining = Some(size);
+        self
+    }
+}
+
+impl HttpBody for StreamBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        match this.stream.poll_next_unpin(cx) {
+            Poll::Ready(Some(Ok(data))) => {
+                if let Some(remaining) = &mut this.remaining {
+                    *remaining = remaining.saturating_sub(data.len() as u64);
+                }
+                Poll::Ready(Some(Ok(Frame::data(data))))
+            }
+            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match self.remaining {
+            Some(remaining) => SizeHint::with_exact(remaining),
+            None => SizeHint::default(),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+//! 从附件中提取可供全文索引的文本
+
+/// 判断能否从该类型的文件中提取文本
+pub fn is_supported(mime_type: &str) -> bool {
+    mime_type.starts_with("text/") || mime_type == "application/pdf"
+}
+
+/// 从文件数据中提取文本，文本超出 `max_size` 字节时会被截断，不支持的文件类型返回 `None`
+pub async fn extract_text(
+    data: Vec<u8>,
+    mime_type: &str,
+    max_size: usize,
+) -> anyhow::Result<Option<String>> {
+    let text = if mime_type == "application/pdf" {
+        // PDF 解析耗时较长，且可能因文件损坏而 panic，放到阻塞线程中执行
+        tokio::task::spawn_blocking(move || pdf_extract::extract_text_from_mem(&data))
+            .await
+            .map_err(|e| anyhow::anyhow!("PDF 文本提取异常终止：{e}"))??
+    } else if mime_type.starts_with("text/") {
+        let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&data);
+        String::from_utf8_lossy(data).into_owned()
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(normalize(&text, max_size)))
+}
+
+/// 合并连续的空白符，并将文本截断到不超过 `max_size` 字节
+fn normalize(text: &str, max_size: usize) -> String {
+    let mut normalized = String::new();
+    for word in text.split_whitespace() {
+        if !normalized.is_empty() {
+            if normalized.len() + 1 >= max_size {
+                break;
+            }
+            normalized.push(' ');
+        }
+        let mut end = word.len().min(max_size - normalized.len());
+        while !word.is_char_boundary(end) {
+            end -= 1;
+        }
+        normalized.push_str(&word[..end]);
+        if end < word.len() {
+            break;
+        }
+    }
+    normalized.truncate(normalized.trim_end().len());
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_text() {
+        assert_eq!(normalize("  hello \n\t world  ", 100), "hello world");
+        assert_eq!(normalize("hello world foo", 12), "hello world");
+        assert_eq!(normalize("hello world", 8), "hello wo");
+        assert_eq!(normalize("你好 世界", 8), "你好");
+        assert_eq!(normalize("你好世界", 8), "你好");
+    }
+
+    #[tokio::test]
+    async fn extract_plain_text() {
+        let data = b"\xEF\xBB\xBF# Title\n\nbody".to_vec();
+        assert_eq!(
+            extract_text(data, "text/plain", 100)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some("# Title body")
+        );
+        assert_eq!(
+            extract_text(vec![0, 1, 2], "application/octet-stream", 100)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! 全文检索的辅助函数
+
+use std::borrow::Cow;
+
+use jiff::civil::Date;
+use jiff::tz::TimeZone;
+
+/// 高亮开始标记，使用私有区字符以免与正文内容冲突
+pub const HIGHLIGHT_START: &str = "\u{E000}";
+/// 高亮结束标记
+pub const HIGHLIGHT_END: &str = "\u{E001}";
+/// 正文片段被截断处使用的省略符
+pub const SNIPPET_ELLIPSIS: &str = "…";
+/// 正文片段的最大词元数量
+pub const SNIPPET_TOKENS: u32 = 48;
+/// 写入全文索引时插入到相邻中日韩文字之间的零宽空格，使按空白与标点分词的分词器逐字切分中日韩文字
+pub const TOKEN_SEPARATOR: char = '\u{200B}';
+
+/// 将带高亮标记的文本转换为 HTML，文本中的其他内容会被转义，高亮部分使用 `<mark>` 包裹
+pub fn highlight_to_html(text: &str) -> String {
+    let mut html = String::with_capacity(text.len());
+    let mut in_mark = false;
+    for c in text.chars() {
+        match c {
+            '\u{E000}' if !in_mark => {
+                html.push_str("<mark>");
+                in_mark = true;
+            }
+            '\u{E001}' if in_mark => {
+                html.push_str("</mark>");
+                in_mark = false;
+            }
+            '\u{E000}' | '\u{E001}' | TOKEN_SEPARATOR => {}
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#x27;"),
+            c => html.push(c),
+        }
+    }
+    if in_mark {
+        html.push_str("</mark>");
+    }
+    html
+}
+
+/// 搜索词限定的字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchColumn {
+    /// 标题
+    Title,
+    /// 正文
+    Content,
+}
+
+impl SearchColumn {
+    /// 全文索引中的列名
+    pub fn column_name(self) -> &'static str {
+        match self {
+            SearchColumn::Title => "title",
+            SearchColumn::Content => "plain_content",
+        }
+    }
+}
+
+/// 搜索词
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchTerm {
+    /// 搜索内容
+    pub text: String,
+    /// 是否为精确匹配的短语
+    pub phrase: bool,
+    /// 限定的字段，为空时匹配所有字段
+    pub column: Option<SearchColumn>,
+    /// 是否排除匹配的文章
+    pub exclude: bool,
+}
+
+/// 解析后的搜索语句
+///
+/// 支持的语法：
+///
+/// - `foo`：匹配关键词，关键词会经过分词
+/// - `"exact phrase"`：精确匹配短语
+/// - `-foo`、`-"exact phrase"`：排除匹配的文章
+/// - `title:foo`、`content:foo`：仅匹配标题或正文，可以与短语、排除组合使用
+/// - `tag:foo`：文章暂不支持标签，按普通关键词处理
+/// - `after:2024-01-01`、`before:2024-02-01`：发布日期（UTC）不早于、早于指定日期
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchQuery {
+    /// 搜索词
+    pub terms: Vec<SearchTerm>,
+    /// 发布日期不早于
+    pub after: Option<Date>,
+    /// 发布日期早于
+    pub before: Option<Date>,
+}
+
+impl SearchQuery {
+    /// 解析搜索语句，语法错误时将整个输入作为普通关键词处理
+    pub fn parse(input: &str) -> Self {
+        Self::try_parse(input).unwrap_or_else(|| {
+            let mut query = Self::default();
+            query.push_term(input.trim().to_owned(), false, None, false);
+            query
+        })
+    }
+
+    /// 需要匹配的搜索词
+    pub fn includes(&self) -> impl Iterator<Item = &SearchTerm> {
+        self.terms.iter().filter(|term| !term.exclude)
+    }
+
+    /// 需要排除的搜索词
+    pub fn excludes(&self) -> impl Iterator<Item = &SearchTerm> {
+        self.terms.iter().filter(|term| term.exclude)
+    }
+
+    /// 发布时间范围（大于等于，小于）的 Unix 时间戳
+    pub fn published_at_range(&self) -> anyhow::Result<(Option<i64>, Option<i64>)> {
+        let timestamp = |date: Date| -> anyhow::Result<i64> {
+            Ok(date.to_zoned(TimeZone::UTC)?.timestamp().as_second())
+        };
+        Ok((
+            self.after.map(timestamp).transpose()?,
+            self.before.map(timestamp).transpose()?,
+        ))
+    }
+
+    fn try_parse(input: &str) -> Option<Self> {
+        let mut query = Self::default();
+        let mut chars = input.chars().peekable();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.peek().is_none() {
+                return Some(query);
+            }
+
+            let exclude = chars.next_if_eq(&'-').is_some();
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '"') {
+                word.push(c);
+            }
+
+            let (field, text, phrase) = if chars.next_if_eq(&'"').is_some() {
+                // 引号只能出现在搜索词开头或字段名之后
+                let field = match word.strip_suffix(':') {
+                    Some(field) => Some(field.to_owned()),
+                    None if word.is_empty() => None,
+                    None => return None,
+                };
+                let mut text = String::new();
+                loop {
+                    match chars.next()? {
+                        '"' => break,
+                        c => text.push(c),
+                    }
+                }
+                if chars.peek().is_some_and(|c| !c.is_whitespace()) {
+                    return None;
+                }
+                (field, text, true)
+            } else {
+                let field = word
+                    .split_once(':')
+                    .filter(|(field, _)| Self::is_field(field))
+                    .map(|(field, text)| (field.to_owned(), text.to_owned()));
+                match field {
+                    Some((field, text)) => (Some(field), text, false),
+                    None => (None, word, false),
+                }
+            };
+
+            match field.as_deref() {
+                None | Some("tag") => query.push_term(text, phrase, None, exclude),
+                Some("title") => query.push_term(text, phrase, Some(SearchColumn::Title), exclude),
+                Some("content") => {
+                    query.push_term(text, phrase, Some(SearchColumn::Content), exclude)
+                }
+                Some("after") if !exclude && !phrase => query.after = Some(text.parse().ok()?),
+                Some("before") if !exclude && !phrase => query.before = Some(text.parse().ok()?),
+                Some(_) => return None,
+            }
+        }
+    }
+
+    fn is_field(name: &str) -> bool {
+        matches!(name, "title" | "content" | "tag" | "after" | "before")
+    }
+
+    /// 添加搜索词，忽略不包含文字、数字的搜索词
+    fn push_term(
+        &mut self,
+        text: String,
+        phrase: bool,
+        column: Option<SearchColumn>,
+        exclude: bool,
+    ) {
+        if text.chars().any(char::is_alphanumeric) {
+            self.terms.push(SearchTerm {
+                text,
+                phrase,
+                column,
+                exclude,
+            });
+        }
+    }
+}
+
+/// 将关键词切分为用于全文检索的片段，片段在空白、标点以及中日韩文字与其他文字的交界处切分
+pub fn segment_keyword(text: &str) -> Vec<&str> {
+    let mut segments = vec![];
+    let mut start = None;
+    let mut prev_cjk = false;
+    for (i, c) in text.char_indices() {
+        if !c.is_alphanumeric() {
+            if let Some(start) = start.take() {
+                segments.push(&text[start..i]);
+            }
+            continue;
+        }
+        let cjk = is_cjk(c);
+        match start {
+            Some(s) if cjk != prev_cjk => {
+                segments.push(&text[s..i]);
+                start = Some(i);
+            }
+            Some(_) => {}
+            None => start = Some(i),
+        }
+        prev_cjk = cjk;
+    }
+    if let Some(start) = start {
+        segments.push(&text[start..]);
+    }
+    segments
+}
+
+/// 在相邻的中日韩文字之间插入 [`TOKEN_SEPARATOR`]
+pub fn separate_cjk(text: &str) -> Cow<'_, str> {
+    let adjacent = text
+        .chars()
+        .zip(text.chars().skip(1))
+        .any(|(a, b)| is_cjk(a) && is_cjk(b));
+    if !adjacent {
+        return Cow::Borrowed(text);
+    }
+    let mut separated = String::with_capacity(text.len() * 2);
+    let mut prev_cjk = false;
+    for c in text.chars() {
+        let cjk = is_cjk(c);
+        if prev_cjk && cjk {
+            separated.push(TOKEN_SEPARATOR);
+        }
+        separated.push(c);
+        prev_cjk = cjk;
+    }
+    Cow::Owned(separated)
+}
+
+/// 移除 [`separate_cjk`] 插入的 [`TOKEN_SEPARATOR`]
+pub fn strip_separators(text: &str) -> Cow<'_, str> {
+    if text.contains(TOKEN_SEPARATOR) {
+        Cow::Owned(text.replace(TOKEN_SEPARATOR, ""))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// 判断字符是否为中日韩文字
+pub fn is_cjk(c: char) -> bool {
+    matches!(
+        c,
+        '\u{3040}'..='\u{30FF}'
+            | '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{AC00}'..='\u{D7AF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{2FA1F}'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(text: &str, phrase: bool, column: Option<SearchColumn>, exclude: bool) -> SearchTerm {
+        SearchTerm {
+            text: text.to_owned(),
+            phrase,
+            column,
+            exclude,
+        }
+    }
+
+    #[test]
+    fn highlight_html() {
+        assert_eq!(
+            highlight_to_html("Rust \u{E000}异步\u{E001}编程"),
+            "Rust <mark>异步</mark>编程"
+        );
+        assert_eq!(
+            highlight_to_html("<b>\u{E000}a&b\u{E001}</b>"),
+            "&lt;b&gt;<mark>a&amp;b</mark>&lt;/b&gt;"
+        );
+        assert_eq!(highlight_to_html("\u{E001}a\u{E000}b"), "a<mark>b</mark>");
+    }
+
+    #[test]
+    fn parse_query() {
+        let query = SearchQuery::parse(
+            r#"rust "async io" -java title:tokio -content:"hello world" tag:web after:2024-01-01 before:2024-02-01"#,
+        );
+        assert_eq!(
+            query.terms,
+            vec![
+                term("rust", false, None, false),
+                term("async io", true, None, false),
+                term("java", false, None, true),
+                term("tokio", false, Some(SearchColumn::Title), false),
+                term("hello world", true, Some(SearchColumn::Content), true),
+                term("web", false, None, false),
+            ]
+        );
+        assert_eq!(query.after, Some(jiff::civil::date(2024, 1, 1)));
+        assert_eq!(query.before, Some(jiff::civil::date(2024, 2, 1)));
+
+        // 未知字段按普通关键词处理，不包含文字、数字的搜索词被忽略
+        let query = SearchQuery::parse("http://example.com - --");
+        assert_eq!(
+            query.terms,
+            vec![term("http://example.com", false, None, false)]
+        );
+    }
+
+    #[test]
+    fn parse_malformed_query() {
+        for input in [
+            r#""unterminated phrase"#,
+            r#"foo"bar""#,
+            r#""foo"bar"#,
+            "after:2024-13-01",
+            "-after:2024-01-01",
+            r#"unknown:"foo""#,
+        ] {
+            let query = SearchQuery::parse(input);
+            assert_eq!(query.terms, vec![term(input, false, None, false)]);
+            assert_eq!(query.after, None);
+        }
+    }
+
+    #[test]
+    fn segment_keyword_by_script() {
+        assert_eq!(segment_keyword("Rust异步编程"), vec!["Rust", "异步编程"]);
+        assert_eq!(
+            segment_keyword("  tokio::spawn 使用-指南 "),
+            vec!["tokio", "spawn", "使用", "指南"]
+        );
+        assert_eq!(segment_keyword("v2版本"), vec!["v2", "版本"]);
+        assert!(segment_keyword("--").is_empty());
+    }
+
+    #[test]
+    fn separate_cjk_characters() {
+        assert_eq!(
+            separate_cjk("Rust 异步编程"),
+            "Rust 异\u{200B}步\u{200B}编\u{200B}程"
+        );
+        assert_eq!(separate_cjk("异步 io 编程"), "异\u{200B}步 io 编\u{200B}程");
+        assert_eq!(separate_cjk("异a步"), "异a步");
+        assert!(matches!(separate_cjk("plain text 中"), Cow::Borrowed(_)));
+        assert_eq!(strip_separators(&separate_cjk("异步编程")), "异步编程");
+        assert_eq!(
+            highlight_to_html(&separate_cjk("\u{E000}异步\u{E001}编程")),
+            "<mark>异步</mark>编程"
+        );
+    }
+}
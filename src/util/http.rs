@@ -1,7 +1,8 @@
 use std::fmt::Display;
 use std::str::FromStr;
 
-use boluo::http::HeaderMap;
+use boluo::http::uri::Authority;
+use boluo::http::{HeaderMap, Uri};
 
 pub fn typed_header<T>(headers: &HeaderMap, header_name: &str) -> Result<T, String>
 where
@@ -32,3 +33,100 @@ where
             )
         })
 }
+
+/// 生成符合 RFC 6266 的 `Content-Disposition` 响应头的值
+///
+/// `filename` 参数仅保留 ASCII 字符作为兼容旧客户端的回退值，完整文件名通过 `filename*` 参数以 UTF-8 编码传递。
+pub fn content_disposition(disposition: &str, filename: &str) -> String {
+    let fallback = filename
+        .chars()
+        .map(|c| match c {
+            '"' | '\\' => '_',
+            c if c.is_ascii() && !c.is_ascii_control() => c,
+            _ => '_',
+        })
+        .collect::<String>();
+    let mut encoded = String::with_capacity(filename.len());
+    for byte in filename.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'!'
+            | b'#'
+            | b'$'
+            | b'&'
+            | b'+'
+            | b'-'
+            | b'.'
+            | b'^'
+            | b'_'
+            | b'`'
+            | b'|'
+            | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    format!("{disposition}; filename=\"{fallback}\"; filename*=UTF-8''{encoded}")
+}
+
+/// 提取来源页面的域名，来源无法解析或与当前站点相同时返回 `None`
+pub fn referrer_domain(referer: &str, host: Option<&str>) -> Option<String> {
+    let uri = referer.parse::<Uri>().ok()?;
+    if !matches!(uri.scheme_str(), Some("http" | "https")) {
+        return None;
+    }
+    let domain = uri.host()?.to_ascii_lowercase();
+    let is_same_site = host
+        .and_then(|host| host.parse::<Authority>().ok())
+        .is_some_and(|host| host.host().eq_ignore_ascii_case(&domain));
+    (!is_same_site).then_some(domain)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_disposition_ascii() {
+        assert_eq!(
+            content_disposition("attachment", "report.pdf"),
+            r#"attachment; filename="report.pdf"; filename*=UTF-8''report.pdf"#
+        );
+        assert_eq!(
+            content_disposition("inline", r#"a "b"\c d.txt"#),
+            r#"inline; filename="a _b__c d.txt"; filename*=UTF-8''a%20%22b%22%5Cc%20d.txt"#
+        );
+    }
+
+    #[test]
+    fn content_disposition_utf8() {
+        assert_eq!(
+            content_disposition("attachment", "报告.pdf"),
+            r#"attachment; filename="__.pdf"; filename*=UTF-8''%E6%8A%A5%E5%91%8A.pdf"#
+        );
+    }
+
+    #[test]
+    fn referrer_domain_external() {
+        assert_eq!(
+            referrer_domain("https://www.Google.com/search?q=x", Some("example.com")),
+            Some("www.google.com".to_owned())
+        );
+        assert_eq!(
+            referrer_domain("http://news.ycombinator.com:8080/item", None),
+            Some("news.ycombinator.com".to_owned())
+        );
+    }
+
+    #[test]
+    fn referrer_domain_ignored() {
+        assert_eq!(
+            referrer_domain("https://example.com/articles", Some("Example.com:443")),
+            None
+        );
+        assert_eq!(referrer_domain("android-app://com.example", None), None);
+        assert_eq!(referrer_domain("/articles", None), None);
+        assert_eq!(referrer_domain("not a url", None), None);
+    }
+}
@@ -0,0 +1,105 @@
+use mime::Mime;
+
+/// 探测文件类型时读取的最大字节数
+pub const SNIFF_LEN: usize = 8192;
+
+/// 根据文件开头的内容探测 MIME 类型
+pub fn sniff(head: &[u8]) -> &'static str {
+    if let Some(kind) = infer::get(head) {
+        return kind.mime_type();
+    }
+
+    let text = head.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(head);
+    let text = text.trim_ascii_start();
+    let text = &text[..text.len().min(64)].to_ascii_lowercase();
+    if text.starts_with(b"<svg") {
+        return "image/svg+xml";
+    }
+    if [
+        b"<!doctype html".as_slice(),
+        b"<html",
+        b"<head",
+        b"<body",
+        b"<script",
+        b"<iframe",
+    ]
+    .iter()
+    .any(|tag| text.starts_with(tag))
+    {
+        return "text/html";
+    }
+
+    match std::str::from_utf8(head) {
+        Ok(_) => "text/plain",
+        // 截断位置可能位于多字节字符中间
+        Err(e) if e.error_len().is_none() => "text/plain",
+        Err(_) => "application/octet-stream",
+    }
+}
+
+/// 判断探测结果是否为无法识别具体类型时使用的通用类型
+pub fn is_unrecognized(mime_type: &str) -> bool {
+    matches!(mime_type, "text/plain" | "application/octet-stream")
+}
+
+/// 判断该类型的文件能否安全地在浏览器中直接打开
+///
+/// 可能包含脚本的类型（如 HTML、SVG、XML）需要以附件形式下载。
+pub fn is_inline_safe(mime_type: &str) -> bool {
+    let Ok(mime) = mime_type.parse::<Mime>() else {
+        return false;
+    };
+    match (mime.type_(), mime.subtype()) {
+        (mime::IMAGE, subtype) => subtype != mime::SVG,
+        (mime::AUDIO, _) | (mime::VIDEO, _) => true,
+        (mime::TEXT, mime::PLAIN) => true,
+        (mime::APPLICATION, mime::PDF) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sniff_binary() {
+        assert_eq!(sniff(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR"), "image/png");
+        assert_eq!(sniff(b"%PDF-1.7\n"), "application/pdf");
+        assert_eq!(sniff(b"\x80\x81\x82\x83\x84"), "application/octet-stream");
+    }
+
+    #[test]
+    fn sniff_text() {
+        assert_eq!(sniff(b"hello world"), "text/plain");
+        assert_eq!(sniff("你好".as_bytes()), "text/plain");
+        assert_eq!(sniff(&"你好".as_bytes()[..4]), "text/plain");
+        assert_eq!(sniff(b"  <!DOCTYPE html><html></html>"), "text/html");
+        assert_eq!(sniff(b"\xEF\xBB\xBF<script>alert(1)</script>"), "text/html");
+        assert_eq!(
+            sniff(br#"<svg xmlns="http://www.w3.org/2000/svg"></svg>"#),
+            "image/svg+xml"
+        );
+    }
+
+    #[test]
+    fn unrecognized() {
+        assert!(is_unrecognized(sniff(b"hello world")));
+        assert!(is_unrecognized(sniff(b"\x80\x81\x82\x83\x84")));
+        assert!(!is_unrecognized(sniff(b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR")));
+        assert!(!is_unrecognized(sniff(b"<html></html>")));
+    }
+
+    #[test]
+    fn inline_safe() {
+        assert!(is_inline_safe("image/png"));
+        assert!(is_inline_safe("video/mp4"));
+        assert!(is_inline_safe("text/plain; charset=utf-8"));
+        assert!(is_inline_safe("application/pdf"));
+        assert!(!is_inline_safe("image/svg+xml"));
+        assert!(!is_inline_safe("text/html"));
+        assert!(!is_inline_safe("application/xhtml+xml"));
+        assert!(!is_inline_safe("application/octet-stream"));
+        assert!(!is_inline_safe("invalid"));
+    }
+}
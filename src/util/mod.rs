@@ -1,9 +1,14 @@
+pub mod body;
+pub mod extract;
+pub mod fts;
 pub mod http;
 pub mod join;
 pub mod jwt;
+pub mod mime;
 pub mod pagination;
 pub mod password;
 pub mod path;
+pub mod range;
 pub mod result;
 pub mod serde;
 pub mod sqlx;
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -125,6 +126,24 @@ impl<D> PageData<HashSet<D>> {
     }
 }
 
+/// 将游标分页的位置编码为不透明的游标字符串
+pub fn encode_cursor<T: Serialize>(position: &T) -> anyhow::Result<String> {
+    let json = serde_json::to_vec(position)?;
+    Ok(json.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+/// 解码游标字符串，游标格式错误时返回 `None`
+pub fn decode_cursor<T: DeserializeOwned>(cursor: &str) -> Option<T> {
+    if !cursor.is_ascii() || cursor.len() % 2 != 0 {
+        return None;
+    }
+    let json = (0..cursor.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&cursor[i..i + 2], 16).ok())
+        .collect::<Option<Vec<_>>>()?;
+    serde_json::from_slice(&json).ok()
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct OptionalPage {
     /// 分页页码
@@ -339,3 +358,31 @@ impl Contains for Vec<u64> {
         format!("{self:?}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_round_trip() {
+        let position = (Some(1700000000_i64), "article".to_owned());
+        let cursor = encode_cursor(&position).unwrap();
+        assert!(cursor.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(
+            decode_cursor::<(Option<i64>, String)>(&cursor),
+            Some(position)
+        );
+    }
+
+    #[test]
+    fn decode_malformed_cursor() {
+        assert_eq!(decode_cursor::<(i64, String)>(""), None);
+        assert_eq!(decode_cursor::<(i64, String)>("abc"), None);
+        assert_eq!(decode_cursor::<(i64, String)>("zz"), None);
+        assert_eq!(decode_cursor::<(i64, String)>("中文"), None);
+        assert_eq!(
+            decode_cursor::<(i64, String)>(&encode_cursor(&"text").unwrap()),
+            None
+        );
+    }
+}
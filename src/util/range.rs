@@ -0,0 +1,106 @@
+use std::ops::Range;
+
+/// 请求的字节区间无法满足
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("请求的字节区间超出数据范围（数据大小：{size}）")]
+pub struct RangeNotSatisfiable {
+    /// 数据大小
+    pub size: u64,
+}
+
+/// 解析 HTTP `Range` 请求头，返回需要读取的字节区间
+///
+/// 仅支持单个区间，多个区间或格式错误时返回 `None`，即忽略该请求头返回完整数据。
+pub fn parse(header: &str, size: u64) -> Result<Option<Range<u64>>, RangeNotSatisfiable> {
+    let Some((unit, spec)) = header.trim().split_once('=') else {
+        return Ok(None);
+    };
+    if !unit.trim().eq_ignore_ascii_case("bytes") || spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Ok(None);
+    };
+    let (start, end) = (start.trim(), end.trim());
+
+    if start.is_empty() {
+        let Ok(suffix) = end.parse::<u64>() else {
+            return Ok(None);
+        };
+        if suffix == 0 || size == 0 {
+            return Err(RangeNotSatisfiable { size });
+        }
+        return Ok(Some(size.saturating_sub(suffix)..size));
+    }
+
+    let Ok(start) = start.parse::<u64>() else {
+        return Ok(None);
+    };
+    let end = if end.is_empty() {
+        None
+    } else {
+        match end.parse::<u64>() {
+            Ok(end) if end >= start => Some(end),
+            _ => return Ok(None),
+        }
+    };
+    if start >= size {
+        return Err(RangeNotSatisfiable { size });
+    }
+    let end = end.map_or(size, |end| end.saturating_add(1).min(size));
+    Ok(Some(start..end))
+}
+
+/// 生成 `Content-Range` 响应头的值
+pub fn content_range(range: &Range<u64>, size: u64) -> String {
+    if range.is_empty() {
+        format!("bytes */{size}")
+    } else {
+        format!("bytes {}-{}/{size}", range.start, range.end - 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_single_range() {
+        assert_eq!(parse("bytes=0-499", 1000), Ok(Some(0..500)));
+        assert_eq!(parse("bytes=500-", 1000), Ok(Some(500..1000)));
+        assert_eq!(parse("bytes=-200", 1000), Ok(Some(800..1000)));
+        assert_eq!(parse("bytes=-2000", 1000), Ok(Some(0..1000)));
+        assert_eq!(parse("bytes=900-2000", 1000), Ok(Some(900..1000)));
+        assert_eq!(parse(" Bytes = 1-1 ", 1000), Ok(Some(1..2)));
+    }
+
+    #[test]
+    fn ignore_unsupported_range() {
+        assert_eq!(parse("bytes=0-1,5-6", 1000), Ok(None));
+        assert_eq!(parse("items=0-1", 1000), Ok(None));
+        assert_eq!(parse("bytes=abc", 1000), Ok(None));
+        assert_eq!(parse("bytes=5-1", 1000), Ok(None));
+        assert_eq!(parse("bytes=a-1", 1000), Ok(None));
+        assert_eq!(parse("bytes=-", 1000), Ok(None));
+    }
+
+    #[test]
+    fn unsatisfiable_range() {
+        assert_eq!(
+            parse("bytes=1000-", 1000),
+            Err(RangeNotSatisfiable { size: 1000 })
+        );
+        assert_eq!(
+            parse("bytes=-0", 1000),
+            Err(RangeNotSatisfiable { size: 1000 })
+        );
+        assert_eq!(parse("bytes=0-", 0), Err(RangeNotSatisfiable { size: 0 }));
+        assert_eq!(parse("bytes=-1", 0), Err(RangeNotSatisfiable { size: 0 }));
+    }
+
+    #[test]
+    fn format_content_range() {
+        assert_eq!(content_range(&(0..500), 1000), "bytes 0-499/1000");
+        assert_eq!(content_range(&(0..0), 1000), "bytes */1000");
+    }
+}
@@ -5,6 +5,35 @@ pub fn error_is_unique_violation(err: &anyhow::Error) -> bool {
         .unwrap_or(false)
 }
 
-pub fn escape_like_special_chars(s: &str) -> String {
-    s.replace("%", "\\%").replace("_", "\\_")
+/// 计算前缀匹配的上界（不包含），用于将前缀匹配转换为可以使用索引的范围查询
+///
+/// 返回 `None` 表示没有上界，即所有不小于前缀的字符串都以该前缀开头。
+pub fn prefix_upper_bound(prefix: &str) -> Option<String> {
+    let mut chars = prefix.chars().collect::<Vec<_>>();
+    while let Some(last) = chars.pop() {
+        let next = (last as u32 + 1..=char::MAX as u32).find_map(char::from_u32);
+        if let Some(next) = next {
+            chars.push(next);
+            return Some(chars.into_iter().collect());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upper_bound_of_prefix() {
+        assert_eq!(prefix_upper_bound("abc").as_deref(), Some("abd"));
+        assert_eq!(prefix_upper_bound("a:").as_deref(), Some("a;"));
+        assert_eq!(
+            prefix_upper_bound("a\u{D7FF}").as_deref(),
+            Some("a\u{E000}")
+        );
+        assert_eq!(prefix_upper_bound("a\u{10FFFF}").as_deref(), Some("b"));
+        assert_eq!(prefix_upper_bound("\u{10FFFF}"), None);
+        assert_eq!(prefix_upper_bound(""), None);
+    }
 }
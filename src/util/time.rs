@@ -32,6 +32,11 @@ impl UnixTimestampSecs {
         Ok(t.strftime("%FT%TZ").to_string())
     }
 
+    pub fn into_str_date_utc(self) -> anyhow::Result<String> {
+        let t = Timestamp::from_second(self.as_i64())?;
+        Ok(t.strftime("%F").to_string())
+    }
+
     pub fn to_date_utc(self) -> anyhow::Result<Self> {
         self.to_date(TimeZone::UTC)
     }
@@ -0,0 +1,26 @@
+use crate::validator::ValidationError;
+
+/// 系列描述的最大长度（字符数）
+const DESCRIPTION_MAX_CHARS: usize = 500;
+
+pub fn validate_title(title: &str) -> Result<(), ValidationError> {
+    if title.trim().is_empty() {
+        return Err(ValidationError::validation("系列标题不能为空或仅包含空格"));
+    }
+    if title.len() > crate::config::get().article.title_max_size {
+        return Err(ValidationError::validation(format!(
+            "系列标题长度超出限制，最大允许 {} 字节",
+            crate::config::get().article.title_max_size
+        )));
+    }
+    Ok(())
+}
+
+pub fn validate_description(description: &str) -> Result<(), ValidationError> {
+    if description.chars().count() > DESCRIPTION_MAX_CHARS {
+        return Err(ValidationError::validation(format!(
+            "系列描述长度超出限制，最大允许 {DESCRIPTION_MAX_CHARS} 字符"
+        )));
+    }
+    Ok(())
+}
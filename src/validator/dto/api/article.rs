@@ -1,7 +1,7 @@
 use crate::model::dto::api::article::{
-    CreateArticleDto, DownloadArticleAttachmentDto, GetArticleDto, RemoveArticleAttachmentDto,
-    RemoveArticleDto, SearchArticleDto, UnlockArticleDto, UpdateArticleDto,
-    UploadArticleAttachmentDto,
+    CreateArticleDto, DownloadArticleAttachmentDto, GetArticleAnalyticsDto, GetArticleDto,
+    RemoveArticleAttachmentDto, RemoveArticleDto, SearchArticleDto, UnlockArticleDto,
+    UpdateArticleDto, UploadArticleAttachmentDto,
 };
 use crate::validator::{Validation, ValidationError};
 
@@ -54,6 +54,9 @@ impl Validation<()> for SearchArticleDto {
         if let Some(full_text) = &self.full_text {
             crate::validator::common::article::validate_full_text(full_text)?;
         }
+        if self.cursor.is_some() && self.page.is_some() {
+            return Err(ValidationError::validation("游标分页不能同时指定分页页码"));
+        }
         Ok(())
     }
 }
@@ -100,3 +103,18 @@ impl Validation<()> for DownloadArticleAttachmentDto {
         Ok(())
     }
 }
+
+impl Validation<()> for GetArticleAnalyticsDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if self.article_id.as_ref().is_some_and(|id| id.is_empty()) {
+            return Err(ValidationError::validation("文章ID不能为空"));
+        }
+        if self.start_date.is_empty() || self.end_date.is_empty() {
+            return Err(ValidationError::validation("开始日期和结束日期不能为空"));
+        }
+        if self.limit == Some(0) {
+            return Err(ValidationError::validation("排行榜条目数量必须大于 0"));
+        }
+        Ok(())
+    }
+}
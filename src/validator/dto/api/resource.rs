@@ -2,7 +2,9 @@ use std::str::FromStr;
 
 use mime::Mime;
 
-use crate::model::dto::api::resource::{DownloadResourceDto, RemoveResourceDto, UploadResourceDto};
+use crate::model::dto::api::resource::{
+    DownloadResourceDto, GetResourceStatsDto, RemoveResourceDto, SignResourceDto, UploadResourceDto,
+};
 use crate::validator::{Validation, ValidationError};
 
 impl Validation<()> for UploadResourceDto {
@@ -43,3 +45,24 @@ impl Validation<()> for RemoveResourceDto {
         Ok(())
     }
 }
+
+impl Validation<()> for SignResourceDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if self.resource_id.is_empty() {
+            return Err(ValidationError::validation("资源ID不能为空"));
+        }
+        if self.ttl == Some(0) {
+            return Err(ValidationError::validation("下载链接有效期必须大于 0"));
+        }
+        Ok(())
+    }
+}
+
+impl Validation<()> for GetResourceStatsDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if self.resource_id.is_empty() {
+            return Err(ValidationError::validation("资源ID不能为空"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use crate::model::dto::api::series::{
+    CreateSeriesDto, GetSeriesDto, RemoveSeriesDto, SetSeriesArticlesDto, UpdateSeriesDto,
+};
+use crate::validator::{Validation, ValidationError};
+
+impl Validation<()> for CreateSeriesDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        crate::validator::common::series::validate_title(&self.title)?;
+        crate::validator::common::series::validate_description(&self.description)?;
+        Ok(())
+    }
+}
+
+impl Validation<()> for UpdateSeriesDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if self.series_id.is_empty() {
+            return Err(ValidationError::validation("系列ID不能为空"));
+        }
+        crate::validator::common::series::validate_title(&self.title)?;
+        crate::validator::common::series::validate_description(&self.description)?;
+        Ok(())
+    }
+}
+
+impl Validation<()> for RemoveSeriesDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if self.series_id.is_empty() {
+            return Err(ValidationError::validation("系列ID不能为空"));
+        }
+        Ok(())
+    }
+}
+
+impl Validation<()> for GetSeriesDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if self.series_id.is_empty() {
+            return Err(ValidationError::validation("系列ID不能为空"));
+        }
+        Ok(())
+    }
+}
+
+impl Validation<()> for SetSeriesArticlesDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if self.series_id.is_empty() {
+            return Err(ValidationError::validation("系列ID不能为空"));
+        }
+        if self.article_ids.iter().any(|id| id.is_empty()) {
+            return Err(ValidationError::validation("文章ID不能为空"));
+        }
+        Ok(())
+    }
+}
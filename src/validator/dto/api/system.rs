@@ -1,4 +1,6 @@
-use crate::model::dto::api::system::{SetLogLevelDto, SetShutdownTimeoutDto};
+use crate::model::dto::api::system::{
+    InspectCacheDto, RemoveCacheDto, SetLogLevelDto, SetShutdownTimeoutDto,
+};
 use crate::validator::{Validation, ValidationError};
 
 impl Validation<()> for SetLogLevelDto {
@@ -15,3 +17,24 @@ impl Validation<()> for SetShutdownTimeoutDto {
         Ok(())
     }
 }
+
+impl Validation<()> for InspectCacheDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if self.kind.is_empty() {
+            return Err(ValidationError::validation("缓存类型不能为空"));
+        }
+        if self.id.is_empty() {
+            return Err(ValidationError::validation("缓存ID不能为空"));
+        }
+        Ok(())
+    }
+}
+
+impl Validation<()> for RemoveCacheDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if self.kind.is_empty() {
+            return Err(ValidationError::validation("缓存类型不能为空"));
+        }
+        Ok(())
+    }
+}
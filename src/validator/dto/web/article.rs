@@ -1,6 +1,6 @@
 use crate::model::dto::web::article::{
-    CreateArticleSubmitDto, GetArticleDto, SearchArticleDto, UnlockArticleDto, UpdateArticleDto,
-    UpdateArticleSubmitDto,
+    ArchiveArticleDto, CreateArticleSubmitDto, GetArticleDto, SearchArticleDto, UnlockArticleDto,
+    UpdateArticleDto, UpdateArticleSubmitDto,
 };
 use crate::validator::{Validation, ValidationError};
 
@@ -13,6 +13,18 @@ impl Validation<()> for SearchArticleDto {
     }
 }
 
+impl Validation<()> for ArchiveArticleDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if !(1970..=9999).contains(&self.year) {
+            return Err(ValidationError::validation("归档年份错误"));
+        }
+        if self.month.is_some_and(|month| !(1..=12).contains(&month)) {
+            return Err(ValidationError::validation("归档月份错误"));
+        }
+        Ok(())
+    }
+}
+
 impl Validation<()> for GetArticleDto {
     fn validate(&self, _context: &()) -> Result<(), ValidationError> {
         if self.article_id.is_empty() {
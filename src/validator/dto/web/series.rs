@@ -0,0 +1,11 @@
+use crate::model::dto::web::series::GetSeriesDto;
+use crate::validator::{Validation, ValidationError};
+
+impl Validation<()> for GetSeriesDto {
+    fn validate(&self, _context: &()) -> Result<(), ValidationError> {
+        if self.series_id.is_empty() {
+            return Err(ValidationError::validation("系列ID不能为空"));
+        }
+        Ok(())
+    }
+}
@@ -87,4 +87,12 @@ function alert_error(response) {
         return false;
     }
     return true;
-}
\ No newline at end of file
+}
+// 携带访客 Cookie 回传确认请求，确认浏览器接受 Cookie 后本次页面访问才会计入统计
+function api_confirm_visitor() {
+    if (navigator.sendBeacon) {
+        navigator.sendBeacon("/_visitor/confirm");
+    } else {
+        fetch("/_visitor/confirm", { method: "POST", keepalive: true }).catch(() => {});
+    }
+}